@@ -0,0 +1,163 @@
+//! Async client for the Geode index API.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), geode_index_client::ClientError> {
+//! use geode_index_client::Client;
+//!
+//! let client = Client::new("https://api.geode-sdk.org");
+//! let the_mod = client.get_mod("geode.nodeids").await?;
+//! println!("{} has {} versions", the_mod.id, the_mod.versions.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use geode_index_types::{
+    api::{ApiErrorResponse, ApiResponse, PaginatedData},
+    models::{
+        mod_entity::{IndexQueryParams, Mod, UpdateQueryParams, UpdateQueryResponse},
+        mod_version::ModVersion,
+    },
+};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Serialize, de::DeserializeOwned};
+
+pub use geode_index_types as types;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("{} ({status})", .response.error)]
+    Api {
+        status: StatusCode,
+        response: ApiErrorResponse,
+    },
+    #[error("Unexpected response status {0}")]
+    Status(StatusCode),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+#[derive(Serialize)]
+struct PublishPayload<'a> {
+    download_link: &'a str,
+}
+
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(reqwest::Client::new(), base_url)
+    }
+
+    pub fn with_http_client(http: reqwest::Client, base_url: impl Into<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// Authenticates requests with an index access token, required for publishing
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// `GET /v1/mods`
+    pub async fn list_mods(&self, query: &IndexQueryParams) -> Result<PaginatedData<Mod>> {
+        self.send(self.request(reqwest::Method::GET, "/v1/mods").query(query))
+            .await
+    }
+
+    /// `GET /v1/mods/{id}`
+    pub async fn get_mod(&self, id: &str) -> Result<Mod> {
+        self.send(self.request(reqwest::Method::GET, &format!("/v1/mods/{id}")))
+            .await
+    }
+
+    /// `GET /v1/mods/{id}/versions/{version}`. `version` can be `latest`.
+    pub async fn get_version(&self, id: &str, version: &str) -> Result<ModVersion> {
+        self.send(self.request(
+            reqwest::Method::GET,
+            &format!("/v1/mods/{id}/versions/{version}"),
+        ))
+        .await
+    }
+
+    /// `GET /v1/mods/updates`
+    pub async fn check_updates(&self, query: &UpdateQueryParams) -> Result<UpdateQueryResponse> {
+        self.send(
+            self.request(reqwest::Method::GET, "/v1/mods/updates")
+                .query(query),
+        )
+        .await
+    }
+
+    /// Downloads the .geode file of a mod version, following the index's redirect
+    pub async fn download(&self, id: &str, version: &str) -> Result<Vec<u8>> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/v1/mods/{id}/versions/{version}/download"),
+            )
+            .send()
+            .await?;
+        let response = Self::check_status(response).await?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// `POST /v1/mods`, submits a new mod from the .geode file at `download_link`
+    pub async fn publish_mod(&self, download_link: &str) -> Result<Mod> {
+        self.send(
+            self.request(reqwest::Method::POST, "/v1/mods")
+                .json(&PublishPayload { download_link }),
+        )
+        .await
+    }
+
+    /// `POST /v1/mods/{id}/versions`, submits a new version of an existing mod
+    pub async fn publish_version(&self, id: &str, download_link: &str) -> Result<ModVersion> {
+        self.send(
+            self.request(reqwest::Method::POST, &format!("/v1/mods/{id}/versions"))
+                .json(&PublishPayload { download_link }),
+        )
+        .await
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
+        let builder = self
+            .http
+            .request(method, format!("{}{path}", self.base_url));
+
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, builder: RequestBuilder) -> Result<T> {
+        let response = Self::check_status(builder.send().await?).await?;
+        let body: ApiResponse<T> = response.json().await?;
+
+        Ok(body.payload)
+    }
+
+    async fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        match response.json::<ApiErrorResponse>().await {
+            Ok(response) => Err(ClientError::Api { status, response }),
+            Err(_) => Err(ClientError::Status(status)),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+const ONE_THOUSAND: f64 = 1_000.0;
+const ONE_MILLION: f64 = 1_000_000.0;
+const ONE_BILLION: f64 = 1_000_000_000.0;
+
+pub fn abbreviate_number(n: i32) -> String {
+    let n = n as f64;
+    if n.abs() >= ONE_BILLION {
+        format!("{:.1}B", n / ONE_BILLION)
+    } else if n.abs() >= ONE_MILLION {
+        format!("{:.1}M", n / ONE_MILLION)
+    } else if n.abs() >= ONE_THOUSAND {
+        format!("{:.1}K", n / ONE_THOUSAND)
+    } else {
+        format!("{:.0}", n)
+    }
+}
+
+/// Inverse of [`abbreviate_number`], rounded to the abbreviation's precision
+pub fn parse_abbreviated(s: &str) -> Option<i32> {
+    let (number, multiplier) = match s.chars().last()? {
+        'B' => (&s[..s.len() - 1], ONE_BILLION),
+        'M' => (&s[..s.len() - 1], ONE_MILLION),
+        'K' => (&s[..s.len() - 1], ONE_THOUSAND),
+        _ => (s, 1.0),
+    };
+
+    let n = number.parse::<f64>().ok()? * multiplier;
+    (n.is_finite() && n.abs() <= i32::MAX as f64).then(|| n.round() as i32)
+}
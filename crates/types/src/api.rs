@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PaginatedData<T> {
+    pub data: Vec<T>,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ApiResponse<T> {
+    pub error: String,
+    pub payload: T,
+}
+
+/// Stable, machine-readable identifier for an error response.
+/// Clients should match on these instead of the human-readable message.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    BadRequest,
+    InvalidQuery,
+    InvalidJson,
+    ValidationFailed,
+    Unauthenticated,
+    InvalidToken,
+    AuthPending,
+    Forbidden,
+    NotFound,
+    ModNotFound,
+    VersionNotFound,
+    Conflict,
+    ModExists,
+    VersionExists,
+    InvalidModJson,
+    InvalidModFile,
+    InvalidBinaries,
+    LogoInvalid,
+    ScreenshotInvalid,
+    HashMismatch,
+    ModFileFetchFailed,
+    ModFileTooLarge,
+    InvalidPlatform,
+    TooManyRequests,
+    ExternalServiceError,
+    InternalError,
+    /// A code added in a newer version of the server than this crate knows of
+    #[serde(other)]
+    Unknown,
+}
+
+/// A single failed validation rule, as reported by `validator`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FieldError {
+    /// Path to the offending field, e.g. `links.website` or `dependencies[0].id`
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+/// Body of every error response
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ApiErrorResponse {
+    pub error: String,
+    pub code: ErrorCode,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+    pub payload: String,
+}
+
+pub fn create_download_link(app_url: &str, mod_id: &str, version: &str) -> String {
+    format!(
+        "{}/v1/mods/{}/versions/{}/download",
+        app_url, mod_id, version
+    )
+}
@@ -0,0 +1,8 @@
+//! Request and response types of the Geode index API, shared between the
+//! server and Rust clients. Enable the `sqlx` and `utoipa` features to get
+//! the database and OpenAPI derives the server uses.
+
+pub mod abbreviate;
+pub mod api;
+pub mod models;
+pub mod serde;
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// One version's entry in `GET /v1/mods/{id}/changelog`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ChangelogEntry {
+    pub version: String,
+    /// The part of changelog.md about this version, or all of it when it has no
+    /// heading for the version
+    pub changelog: Option<String>,
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ResponseDependency {
+    pub mod_id: String,
+    pub version: String,
+    pub importance: DependencyImportance,
+    pub required: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "dependency_importance", rename_all = "lowercase")
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyImportance {
+    Suggested,
+    Recommended,
+    #[default]
+    Required,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Deprecation {
+    pub id: i32,
+    pub mod_id: String,
+    pub by: Vec<String>,
+    pub reason: String,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Permissions of a non-owner developer on a mod. Each role includes the ones before it.
+/// Owners can do everything regardless of role.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(rename_all = "lowercase", type_name = "mod_developer_role")
+)]
+pub enum ModDeveloperRole {
+    /// Can see pending and rejected versions
+    Viewer,
+    /// Can manage deprecations
+    Editor,
+    /// Can publish new versions
+    Maintainer,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModDeveloper {
+    pub id: i32,
+    pub username: String,
+    pub display_name: String,
+    pub is_owner: bool,
+    pub role: ModDeveloperRole,
+}
@@ -0,0 +1,70 @@
+use crate::abbreviate::{abbreviate_number, parse_abbreviated};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DownloadCount {
+    count: i32,
+    abbreviate: bool,
+}
+
+impl DownloadCount {
+    pub const fn new(count: i32) -> Self {
+        Self {
+            count,
+            abbreviate: false,
+        }
+    }
+
+    pub const fn get(&self) -> i32 {
+        self.count
+    }
+
+    pub fn set_abbreviated(&mut self, abbreviate: bool) {
+        self.abbreviate = abbreviate;
+    }
+}
+
+impl From<i32> for DownloadCount {
+    fn from(count: i32) -> Self {
+        Self::new(count)
+    }
+}
+
+impl Serialize for DownloadCount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.abbreviate {
+            serializer.serialize_str(&abbreviate_number(self.count))
+        } else {
+            serializer.serialize_i32(self.count)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DownloadCount {
+    /// Accepts both plain counts and abbreviated ones (`?abbreviate=true`).
+    /// Abbreviated counts are only as precise as the abbreviation.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Count(i32),
+            Abbreviated(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Count(count) => Ok(Self::new(count)),
+            Repr::Abbreviated(s) => parse_abbreviated(&s)
+                .map(|count| Self {
+                    count,
+                    abbreviate: true,
+                })
+                .ok_or_else(|| de::Error::custom(format!("invalid download count {s}"))),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Why a download was flagged by the download analyzer
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "download_flag_reason", rename_all = "snake_case")
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadFlagReason {
+    /// One of many downloads of the version from the same subnet on the same day
+    Subnet,
+    /// Part of an unusually large number of downloads right after the version was published
+    PublishBurst,
+    /// The client didn't send a user agent
+    NoUserAgent,
+}
+
+/// Flagged downloads of a mod version that haven't been cleaned up yet.
+/// A download can be flagged for several reasons.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FlaggedDownloads {
+    pub mod_id: String,
+    pub version: String,
+    /// Downloads of the version that are still kept
+    pub total: i64,
+    pub flagged: i64,
+    /// Flagged downloads that were already taken out of the download counts
+    pub discounted: i64,
+    pub subnet: i64,
+    pub publish_burst: i64,
+    pub no_user_agent: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DiscountedDownloads {
+    pub mod_id: String,
+    /// Subtracted from the versions' download counts
+    pub version_downloads: i64,
+    /// Subtracted from the mod's download count
+    pub mod_downloads: i64,
+}
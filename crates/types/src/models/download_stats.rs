@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum StatsGranularity {
+    #[default]
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+impl StatsGranularity {
+    /// Name of the unit as accepted by Postgres' `date_trunc`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatsGranularity::Day => "day",
+            StatsGranularity::Week => "week",
+            StatsGranularity::Month => "month",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DownloadStatsPoint {
+    /// First day of the period
+    pub date: NaiveDate,
+    pub downloads: i64,
+    /// Downloads per version, only shown to the mod's developers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<BTreeMap<String, i64>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModDownloadStats {
+    pub mod_id: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub granularity: StatsGranularity,
+    /// Downloads counted in the range
+    pub total: i64,
+    pub points: Vec<DownloadStatsPoint>,
+    /// Only shown to the mod's developers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdowns: Option<DownloadBreakdowns>,
+}
+
+/// Downloads in the range split by what the client reported. Downloads from
+/// clients that didn't send a value aren't counted here.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DownloadBreakdowns {
+    /// `android` and `mac` count clients that didn't say which architecture they're on
+    pub platforms: BTreeMap<String, i64>,
+    pub gd_versions: BTreeMap<String, i64>,
+    pub loader_versions: BTreeMap<String, i64>,
+}
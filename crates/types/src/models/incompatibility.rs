@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use super::dependency::ResponseDependency;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Replacement {
+    pub id: String,
+    pub version: String,
+    #[serde(skip_serializing, default)]
+    pub replacement_id: i32,
+    pub download_link: String,
+    pub dependencies: Vec<ResponseDependency>,
+    pub incompatibilities: Vec<ResponseIncompatibility>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "incompatibility_importance", rename_all = "lowercase")
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IncompatibilityImportance {
+    #[default]
+    Breaking,
+    Conflicting,
+    Superseded,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ResponseIncompatibility {
+    pub mod_id: String,
+    pub version: String,
+    pub importance: IncompatibilityImportance,
+    pub breaking: bool,
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::mod_gd_version::{DetailedGDVersion, VerPlatform};
+use crate::serde::chrono_dt_secs;
+
+/// Release channel of a loader version. Each channel includes the ones before it
+/// when looking up the latest version.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "loader_channel", rename_all = "lowercase")
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LoaderChannel {
+    #[default]
+    Stable,
+    Beta,
+    Nightly,
+}
+
+/// Downloadable build of a loader version
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LoaderAsset {
+    pub platform: VerPlatform,
+    pub url: String,
+    /// Size in bytes
+    pub size: i64,
+    pub sha256: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LoaderVersion {
+    pub version: String,
+    pub tag: String,
+    pub gd: DetailedGDVersion,
+    /// Kept for older clients, true for anything outside the stable channel
+    pub prerelease: bool,
+    #[serde(default)]
+    pub channel: LoaderChannel,
+    pub commit_hash: String,
+    #[serde(default)]
+    pub changelog: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<LoaderAsset>,
+    /// Yanked versions are never returned as the latest version
+    #[serde(default)]
+    pub yanked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yank_reason: Option<String>,
+    #[serde(with = "chrono_dt_secs")]
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,17 @@
+pub mod changelog;
+pub mod dependency;
+pub mod deprecations;
+pub mod developer;
+pub mod download_count;
+pub mod download_flags;
+pub mod download_stats;
+pub mod incompatibility;
+pub mod loader_version;
+pub mod mod_entity;
+pub mod mod_gd_version;
+pub mod mod_link;
+pub mod mod_media;
+pub mod mod_version;
+pub mod mod_version_file;
+pub mod mod_version_status;
+pub mod organization;
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    dependency::ResponseDependency,
+    deprecations::Deprecation,
+    developer::ModDeveloper,
+    download_count::DownloadCount,
+    incompatibility::{Replacement, ResponseIncompatibility},
+    mod_gd_version::{GDVersion, VerPlatform},
+    mod_link::ModLinks,
+    mod_media::ModMedia,
+    mod_version::ModVersion,
+    mod_version_status::ModVersionStatusEnum,
+    organization::Organization,
+};
+use crate::serde::chrono_dt_secs;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Mod {
+    pub id: String,
+    pub repository: Option<String>,
+    pub featured: bool,
+    #[cfg_attr(feature = "utoipa", schema(value_type = i32))]
+    pub download_count: DownloadCount,
+    pub developers: Vec<ModDeveloper>,
+    pub versions: Vec<ModVersion>,
+    pub tags: Vec<String>,
+    pub about: Option<String>,
+    pub changelog: Option<String>,
+    #[serde(with = "chrono_dt_secs")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "chrono_dt_secs")]
+    pub updated_at: DateTime<Utc>,
+    pub links: Option<ModLinks>,
+    /// Organization the mod belongs to, whose members can manage it
+    pub organization: Option<Organization>,
+    /// Only included when fetching a single mod
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<ModMedia>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModUpdate {
+    pub id: String,
+    pub version: String,
+    #[serde(skip_serializing, default)]
+    pub mod_version_id: i32,
+    pub download_link: String,
+    pub replacement: Option<Replacement>,
+    pub dependencies: Vec<ResponseDependency>,
+    pub incompatibilities: Vec<ResponseIncompatibility>,
+}
+
+impl Mod {
+    pub fn set_abbreviated_download_counts(&mut self, abbreviate: bool) {
+        self.download_count.set_abbreviated(abbreviate);
+        for version in &mut self.versions {
+            version.set_abbreviated_download_count(abbreviate);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum IndexSortType {
+    #[default]
+    Downloads,
+    RecentlyUpdated,
+    RecentlyPublished,
+    Oldest,
+    Name,
+    NameReverse,
+    /// Recent downloads, favouring mods that are picking up
+    Trending,
+    /// Downloads in the last 7 days
+    PopularThisWeek,
+}
+
+/// How `about` and `changelog` are returned
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ContentFormat {
+    /// As written in the .geode file
+    #[default]
+    Markdown,
+    /// Sanitized HTML, with Geode's color tags turned into colored spans
+    Html,
+}
+
+/// Query of `GET /v1/mods`
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+pub struct IndexQueryParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub query: Option<String>,
+    #[serde(default)]
+    pub gd: Option<GDVersion>,
+    #[serde(default)]
+    pub platforms: Option<String>,
+    #[serde(default)]
+    pub sort: IndexSortType,
+    pub geode: Option<String>,
+    pub developer: Option<String>,
+    /// Name of the organization the mods belong to
+    pub organization: Option<String>,
+    pub tags: Option<String>,
+    pub featured: Option<bool>,
+    pub jitless: Option<bool>,
+    pub status: Option<ModVersionStatusEnum>,
+}
+
+/// Query of `GET /v1/mods/updates`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+pub struct UpdateQueryParams {
+    /// Mod ids separated by `;`
+    pub ids: String,
+    pub gd: GDVersion,
+    pub platform: VerPlatform,
+    pub geode: String,
+}
+
+/// Response of `GET /v1/mods/updates`. Geode v5 and newer get deprecations alongside the updates.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(untagged)]
+pub enum UpdateQueryResponse {
+    V4(Vec<ModUpdate>),
+    V5 {
+        updates: Vec<ModUpdate>,
+        deprecations: Vec<Deprecation>,
+    },
+}
@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A Geometry Dash version such as `2.2074`, or `*` for mods that work on
+/// any version. Known versions and their ordering live in the `gd_versions`
+/// table, so this only checks the format.
+#[derive(Debug, Serialize, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "sqlx", sqlx(transparent))]
+#[cfg_attr(feature = "utoipa", schema(value_type = String, example = "2.2074"))]
+#[serde(transparent)]
+pub struct GDVersion(String);
+
+impl GDVersion {
+    pub fn all() -> Self {
+        GDVersion("*".into())
+    }
+
+    pub fn is_all(&self) -> bool {
+        self.0 == "*"
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Invalid GD version {0}")]
+pub struct GDVersionParseError(pub String);
+
+impl FromStr for GDVersion {
+    type Err = GDVersionParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valid = s == "*"
+            || s.split_once('.').is_some_and(|(major, minor)| {
+                !major.is_empty()
+                    && !minor.is_empty()
+                    && major.chars().all(|c| c.is_ascii_digit())
+                    && minor.chars().all(|c| c.is_ascii_digit())
+            });
+
+        if valid {
+            Ok(GDVersion(s.into()))
+        } else {
+            Err(GDVersionParseError(s.into()))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GDVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        GDVersion::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::fmt::Display for GDVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A Geometry Dash release known to the index
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GDRelease {
+    pub name: GDVersion,
+    /// Position in release order, newer releases have a higher value
+    pub sort_order: i32,
+    pub released_at: Option<NaiveDate>,
+    /// Platforms the release came out on
+    pub platforms: Vec<VerPlatform>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PlatformParseError {
+    #[error("Invalid platform {0}")]
+    InvalidPlatform(String),
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "gd_ver_platform", rename_all = "lowercase")
+)]
+#[serde(rename_all = "lowercase")]
+pub enum VerPlatform {
+    #[cfg_attr(feature = "sqlx", sqlx(skip))]
+    Android,
+    Android32,
+    Android64,
+    Ios,
+    #[cfg_attr(feature = "sqlx", sqlx(skip))]
+    Mac,
+    #[cfg_attr(feature = "sqlx", sqlx(rename = "mac-arm"))]
+    #[serde(rename = "mac-arm")]
+    MacArm,
+    #[cfg_attr(feature = "sqlx", sqlx(rename = "mac-intel"))]
+    #[serde(rename = "mac-intel")]
+    MacIntel,
+    Win,
+}
+
+impl FromStr for VerPlatform {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "android" => Ok(VerPlatform::Android),
+            "android32" => Ok(VerPlatform::Android32),
+            "android64" => Ok(VerPlatform::Android64),
+            "ios" => Ok(VerPlatform::Ios),
+            "mac" => Ok(VerPlatform::Mac),
+            "mac-arm" => Ok(VerPlatform::MacArm),
+            "mac-intel" => Ok(VerPlatform::MacIntel),
+            "win" => Ok(VerPlatform::Win),
+            "windows" => Ok(VerPlatform::Win),
+            "macos" => Ok(VerPlatform::Mac),
+            _ => Err(()),
+        }
+    }
+}
+
+impl VerPlatform {
+    pub fn parse_query_string(s: &str) -> Result<Vec<VerPlatform>, PlatformParseError> {
+        let mut ret = vec![];
+
+        for x in s.split(',') {
+            let x = x.trim();
+            if x.is_empty() {
+                continue;
+            }
+            let v = VerPlatform::from_str(x)
+                .map_err(|_| PlatformParseError::InvalidPlatform(x.into()))?;
+
+            match v {
+                VerPlatform::Android => {
+                    ret.push(VerPlatform::Android32);
+                    ret.push(VerPlatform::Android64);
+                }
+                VerPlatform::Mac => {
+                    ret.push(VerPlatform::MacArm);
+                    ret.push(VerPlatform::MacIntel);
+                }
+                default => ret.push(default),
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+/// Supported GD version per platform. `android` and `mac` are only read from
+/// mod.json files, responses list the individual architectures instead.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DetailedGDVersion {
+    pub win: Option<GDVersion>,
+    #[serde(skip_serializing)]
+    pub android: Option<GDVersion>,
+    pub android32: Option<GDVersion>,
+    pub android64: Option<GDVersion>,
+    #[serde(skip_serializing)]
+    pub mac: Option<GDVersion>,
+    #[serde(rename = "mac-intel")]
+    pub mac_intel: Option<GDVersion>,
+    #[serde(rename = "mac-arm")]
+    pub mac_arm: Option<GDVersion>,
+    pub ios: Option<GDVersion>,
+}
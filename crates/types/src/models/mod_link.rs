@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModLinks {
+    #[serde(skip_serializing, default)]
+    pub mod_id: String,
+    pub community: Option<String>,
+    pub homepage: Option<String>,
+    pub source: Option<String>,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "mod_media_kind", rename_all = "lowercase")
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ModMediaKind {
+    Screenshot,
+    Video,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "mod_media_source", rename_all = "snake_case")
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ModMediaSource {
+    /// Added through the API
+    Upload,
+    /// Read from the `screenshots` folder of the latest accepted .geode,
+    /// replaced whenever a new version is accepted
+    ModFile,
+}
+
+/// Screenshot or video shown on a mod's page
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModMedia {
+    pub id: i32,
+    pub kind: ModMediaKind,
+    pub source: ModMediaSource,
+    /// Image URL for screenshots, link to the video for videos
+    pub url: String,
+    pub caption: Option<String>,
+    /// Media is shown in ascending order
+    pub position: i32,
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    dependency::ResponseDependency, developer::ModDeveloper, download_count::DownloadCount,
+    incompatibility::ResponseIncompatibility, mod_gd_version::DetailedGDVersion,
+    mod_version_status::ModVersionStatusEnum,
+};
+use crate::{api::create_download_link, serde::chrono_dt_secs};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModVersion {
+    #[serde(skip_serializing, default)]
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub version: String,
+    pub download_link: String,
+    pub hash: String,
+    pub geode: String,
+    #[cfg_attr(feature = "utoipa", schema(value_type = i32))]
+    pub download_count: DownloadCount,
+    pub early_load: bool,
+    pub requires_patching: bool,
+    pub api: bool,
+    pub mod_id: String,
+    pub gd: DetailedGDVersion,
+    pub status: ModVersionStatusEnum,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<ResponseDependency>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incompatibilities: Option<Vec<ResponseIncompatibility>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub developers: Option<Vec<ModDeveloper>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, with = "chrono_dt_secs::option")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "chrono_dt_secs::option")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Admin/developer only - Reason given to status
+    pub info: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Admin/developer only - Direct download to mod
+    pub direct_download_link: Option<String>,
+}
+
+impl ModVersion {
+    pub fn set_abbreviated_download_count(&mut self, abbreviate: bool) {
+        self.download_count.set_abbreviated(abbreviate);
+    }
+
+    /// Points `download_link` at the index's download endpoint
+    fn modify_download_link(&mut self, app_url: &str) {
+        self.download_link = create_download_link(app_url, &self.mod_id, &self.version)
+    }
+
+    /// Hides admin/developer only information unless `keep_information` is set
+    pub fn modify_metadata(&mut self, app_url: &str, keep_information: bool) {
+        if keep_information {
+            self.direct_download_link = Some(self.download_link.clone());
+        } else {
+            self.direct_download_link = None;
+            self.info = None;
+        }
+
+        self.modify_download_link(app_url)
+    }
+}
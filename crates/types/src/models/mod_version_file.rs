@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// An entry of a version's .geode archive, from `GET /v1/mods/{id}/versions/{version}/files`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ModVersionFile {
+    /// Path inside the archive
+    pub name: String,
+    /// Uncompressed size in bytes
+    pub size: i64,
+    /// CRC-32 of the uncompressed contents, as stored in the archive
+    pub crc32: u32,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(rename_all = "lowercase", type_name = "mod_version_status")
+)]
+pub enum ModVersionStatusEnum {
+    Pending,
+    Accepted,
+    Rejected,
+    Unlisted,
+}
@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Organization {
+    pub id: i32,
+    pub name: String,
+    pub display_name: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1 @@
+pub mod chrono_dt_secs;
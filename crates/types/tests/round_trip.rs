@@ -0,0 +1,339 @@
+//! The server serializes responses with these same types, so anything a client
+//! deserializes must serialize back to the exact same JSON.
+
+use chrono::{TimeZone, Utc};
+use geode_index_types::{
+    api::{ApiErrorResponse, ApiResponse, ErrorCode, FieldError, PaginatedData},
+    models::{
+        dependency::{DependencyImportance, ResponseDependency},
+        deprecations::Deprecation,
+        developer::{ModDeveloper, ModDeveloperRole},
+        download_count::DownloadCount,
+        download_flags::DownloadFlagReason,
+        incompatibility::{IncompatibilityImportance, Replacement, ResponseIncompatibility},
+        loader_version::{LoaderAsset, LoaderChannel, LoaderVersion},
+        mod_entity::{
+            ContentFormat, IndexQueryParams, IndexSortType, Mod, ModUpdate, UpdateQueryResponse,
+        },
+        mod_gd_version::{DetailedGDVersion, GDVersion, VerPlatform},
+        mod_link::ModLinks,
+        mod_media::{ModMedia, ModMediaKind, ModMediaSource},
+        mod_version::ModVersion,
+        mod_version_file::ModVersionFile,
+        mod_version_status::ModVersionStatusEnum,
+        organization::Organization,
+    },
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+fn assert_round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Value {
+    let serialized = serde_json::to_value(value).unwrap();
+    let parsed: T = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), serialized);
+    serialized
+}
+
+fn gd_version(s: &str) -> GDVersion {
+    s.parse().unwrap()
+}
+
+fn gd() -> DetailedGDVersion {
+    DetailedGDVersion {
+        win: Some(gd_version("2.206")),
+        android: Some(gd_version("2.206")),
+        android32: Some(gd_version("2.206")),
+        android64: Some(gd_version("2.206")),
+        mac: None,
+        mac_intel: Some(gd_version("2.207")),
+        mac_arm: Some(gd_version("2.207")),
+        ios: None,
+    }
+}
+
+fn dependency() -> ResponseDependency {
+    ResponseDependency {
+        mod_id: "geode.node-ids".into(),
+        version: ">=1.0.0".into(),
+        importance: DependencyImportance::Required,
+        required: true,
+    }
+}
+
+fn incompatibility() -> ResponseIncompatibility {
+    ResponseIncompatibility {
+        mod_id: "someone.other".into(),
+        version: "*".into(),
+        importance: IncompatibilityImportance::Breaking,
+        breaking: true,
+    }
+}
+
+fn developer() -> ModDeveloper {
+    ModDeveloper {
+        id: 1,
+        username: "dev".into(),
+        display_name: "Dev".into(),
+        is_owner: true,
+        role: ModDeveloperRole::Maintainer,
+    }
+}
+
+fn version() -> ModVersion {
+    ModVersion {
+        id: 0,
+        name: "Example".into(),
+        description: Some("An example mod".into()),
+        version: "1.2.0".into(),
+        download_link: "https://api.geode-sdk.org/v1/mods/dev.example/versions/1.2.0/download"
+            .into(),
+        hash: "abcdef".into(),
+        geode: "4.0.0".into(),
+        download_count: DownloadCount::new(1234),
+        early_load: false,
+        requires_patching: false,
+        api: false,
+        mod_id: "dev.example".into(),
+        gd: gd(),
+        status: ModVersionStatusEnum::Accepted,
+        dependencies: Some(vec![dependency()]),
+        incompatibilities: Some(vec![incompatibility()]),
+        developers: Some(vec![developer()]),
+        tags: Some(vec!["gameplay".into()]),
+        created_at: Some(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()),
+        updated_at: None,
+        info: None,
+        direct_download_link: None,
+    }
+}
+
+fn the_mod() -> Mod {
+    Mod {
+        id: "dev.example".into(),
+        repository: Some("https://github.com/dev/example".into()),
+        featured: false,
+        download_count: DownloadCount::new(5678),
+        developers: vec![developer()],
+        versions: vec![version()],
+        tags: vec!["gameplay".into()],
+        about: Some("# About".into()),
+        changelog: None,
+        created_at: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        updated_at: Utc.with_ymd_and_hms(2024, 2, 3, 4, 5, 6).unwrap(),
+        links: Some(ModLinks {
+            mod_id: String::new(),
+            community: Some("https://discord.gg/example".into()),
+            homepage: None,
+            source: Some("https://github.com/dev/example".into()),
+        }),
+        organization: Some(Organization {
+            id: 1,
+            name: "studio".into(),
+            display_name: "Studio".into(),
+            created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        }),
+        media: vec![ModMedia {
+            id: 1,
+            kind: ModMediaKind::Screenshot,
+            source: ModMediaSource::ModFile,
+            url: "https://example.com/storage/screenshots/ab/ab.png".into(),
+            caption: Some("Main menu".into()),
+            position: 0,
+        }],
+    }
+}
+
+fn update() -> ModUpdate {
+    ModUpdate {
+        id: "dev.example".into(),
+        version: "1.2.0".into(),
+        mod_version_id: 0,
+        download_link: "https://example.com/dev.example.geode".into(),
+        replacement: Some(Replacement {
+            id: "dev.example2".into(),
+            version: "2.0.0".into(),
+            replacement_id: 0,
+            download_link: "https://example.com/dev.example2.geode".into(),
+            dependencies: vec![dependency()],
+            incompatibilities: vec![],
+        }),
+        dependencies: vec![dependency()],
+        incompatibilities: vec![incompatibility()],
+    }
+}
+
+#[test]
+fn mod_round_trip() {
+    let value = assert_round_trip(&ApiResponse {
+        error: String::new(),
+        payload: PaginatedData {
+            data: vec![the_mod()],
+            count: 1,
+        },
+    });
+
+    let m = &value["payload"]["data"][0];
+    assert_eq!(m["created_at"], "2024-01-02T03:04:05Z");
+    assert_eq!(m["versions"][0]["gd"]["android32"], "2.206");
+    assert!(m["versions"][0]["gd"].get("android").is_none());
+    assert!(m["versions"][0].get("id").is_none());
+    assert!(m["links"].get("mod_id").is_none());
+}
+
+#[test]
+fn abbreviated_download_count_round_trip() {
+    let mut m = the_mod();
+    m.set_abbreviated_download_counts(true);
+
+    let value = assert_round_trip(&m);
+    assert_eq!(value["download_count"], "5.7K");
+    assert_eq!(value["versions"][0]["download_count"], "1.2K");
+}
+
+#[test]
+fn version_metadata_round_trip() {
+    let mut v = version();
+    v.info = Some("Looks good".into());
+    v.modify_metadata("https://api.geode-sdk.org", true);
+
+    let value = assert_round_trip(&v);
+    assert_eq!(value["info"], "Looks good");
+    assert!(value["direct_download_link"].is_string());
+}
+
+#[test]
+fn update_round_trip() {
+    let v4 = assert_round_trip(&UpdateQueryResponse::V4(vec![update()]));
+    assert!(v4.is_array());
+
+    let v5 = assert_round_trip(&UpdateQueryResponse::V5 {
+        updates: vec![update()],
+        deprecations: vec![Deprecation {
+            id: 1,
+            mod_id: "dev.example".into(),
+            by: vec!["dev.example2".into()],
+            reason: "Renamed".into(),
+        }],
+    });
+    assert!(v5["updates"].is_array());
+
+    let parsed: UpdateQueryResponse = serde_json::from_value(v5).unwrap();
+    assert!(matches!(parsed, UpdateQueryResponse::V5 { .. }));
+}
+
+#[test]
+fn loader_version_round_trip() {
+    let value = assert_round_trip(&LoaderVersion {
+        version: "4.0.0".into(),
+        tag: "v4.0.0".into(),
+        gd: gd(),
+        prerelease: true,
+        channel: LoaderChannel::Beta,
+        commit_hash: "0123456".into(),
+        changelog: Some("## v4.0.0".into()),
+        assets: vec![LoaderAsset {
+            platform: VerPlatform::Win,
+            url: "https://example.com/geode-v4.0.0-win.zip".into(),
+            size: 1024,
+            sha256: "0".repeat(64),
+        }],
+        yanked: false,
+        yank_reason: None,
+        created_at: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+    });
+    assert_eq!(value["channel"], "beta");
+    assert!(value.get("yank_reason").is_none());
+}
+
+#[test]
+fn version_files_round_trip() {
+    let value = assert_round_trip(&ApiResponse {
+        error: String::new(),
+        payload: vec![ModVersionFile {
+            name: "resources/logo.png".into(),
+            size: 4096,
+            crc32: 0xDEADBEEF,
+        }],
+    });
+    assert_eq!(value["payload"][0]["crc32"], 3735928559u32);
+}
+
+#[test]
+fn error_round_trip() {
+    let value = assert_round_trip(&ApiErrorResponse {
+        error: "Validation failed".into(),
+        code: ErrorCode::ValidationFailed,
+        details: vec![FieldError {
+            field: "links.website".into(),
+            code: "url".into(),
+            message: "must be a valid URL".into(),
+        }],
+        payload: String::new(),
+    });
+    assert_eq!(value["code"], "validation_failed");
+
+    let value = assert_round_trip(&ApiErrorResponse {
+        error: "Mod dev.example not found".into(),
+        code: ErrorCode::ModNotFound,
+        details: vec![],
+        payload: String::new(),
+    });
+    assert!(value.get("details").is_none());
+}
+
+#[test]
+fn unknown_error_code() {
+    let response: ApiErrorResponse = serde_json::from_value(json!({
+        "error": "Something new went wrong",
+        "code": "some_future_code",
+        "payload": "",
+    }))
+    .unwrap();
+    assert_eq!(response.code, ErrorCode::Unknown);
+    assert_eq!(response.error, "Something new went wrong");
+}
+
+#[test]
+fn enum_representations() {
+    assert_eq!(json!(gd_version("2.2074")), json!("2.2074"));
+    assert!(GDVersion::all().is_all());
+    assert!(serde_json::from_value::<GDVersion>(json!("2.2082")).is_ok());
+    assert!(serde_json::from_value::<GDVersion>(json!("2.")).is_err());
+    assert!(serde_json::from_value::<GDVersion>(json!("latest")).is_err());
+    assert_eq!(json!(VerPlatform::MacArm), json!("mac-arm"));
+    assert_eq!(json!(ModVersionStatusEnum::Unlisted), json!("unlisted"));
+    assert_eq!(
+        json!(IndexSortType::RecentlyUpdated),
+        json!("recently_updated")
+    );
+    assert_eq!(
+        json!(IndexSortType::PopularThisWeek),
+        json!("popular_this_week")
+    );
+    assert_eq!(
+        json!(DownloadFlagReason::PublishBurst),
+        json!("publish_burst")
+    );
+    assert_eq!(json!(ContentFormat::Html), json!("html"));
+
+    assert_eq!(
+        VerPlatform::parse_query_string("android, mac,win").unwrap(),
+        vec![
+            VerPlatform::Android32,
+            VerPlatform::Android64,
+            VerPlatform::MacArm,
+            VerPlatform::MacIntel,
+            VerPlatform::Win,
+        ]
+    );
+}
+
+#[test]
+fn index_query_params_round_trip() {
+    assert_round_trip(&IndexQueryParams {
+        query: Some("example".into()),
+        sort: IndexSortType::Name,
+        gd: Some(gd_version("2.206")),
+        ..Default::default()
+    });
+}
@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+ALTER TABLE refresh_tokens DROP COLUMN auth_token_id;
+
+ALTER TABLE auth_tokens
+    DROP COLUMN id,
+    DROP COLUMN created_at,
+    DROP COLUMN last_used_at,
+    DROP COLUMN ip,
+    DROP COLUMN user_agent;
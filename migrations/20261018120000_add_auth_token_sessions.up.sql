@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+ALTER TABLE auth_tokens
+    ADD COLUMN id SERIAL NOT NULL,
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN last_used_at TIMESTAMPTZ,
+    ADD COLUMN ip INET,
+    ADD COLUMN user_agent TEXT;
+
+ALTER TABLE auth_tokens ADD CONSTRAINT auth_tokens_id_key UNIQUE (id);
+
+ALTER TABLE refresh_tokens
+    ADD COLUMN auth_token_id INTEGER,
+    ADD CONSTRAINT refresh_tokens_auth_token_id_fkey
+        FOREIGN KEY (auth_token_id) REFERENCES auth_tokens(id) ON DELETE CASCADE;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS mod_ownership_transfers;
+DROP TYPE IF EXISTS mod_transfer_status;
@@ -0,0 +1,25 @@
+-- Add up migration script here
+
+CREATE TYPE mod_transfer_status AS ENUM ('pending', 'accepted', 'rejected', 'cancelled', 'forced');
+
+CREATE TABLE mod_ownership_transfers (
+    id SERIAL PRIMARY KEY NOT NULL,
+    mod_id TEXT NOT NULL,
+    from_developer_id INTEGER,
+    to_developer_id INTEGER NOT NULL,
+    initiated_by INTEGER,
+    status mod_transfer_status NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    FOREIGN KEY (mod_id) REFERENCES mods(id) ON UPDATE CASCADE ON DELETE CASCADE,
+    FOREIGN KEY (from_developer_id) REFERENCES developers(id) ON DELETE SET NULL,
+    FOREIGN KEY (to_developer_id) REFERENCES developers(id) ON DELETE CASCADE,
+    FOREIGN KEY (initiated_by) REFERENCES developers(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_mod_ownership_transfers_mod_id ON mod_ownership_transfers(mod_id);
+CREATE INDEX idx_mod_ownership_transfers_to_developer_id ON mod_ownership_transfers(to_developer_id);
+-- Only one transfer can be waiting for an answer at a time
+CREATE UNIQUE INDEX idx_mod_ownership_transfers_pending
+    ON mod_ownership_transfers(mod_id)
+    WHERE status = 'pending';
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+ALTER TABLE mods_developers DROP COLUMN role;
+DROP TYPE IF EXISTS mod_developer_role;
@@ -0,0 +1,7 @@
+-- Add up migration script here
+
+-- Declared from least to most privileged, so roles can be compared
+CREATE TYPE mod_developer_role AS ENUM ('viewer', 'editor', 'maintainer');
+
+ALTER TABLE mods_developers
+    ADD COLUMN role mod_developer_role NOT NULL DEFAULT 'maintainer';
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+ALTER TABLE mods DROP COLUMN organization_id;
+DROP TABLE IF EXISTS organization_members;
+DROP TABLE IF EXISTS organizations;
+DROP TYPE IF EXISTS organization_role;
@@ -0,0 +1,31 @@
+-- Add up migration script here
+
+-- Declared from least to most privileged, so roles can be compared
+CREATE TYPE organization_role AS ENUM ('member', 'admin', 'owner');
+
+CREATE TABLE organizations (
+    id SERIAL PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_organizations_name ON organizations(LOWER(name));
+
+CREATE TABLE organization_members (
+    organization_id INTEGER NOT NULL,
+    developer_id INTEGER NOT NULL,
+    role organization_role NOT NULL DEFAULT 'member',
+    PRIMARY KEY (organization_id, developer_id),
+    FOREIGN KEY (organization_id) REFERENCES organizations(id) ON DELETE CASCADE,
+    FOREIGN KEY (developer_id) REFERENCES developers(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_organization_members_developer_id ON organization_members(developer_id);
+
+ALTER TABLE mods
+    ADD COLUMN organization_id INTEGER,
+    ADD CONSTRAINT mods_organization_id_fkey
+        FOREIGN KEY (organization_id) REFERENCES organizations(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_mods_organization_id ON mods(organization_id);
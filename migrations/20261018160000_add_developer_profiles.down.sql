@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+ALTER TABLE developers
+    DROP COLUMN bio,
+    DROP COLUMN avatar,
+    DROP COLUMN website,
+    DROP COLUMN social_links;
@@ -0,0 +1,7 @@
+-- Add up migration script here
+
+ALTER TABLE developers
+    ADD COLUMN bio TEXT,
+    ADD COLUMN avatar TEXT,
+    ADD COLUMN website TEXT,
+    ADD COLUMN social_links TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,28 @@
+-- Add down migration script here
+
+DO $$
+BEGIN
+	EXECUTE format(
+		'CREATE TYPE gd_version AS ENUM (%s)',
+		(SELECT string_agg(quote_literal(name), ', ' ORDER BY sort_order) FROM gd_versions)
+	);
+END
+$$;
+
+ALTER TABLE gd_version_aliases DROP CONSTRAINT gd_version_aliases_version_name_fkey;
+ALTER TABLE geode_versions
+	DROP CONSTRAINT geode_versions_mac_fkey,
+	DROP CONSTRAINT geode_versions_win_fkey,
+	DROP CONSTRAINT geode_versions_android_fkey,
+	DROP CONSTRAINT geode_versions_ios_fkey;
+ALTER TABLE mod_gd_versions DROP CONSTRAINT mod_gd_versions_gd_fkey;
+
+ALTER TABLE gd_version_aliases ALTER COLUMN version_name TYPE gd_version USING version_name::gd_version;
+ALTER TABLE geode_versions
+	ALTER COLUMN mac TYPE gd_version USING mac::gd_version,
+	ALTER COLUMN win TYPE gd_version USING win::gd_version,
+	ALTER COLUMN android TYPE gd_version USING android::gd_version,
+	ALTER COLUMN ios TYPE gd_version USING ios::gd_version;
+ALTER TABLE mod_gd_versions ALTER COLUMN gd TYPE gd_version USING gd::gd_version;
+
+DROP TABLE gd_versions;
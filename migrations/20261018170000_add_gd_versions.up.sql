@@ -0,0 +1,53 @@
+-- Add up migration script here
+
+CREATE TABLE gd_versions (
+	name TEXT PRIMARY KEY NOT NULL,
+	sort_order INTEGER NOT NULL,
+	released_at DATE,
+	platforms gd_ver_platform[] NOT NULL DEFAULT '{}',
+	created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	CONSTRAINT gd_versions_sort_order_key UNIQUE (sort_order) DEFERRABLE INITIALLY IMMEDIATE
+);
+
+INSERT INTO gd_versions (name, sort_order)
+SELECT e.enumlabel, ROW_NUMBER() OVER (ORDER BY e.enumsortorder) - 1
+FROM pg_enum e
+JOIN pg_type t ON t.oid = e.enumtypid
+WHERE t.typname = 'gd_version';
+
+-- platform availability is taken from known builds and what mods and loader releases already target
+UPDATE gd_versions g SET platforms = COALESCE((
+	SELECT array_agg(DISTINCT p ORDER BY p) FROM (
+		SELECT platform AS p FROM mod_gd_versions WHERE gd::text = g.name
+		UNION SELECT 'win'::gd_ver_platform FROM geode_versions WHERE win::text = g.name
+		UNION SELECT unnest('{mac-arm,mac-intel}'::gd_ver_platform[]) FROM geode_versions WHERE mac::text = g.name
+		UNION SELECT unnest('{android32,android64}'::gd_ver_platform[]) FROM geode_versions WHERE android::text = g.name
+		UNION SELECT 'ios'::gd_ver_platform FROM geode_versions WHERE ios::text = g.name
+		UNION SELECT 'mac-arm'::gd_ver_platform FROM gd_version_aliases WHERE mac_arm_uuid IS NOT NULL AND version_name::text = g.name
+		UNION SELECT 'mac-intel'::gd_ver_platform FROM gd_version_aliases WHERE mac_intel_uuid IS NOT NULL AND version_name::text = g.name
+		UNION SELECT unnest('{android32,android64}'::gd_ver_platform[]) FROM gd_version_aliases WHERE android_manifest_id IS NOT NULL AND version_name::text = g.name
+		UNION SELECT 'win'::gd_ver_platform FROM gd_version_aliases WHERE windows_timestamp IS NOT NULL AND version_name::text = g.name
+		UNION SELECT 'ios'::gd_ver_platform FROM gd_version_aliases WHERE ios_bundle_version IS NOT NULL AND version_name::text = g.name
+	) x
+), '{}')
+WHERE g.name <> '*';
+
+ALTER TABLE mod_gd_versions ALTER COLUMN gd TYPE TEXT USING gd::text;
+ALTER TABLE geode_versions
+	ALTER COLUMN mac TYPE TEXT USING mac::text,
+	ALTER COLUMN win TYPE TEXT USING win::text,
+	ALTER COLUMN android TYPE TEXT USING android::text,
+	ALTER COLUMN ios TYPE TEXT USING ios::text;
+ALTER TABLE gd_version_aliases ALTER COLUMN version_name TYPE TEXT USING version_name::text;
+
+ALTER TABLE mod_gd_versions
+	ADD CONSTRAINT mod_gd_versions_gd_fkey FOREIGN KEY (gd) REFERENCES gd_versions(name) ON UPDATE CASCADE;
+ALTER TABLE geode_versions
+	ADD CONSTRAINT geode_versions_mac_fkey FOREIGN KEY (mac) REFERENCES gd_versions(name) ON UPDATE CASCADE,
+	ADD CONSTRAINT geode_versions_win_fkey FOREIGN KEY (win) REFERENCES gd_versions(name) ON UPDATE CASCADE,
+	ADD CONSTRAINT geode_versions_android_fkey FOREIGN KEY (android) REFERENCES gd_versions(name) ON UPDATE CASCADE,
+	ADD CONSTRAINT geode_versions_ios_fkey FOREIGN KEY (ios) REFERENCES gd_versions(name) ON UPDATE CASCADE;
+ALTER TABLE gd_version_aliases
+	ADD CONSTRAINT gd_version_aliases_version_name_fkey FOREIGN KEY (version_name) REFERENCES gd_versions(name) ON UPDATE CASCADE ON DELETE CASCADE;
+
+DROP TYPE gd_version;
@@ -0,0 +1,15 @@
+-- Add down migration script here
+
+DROP TABLE geode_version_assets;
+
+ALTER TABLE geode_versions ADD COLUMN prerelease BOOLEAN DEFAULT FALSE NOT NULL;
+
+UPDATE geode_versions SET prerelease = TRUE WHERE channel <> 'stable';
+
+ALTER TABLE geode_versions
+	DROP COLUMN channel,
+	DROP COLUMN changelog,
+	DROP COLUMN yanked,
+	DROP COLUMN yank_reason;
+
+DROP TYPE loader_channel;
@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+CREATE TYPE loader_channel AS ENUM ('stable', 'beta', 'nightly');
+
+ALTER TABLE geode_versions
+	ADD COLUMN channel loader_channel NOT NULL DEFAULT 'stable',
+	ADD COLUMN changelog TEXT,
+	ADD COLUMN yanked BOOLEAN NOT NULL DEFAULT FALSE,
+	ADD COLUMN yank_reason TEXT;
+
+UPDATE geode_versions SET channel = 'beta' WHERE prerelease;
+
+ALTER TABLE geode_versions DROP COLUMN prerelease;
+
+CREATE TABLE geode_version_assets (
+	id SERIAL PRIMARY KEY NOT NULL,
+	tag TEXT NOT NULL REFERENCES geode_versions(tag) ON DELETE CASCADE ON UPDATE CASCADE,
+	platform gd_ver_platform NOT NULL,
+	url TEXT NOT NULL,
+	size BIGINT NOT NULL,
+	sha256 TEXT NOT NULL,
+	UNIQUE (tag, platform)
+);
@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+CREATE TABLE mod_version_download_stats (
+	mod_version_id INTEGER NOT NULL REFERENCES mod_versions(id) ON DELETE CASCADE,
+	day DATE NOT NULL,
+	downloads INTEGER NOT NULL,
+	PRIMARY KEY (mod_version_id, day)
+);
+
+CREATE INDEX idx_mod_version_download_stats_day ON mod_version_download_stats(day);
+
+-- roll up every complete day that is still in mod_downloads
+INSERT INTO mod_version_download_stats (mod_version_id, day, downloads)
+SELECT mod_version_id, (time_downloaded AT TIME ZONE 'UTC')::date, COUNT(*)
+FROM mod_downloads
+WHERE time_downloaded >= date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' - INTERVAL '29 days'
+AND time_downloaded < date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+GROUP BY 1, 2;
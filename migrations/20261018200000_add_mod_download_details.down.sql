@@ -0,0 +1,9 @@
+-- Add down migration script here
+
+DROP TABLE mod_version_download_breakdowns;
+DROP TYPE download_breakdown_kind;
+
+ALTER TABLE mod_downloads
+	DROP COLUMN platform,
+	DROP COLUMN gd_version,
+	DROP COLUMN loader_version;
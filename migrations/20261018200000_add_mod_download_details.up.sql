@@ -0,0 +1,19 @@
+-- Add up migration script here
+
+ALTER TABLE mod_downloads
+	ADD COLUMN platform gd_ver_platform,
+	ADD COLUMN gd_version TEXT,
+	ADD COLUMN loader_version TEXT;
+
+CREATE TYPE download_breakdown_kind AS ENUM ('platform', 'gd', 'loader');
+
+CREATE TABLE mod_version_download_breakdowns (
+	mod_version_id INTEGER NOT NULL REFERENCES mod_versions(id) ON DELETE CASCADE,
+	day DATE NOT NULL,
+	kind download_breakdown_kind NOT NULL,
+	value TEXT NOT NULL,
+	downloads INTEGER NOT NULL,
+	PRIMARY KEY (mod_version_id, day, kind, value)
+);
+
+CREATE INDEX idx_mod_version_download_breakdowns_day ON mod_version_download_breakdowns(day);
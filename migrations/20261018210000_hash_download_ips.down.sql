@@ -0,0 +1,30 @@
+-- Add down migration script here
+
+-- the IPs can't be recovered from the hashes, so the downloads still waiting
+-- for cleanup are lost. Days that weren't rolled up yet are rolled up first.
+INSERT INTO mod_version_download_stats (mod_version_id, day, downloads)
+SELECT mod_version_id, (time_downloaded AT TIME ZONE 'UTC')::date, COUNT(*)
+FROM mod_downloads
+GROUP BY 1, 2
+ON CONFLICT (mod_version_id, day) DO NOTHING;
+
+INSERT INTO mod_version_download_breakdowns (mod_version_id, day, kind, value, downloads)
+SELECT md.mod_version_id, (md.time_downloaded AT TIME ZONE 'UTC')::date, b.kind, b.value, COUNT(*)
+FROM mod_downloads md
+CROSS JOIN LATERAL (VALUES
+	('platform'::download_breakdown_kind, md.platform::text),
+	('gd'::download_breakdown_kind, md.gd_version),
+	('loader'::download_breakdown_kind, md.loader_version)
+) b(kind, value)
+WHERE b.value IS NOT NULL
+GROUP BY 1, 2, 3, 4
+ON CONFLICT (mod_version_id, day, kind, value) DO NOTHING;
+
+DELETE FROM mod_downloads;
+
+ALTER TABLE mod_downloads
+	DROP CONSTRAINT IF EXISTS mod_downloads_pkey,
+	DROP COLUMN IF EXISTS ip,
+	DROP COLUMN ip_hash,
+	ADD COLUMN ip INET NOT NULL,
+	ADD PRIMARY KEY (mod_version_id, ip);
@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- IPs are hashed with keys derived from DOWNLOAD_HASH_SECRET, which never
+-- goes in the database and so can't be used here. Downloads still waiting
+-- for cleanup keep their IP until `job hash-download-ips` hashes it, which
+-- then drops the ip column and makes (mod_version_id, ip_hash) the key.
+ALTER TABLE mod_downloads
+	DROP CONSTRAINT mod_downloads_pkey,
+	ALTER COLUMN ip DROP NOT NULL,
+	ADD COLUMN ip_hash TEXT;
+
+CREATE UNIQUE INDEX idx_mod_downloads_ip_hash ON mod_downloads(mod_version_id, ip_hash);
@@ -0,0 +1,8 @@
+-- Add up migration script here
+
+CREATE TABLE mod_trending_scores (
+	mod_id TEXT PRIMARY KEY REFERENCES mods(id) ON DELETE CASCADE ON UPDATE CASCADE,
+	weekly_downloads BIGINT NOT NULL,
+	trending_score DOUBLE PRECISION NOT NULL,
+	updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
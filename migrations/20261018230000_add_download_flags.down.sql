@@ -0,0 +1,11 @@
+-- Add down migration script here
+
+DROP INDEX idx_mod_downloads_flagged;
+
+ALTER TABLE mod_downloads
+	DROP COLUMN subnet_hash,
+	DROP COLUMN has_user_agent,
+	DROP COLUMN flags,
+	DROP COLUMN discounted;
+
+DROP TYPE download_flag_reason;
@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+CREATE TYPE download_flag_reason AS ENUM ('subnet', 'publish_burst', 'no_user_agent');
+
+ALTER TABLE mod_downloads
+	ADD COLUMN subnet_hash TEXT,
+	ADD COLUMN has_user_agent BOOLEAN,
+	ADD COLUMN flags download_flag_reason[] NOT NULL DEFAULT '{}',
+	ADD COLUMN discounted BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_mod_downloads_flagged ON mod_downloads(mod_version_id)
+	WHERE cardinality(flags) > 0;
@@ -0,0 +1,11 @@
+-- Add up migration script here
+
+CREATE TABLE stats_snapshots (
+	day DATE PRIMARY KEY,
+	total_mod_count BIGINT NOT NULL,
+	total_mod_downloads BIGINT NOT NULL,
+	total_registered_developers BIGINT NOT NULL,
+	pending_mod_versions BIGINT NOT NULL,
+	total_geode_downloads BIGINT NOT NULL,
+	created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
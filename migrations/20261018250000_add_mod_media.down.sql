@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TABLE mod_media;
+DROP TYPE mod_media_source;
+DROP TYPE mod_media_kind;
@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+CREATE TYPE mod_media_kind AS ENUM ('screenshot', 'video');
+CREATE TYPE mod_media_source AS ENUM ('upload', 'mod_file');
+
+CREATE TABLE mod_media (
+	id SERIAL PRIMARY KEY,
+	mod_id TEXT NOT NULL REFERENCES mods(id) ON DELETE CASCADE ON UPDATE CASCADE,
+	kind mod_media_kind NOT NULL,
+	source mod_media_source NOT NULL DEFAULT 'upload',
+	-- path inside public storage for screenshots, link for videos
+	location TEXT NOT NULL,
+	caption TEXT,
+	position INTEGER NOT NULL,
+	created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_mod_media_mod_id ON mod_media(mod_id);
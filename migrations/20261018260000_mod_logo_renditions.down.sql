@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE mod_logo_renditions;
+DROP TYPE logo_format;
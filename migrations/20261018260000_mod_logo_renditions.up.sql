@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+CREATE TYPE logo_format AS ENUM ('png', 'webp');
+
+CREATE TABLE mod_logo_renditions (
+	mod_id TEXT NOT NULL REFERENCES mods(id) ON DELETE CASCADE ON UPDATE CASCADE,
+	size INTEGER NOT NULL,
+	format logo_format NOT NULL,
+	-- path inside public storage
+	path TEXT NOT NULL,
+	PRIMARY KEY (mod_id, size, format)
+);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+ALTER TABLE mods
+	DROP COLUMN about_html,
+	DROP COLUMN changelog_html;
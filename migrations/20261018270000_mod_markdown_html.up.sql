@@ -0,0 +1,6 @@
+-- Add up migration script here
+
+-- Rendered and sanitized copies of about.md and changelog.md, filled in at ingest
+ALTER TABLE mods
+	ADD COLUMN about_html TEXT,
+	ADD COLUMN changelog_html TEXT;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE mod_versions DROP COLUMN changelog;
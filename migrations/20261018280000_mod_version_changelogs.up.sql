@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+ALTER TABLE mod_versions ADD COLUMN changelog TEXT;
+
+-- Until now only the latest accepted version's changelog was kept, on the mod itself
+UPDATE mod_versions mv
+SET changelog = m.changelog
+FROM mods m
+WHERE m.id = mv.mod_id
+AND m.changelog IS NOT NULL
+AND mv.id = (
+	SELECT l.id FROM mod_versions l
+	INNER JOIN mod_version_statuses ls ON ls.id = l.status_id
+	WHERE l.mod_id = m.id
+	AND ls.status = 'accepted'
+	ORDER BY l.id DESC
+	LIMIT 1
+);
@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- Translations of about.md and changelog.md, from about.<lang>.md and changelog.<lang>.md
+CREATE TABLE mod_localizations (
+	mod_id TEXT NOT NULL REFERENCES mods(id) ON DELETE CASCADE ON UPDATE CASCADE,
+	lang TEXT NOT NULL,
+	about TEXT,
+	changelog TEXT,
+	about_html TEXT,
+	changelog_html TEXT,
+	PRIMARY KEY (mod_id, lang)
+);
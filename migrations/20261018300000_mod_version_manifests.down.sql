@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE mod_version_files;
+ALTER TABLE mod_versions DROP COLUMN mod_json;
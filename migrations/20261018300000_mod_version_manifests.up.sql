@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+-- mod.json exactly as submitted
+ALTER TABLE mod_versions ADD COLUMN mod_json TEXT;
+
+-- Entries of the submitted .geode, in archive order. Versions accepted before this
+-- migration aren't backfilled, so they have no mod_json and no listing
+CREATE TABLE mod_version_files (
+	mod_version_id INTEGER NOT NULL REFERENCES mod_versions(id) ON DELETE CASCADE,
+	position INTEGER NOT NULL,
+	name TEXT NOT NULL,
+	size BIGINT NOT NULL,
+	crc32 BIGINT NOT NULL,
+	PRIMARY KEY (mod_version_id, position)
+);
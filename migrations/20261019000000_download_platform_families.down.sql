@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+UPDATE mod_downloads SET platform = NULL WHERE platform IN ('android', 'mac');
+
+ALTER TABLE mod_downloads
+	ALTER COLUMN platform TYPE gd_ver_platform USING platform::gd_ver_platform;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+
+-- Clients that ask for `android` or `mac` don't say which architecture they
+-- run on, so those are kept as is instead of an architecture
+ALTER TABLE mod_downloads
+	ALTER COLUMN platform TYPE TEXT USING platform::text;
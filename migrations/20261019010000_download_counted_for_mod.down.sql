@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE mod_downloads DROP COLUMN counted_for_mod;
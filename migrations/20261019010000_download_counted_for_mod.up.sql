@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+-- Whether the download also added one to mods.download_count, so discounting
+-- it takes back exactly what was counted
+ALTER TABLE mod_downloads ADD COLUMN counted_for_mod BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- best guess for existing downloads: the first one of each hash for a mod
+UPDATE mod_downloads md
+SET counted_for_mod = TRUE
+FROM (
+	SELECT DISTINCT ON (mv.mod_id, d.ip_hash) d.mod_version_id, d.ip_hash
+	FROM mod_downloads d
+	INNER JOIN mod_versions mv ON mv.id = d.mod_version_id
+	WHERE NOT d.discounted
+	ORDER BY mv.mod_id, d.ip_hash, d.time_downloaded
+) first
+WHERE md.mod_version_id = first.mod_version_id
+AND md.ip_hash = first.ip_hash;
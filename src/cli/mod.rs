@@ -18,10 +18,20 @@ enum Commands {
 
 #[derive(Debug, Subcommand)]
 enum JobCommand {
-    /// Cleans up mod_downloads from more than 30 days ago
+    /// Flags suspicious downloads that are still in mod_downloads
+    AnalyzeDownloads,
+    /// Renders logo sizes and formats for mods that don't have them yet
+    BackfillLogos {
+        /// Render again for every mod with a logo
+        #[arg(long)]
+        all: bool,
+    },
+    /// Rolls up daily download counts and cleans up mod_downloads older than DOWNLOAD_DEDUP_DAYS
     CleanupDownloads,
     /// Cleans up auth and refresh tokens that are expired
     CleanupTokens,
+    /// Hashes the IPs of downloads from before IPs were hashed, then drops the plain IPs
+    HashDownloadIps,
     /// Emergency logout for a developer
     LogoutDeveloper {
         /// Username of the developer
@@ -29,6 +39,22 @@ enum JobCommand {
     },
     /// Runs migrations
     Migrate,
+    /// Recomputes the scores behind the trending and popular_this_week sorts
+    RefreshTrending,
+    /// Renders about.md and changelog.md to HTML for mods that don't have it yet
+    RenderMarkdown {
+        /// Render again for every mod, e.g. after changing what the sanitizer allows
+        #[arg(long)]
+        all: bool,
+    },
+    /// Saves today's platform stats for /v1/stats/history
+    SnapshotStats,
+    /// Adds loader versions for new GitHub releases and reports releases that differ from the index
+    SyncLoaderReleases {
+        /// Only report what would be added
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 pub async fn maybe_cli(data: &AppData) -> anyhow::Result<bool> {
@@ -43,9 +69,33 @@ pub async fn maybe_cli(data: &AppData) -> anyhow::Result<bool> {
 
                     Ok(true)
                 }
+                JobCommand::AnalyzeDownloads => {
+                    let mut conn = data.db().acquire().await?;
+                    jobs::analyze_downloads::analyze_downloads(&mut conn).await?;
+
+                    Ok(true)
+                }
+                JobCommand::BackfillLogos { all } => {
+                    let mut conn = data.db().acquire().await?;
+                    jobs::backfill_logos::backfill_logos(data.public_storage(), all, &mut conn)
+                        .await?;
+
+                    Ok(true)
+                }
                 JobCommand::CleanupDownloads => {
                     let mut conn = data.db().acquire().await?;
-                    jobs::cleanup_downloads::cleanup_downloads(&mut conn).await?;
+                    jobs::cleanup_downloads::cleanup_downloads(data.download_dedup_days(), &mut conn)
+                        .await?;
+
+                    Ok(true)
+                }
+                JobCommand::HashDownloadIps => {
+                    let mut conn = data.db().acquire().await?;
+                    jobs::hash_download_ips::hash_download_ips(
+                        data.download_hash_secret(),
+                        &mut conn,
+                    )
+                    .await?;
 
                     Ok(true)
                 }
@@ -55,6 +105,35 @@ pub async fn maybe_cli(data: &AppData) -> anyhow::Result<bool> {
 
                     Ok(true)
                 }
+                JobCommand::SyncLoaderReleases { dry_run } => {
+                    let mut conn = data.db().acquire().await?;
+                    jobs::sync_loader_releases::sync_loader_releases(
+                        data.github(),
+                        dry_run,
+                        &mut conn,
+                    )
+                    .await?;
+
+                    Ok(true)
+                }
+                JobCommand::RefreshTrending => {
+                    let mut conn = data.db().acquire().await?;
+                    jobs::refresh_trending::refresh_trending(&mut conn).await?;
+
+                    Ok(true)
+                }
+                JobCommand::RenderMarkdown { all } => {
+                    let mut conn = data.db().acquire().await?;
+                    jobs::render_markdown::render_markdown(all, &mut conn).await?;
+
+                    Ok(true)
+                }
+                JobCommand::SnapshotStats => {
+                    let mut conn = data.db().acquire().await?;
+                    jobs::snapshot_stats::snapshot_stats(data.github(), &mut conn).await?;
+
+                    Ok(true)
+                }
                 JobCommand::CleanupTokens => {
                     let mut conn = data.db().acquire().await?;
                     jobs::token_cleanup::token_cleanup(&mut conn).await?;
@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use anyhow::Context;
 use moka::future::Cache;
 
 use crate::storage::{PrivateStorage, PublicStorage, StaticStorage};
@@ -22,6 +23,8 @@ pub struct AppData {
     public_storage: PublicStorage,
     private_storage: PrivateStorage,
     disable_downloads: bool,
+    download_dedup_days: u32,
+    download_hash_secret: String,
     max_download_mb: u32,
     port: u16,
     debug: bool,
@@ -33,6 +36,9 @@ pub struct AppData {
 pub struct GitHubClientData {
     client_id: String,
     client_secret: String,
+    api_url: String,
+    loader_repository: String,
+    stats_cache_hours: i64,
 }
 
 pub async fn build_config() -> anyhow::Result<AppData> {
@@ -51,9 +57,28 @@ pub async fn build_config() -> anyhow::Result<AppData> {
     let front_url = dotenvy::var("FRONT_URL").unwrap_or("http://localhost".to_string());
     let github_client = dotenvy::var("GITHUB_CLIENT_ID").unwrap_or("".to_string());
     let github_secret = dotenvy::var("GITHUB_CLIENT_SECRET").unwrap_or("".to_string());
+    let github_api_url = dotenvy::var("GITHUB_API_URL")
+        .unwrap_or("https://api.github.com".to_string())
+        .trim_end_matches('/')
+        .to_string();
+    let loader_repository =
+        dotenvy::var("LOADER_REPOSITORY").unwrap_or("geode-sdk/geode".to_string());
+    let github_stats_cache_hours = dotenvy::var("GITHUB_STATS_CACHE_HOURS")
+        .unwrap_or("24".to_string())
+        .parse::<i64>()
+        .unwrap_or(24);
     let webhook_url = dotenvy::var("DISCORD_WEBHOOK_URL").unwrap_or("".to_string());
     let disable_downloads =
         dotenvy::var("DISABLE_DOWNLOAD_COUNTS").unwrap_or("0".to_string()) == "1";
+    let download_dedup_days = dotenvy::var("DOWNLOAD_DEDUP_DAYS")
+        .unwrap_or("30".to_string())
+        .parse::<u32>()
+        .unwrap_or(30)
+        .max(2);
+    let download_hash_secret = dotenvy::var("DOWNLOAD_HASH_SECRET")
+        .ok()
+        .filter(|x| !x.is_empty())
+        .context("DOWNLOAD_HASH_SECRET must be set")?;
     let max_download_mb = dotenvy::var("MAX_MOD_FILESIZE_MB")
         .unwrap_or("250".to_string())
         .parse::<u32>()
@@ -71,12 +96,17 @@ pub async fn build_config() -> anyhow::Result<AppData> {
         github: GitHubClientData {
             client_id: github_client,
             client_secret: github_secret,
+            api_url: github_api_url,
+            loader_repository,
+            stats_cache_hours: github_stats_cache_hours,
         },
         webhook_url,
         static_storage: StaticStorage::new(app_url.clone()),
         public_storage: PublicStorage::new(app_url.clone()),
         private_storage: PrivateStorage::new(),
         disable_downloads,
+        download_dedup_days,
+        download_hash_secret,
         max_download_mb,
         port,
         debug,
@@ -92,6 +122,20 @@ impl GitHubClientData {
     pub fn client_secret(&self) -> &str {
         &self.client_secret
     }
+
+    /// `owner/name` of the repository loader releases are published on
+    pub fn loader_repository(&self) -> &str {
+        &self.loader_repository
+    }
+
+    pub fn loader_repository_url(&self) -> String {
+        format!("{}/repos/{}", self.api_url, self.loader_repository)
+    }
+
+    /// How long loader download counts from GitHub are reused
+    pub fn stats_cache_hours(&self) -> i64 {
+        self.stats_cache_hours
+    }
 }
 
 impl AppData {
@@ -119,6 +163,16 @@ impl AppData {
         self.disable_downloads
     }
 
+    /// How long a download from the same client is only counted once
+    pub fn download_dedup_days(&self) -> u32 {
+        self.download_dedup_days
+    }
+
+    /// Key material for hashing download IPs. Never stored in the database.
+    pub fn download_hash_secret(&self) -> &[u8] {
+        self.download_hash_secret.as_bytes()
+    }
+
     pub fn max_download_mb(&self) -> u32 {
         self.max_download_mb
     }
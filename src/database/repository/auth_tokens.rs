@@ -1,38 +1,186 @@
 use crate::database::DatabaseError;
-use chrono::{Days, Utc};
+use crate::extractors::client_info::ClientInfo;
+use crate::types::models::session::Session;
+use chrono::{DateTime, Days, Utc};
+use sqlx::types::ipnetwork::IpNetwork;
 use sqlx::PgConnection;
 use uuid::Uuid;
 
+pub struct GeneratedToken {
+    /// Session ID, stable across refreshes
+    pub id: i32,
+    pub token: Uuid,
+}
+
+struct SessionRow {
+    id: i32,
+    created_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    ip: Option<IpNetwork>,
+    user_agent: Option<String>,
+    current: bool,
+}
+
+impl SessionRow {
+    fn into_session(self) -> Session {
+        Session {
+            id: self.id,
+            created_at: self.created_at,
+            last_used_at: self.last_used_at,
+            expires_at: self.expires_at,
+            ip: self.ip.map(|ip| ip.ip().to_string()),
+            user_agent: self.user_agent,
+            current: self.current,
+        }
+    }
+}
+
+fn token_expiry(with_expiry: bool) -> Option<DateTime<Utc>> {
+    if with_expiry {
+        Some(Utc::now().checked_add_days(Days::new(1)).unwrap())
+    } else {
+        None
+    }
+}
+
 /// Assumes developer ID exists
 pub async fn generate_token(
     developer_id: i32,
     with_expiry: bool,
+    client: &ClientInfo,
     conn: &mut PgConnection,
-) -> Result<Uuid, DatabaseError> {
+) -> Result<GeneratedToken, DatabaseError> {
     let token = Uuid::new_v4();
     let hash = sha256::digest(token.to_string());
-    let expiry = {
-        if with_expiry {
-            Some(Utc::now().checked_add_days(Days::new(1)).unwrap())
-        } else {
-            None
-        }
-    };
+    let expiry = token_expiry(with_expiry);
 
-    sqlx::query!(
-        "INSERT INTO auth_tokens(token, developer_id, expires_at)
-        VALUES ($1, $2, $3)",
+    let id = sqlx::query_scalar!(
+        "INSERT INTO auth_tokens(token, developer_id, expires_at, ip, user_agent)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id",
         hash,
         developer_id,
-        expiry
+        expiry,
+        client.ip,
+        client.user_agent
     )
-    .execute(&mut *conn)
+    .fetch_one(&mut *conn)
     .await
     .inspect_err(|e| {
         log::error!("Failed to insert auth_token for developer {developer_id}: {e}")
     })?;
 
-    Ok(token)
+    Ok(GeneratedToken { id, token })
+}
+
+/// Replaces the token of an existing session, keeping its metadata.
+/// Returns None if the session doesn't exist anymore.
+pub async fn rotate_token(
+    id: i32,
+    conn: &mut PgConnection,
+) -> Result<Option<GeneratedToken>, DatabaseError> {
+    let token = Uuid::new_v4();
+    let hash = sha256::digest(token.to_string());
+    let expiry = token_expiry(true);
+
+    let result = sqlx::query!(
+        "UPDATE auth_tokens
+        SET token = $1,
+            expires_at = $2,
+            last_used_at = NOW()
+        WHERE id = $3",
+        hash,
+        expiry,
+        id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to rotate auth token for session {id}: {e}"))?;
+
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(GeneratedToken { id, token }))
+}
+
+/// Updates the last use time of a token. Only writes at most once every few minutes per token.
+pub async fn touch(token: Uuid, conn: &mut PgConnection) -> Result<(), DatabaseError> {
+    let hash = sha256::digest(token.to_string());
+
+    sqlx::query!(
+        "UPDATE auth_tokens
+        SET last_used_at = NOW()
+        WHERE token = $1
+        AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '5 minutes')",
+        hash
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to update auth token last use: {e}"))?;
+
+    Ok(())
+}
+
+/// Lists sessions which are still usable, either directly or through a refresh token
+pub async fn get_sessions_for_developer(
+    developer_id: i32,
+    current: Uuid,
+    conn: &mut PgConnection,
+) -> Result<Vec<Session>, DatabaseError> {
+    let hash = sha256::digest(current.to_string());
+
+    sqlx::query_as!(
+        SessionRow,
+        r#"SELECT
+            a.id,
+            a.created_at,
+            a.last_used_at,
+            a.expires_at,
+            a.ip,
+            a.user_agent,
+            a.token = $2 as "current!"
+        FROM auth_tokens a
+        WHERE a.developer_id = $1
+        AND (
+            a.expires_at IS NULL
+            OR a.expires_at > NOW()
+            OR EXISTS (
+                SELECT 1 FROM refresh_tokens rt
+                WHERE rt.auth_token_id = a.id
+                AND rt.expires_at > NOW()
+            )
+        )
+        ORDER BY COALESCE(a.last_used_at, a.created_at) DESC"#,
+        developer_id,
+        hash
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch sessions for developer {developer_id}: {e}"))
+    .map(|rows| rows.into_iter().map(|x| x.into_session()).collect())
+    .map_err(|e| e.into())
+}
+
+/// Removes a session along with its refresh tokens. Returns false if the developer has no such session.
+pub async fn remove_session(
+    developer_id: i32,
+    id: i32,
+    conn: &mut PgConnection,
+) -> Result<bool, DatabaseError> {
+    let result = sqlx::query!(
+        "DELETE FROM auth_tokens
+        WHERE id = $1
+        AND developer_id = $2",
+        id,
+        developer_id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to remove session {id}: {e}"))?;
+
+    Ok(result.rows_affected() > 0)
 }
 
 pub async fn remove_token(token: Uuid, conn: &mut PgConnection) -> Result<(), DatabaseError> {
@@ -66,10 +214,16 @@ pub async fn remove_developer_tokens(
     Ok(())
 }
 
+/// Expired tokens are kept around while their session can still be refreshed
 pub async fn cleanup(conn: &mut PgConnection) -> Result<(), DatabaseError> {
     sqlx::query!(
-        "DELETE FROM auth_tokens
-        WHERE expires_at < NOW()"
+        "DELETE FROM auth_tokens a
+        WHERE a.expires_at < NOW()
+        AND NOT EXISTS (
+            SELECT 1 FROM refresh_tokens rt
+            WHERE rt.auth_token_id = a.id
+            AND rt.expires_at > NOW()
+        )"
     )
     .execute(conn)
     .await
@@ -1,6 +1,8 @@
 use crate::database::DatabaseError;
 use crate::types::api::PaginatedData;
-use crate::types::models::developer::{Developer, ModDeveloper};
+use crate::types::models::developer::{
+    Developer, DeveloperLinks, DeveloperStats, ModDeveloper, ModDeveloperRole,
+};
 use sqlx::PgConnection;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -175,15 +177,16 @@ pub async fn get_all_for_mod(
 ) -> Result<Vec<ModDeveloper>, DatabaseError> {
     sqlx::query_as!(
         ModDeveloper,
-        "SELECT
+        r#"SELECT
             dev.id,
             dev.username,
             dev.display_name,
-            md.is_owner
+            md.is_owner,
+            md.role as "role: _"
         FROM developers dev
         INNER JOIN mods_developers md ON dev.id = md.developer_id
         WHERE md.mod_id = $1
-        ORDER BY md.is_owner DESC, dev.id ASC",
+        ORDER BY md.is_owner DESC, dev.id ASC"#,
         mod_id
     )
     .fetch_all(conn)
@@ -206,19 +209,21 @@ pub async fn get_all_for_mods(
         pub username: String,
         pub display_name: String,
         pub is_owner: bool,
+        pub role: ModDeveloperRole,
     }
 
     let result = sqlx::query_as!(
         QueryResult,
-        "SELECT
+        r#"SELECT
             dev.id,
             dev.username,
             dev.display_name,
             md.is_owner,
+            md.role as "role: _",
             md.mod_id
         FROM developers dev
         INNER JOIN mods_developers md ON dev.id = md.developer_id
-        WHERE md.mod_id = ANY($1)",
+        WHERE md.mod_id = ANY($1)"#,
         mod_ids
     )
     .fetch_all(conn)
@@ -235,6 +240,7 @@ pub async fn get_all_for_mods(
                 username: result_item.username,
                 display_name: result_item.display_name,
                 is_owner: result_item.is_owner,
+                role: result_item.role,
             });
     }
 
@@ -249,7 +255,12 @@ pub async fn has_access_to_mod(
     sqlx::query!(
         "SELECT developer_id FROM mods_developers
         WHERE developer_id = $1
-        AND mod_id = $2",
+        AND mod_id = $2
+        UNION
+        SELECT om.developer_id FROM organization_members om
+        INNER JOIN mods m ON m.organization_id = om.organization_id
+        WHERE om.developer_id = $1
+        AND m.id = $2",
         dev_id,
         mod_id
     )
@@ -267,6 +278,38 @@ pub async fn has_access_to_mod(
     .map_err(|e| e.into())
 }
 
+/// Checks if a developer owns a mod or has at least the given role on it.
+/// Members of the organization the mod belongs to have every role.
+pub async fn has_mod_role(
+    dev_id: i32,
+    mod_id: &str,
+    role: ModDeveloperRole,
+    conn: &mut PgConnection,
+) -> Result<bool, DatabaseError> {
+    sqlx::query!(
+        "SELECT developer_id FROM mods_developers
+        WHERE developer_id = $1
+        AND mod_id = $2
+        AND (is_owner = true OR role >= $3)
+        UNION
+        SELECT om.developer_id FROM organization_members om
+        INNER JOIN mods m ON m.organization_id = om.organization_id
+        WHERE om.developer_id = $1
+        AND m.id = $2",
+        dev_id,
+        mod_id,
+        role as ModDeveloperRole
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .inspect_err(|e| {
+        log::error!("Failed to check mod {mod_id} role {role:?} for developer {dev_id}: {e}")
+    })
+    .map(|x| x.is_some())
+    .map_err(|e| e.into())
+}
+
+/// Admins and owners of the organization a mod belongs to count as owners
 pub async fn owns_mod(
     dev_id: i32,
     mod_id: &str,
@@ -276,7 +319,13 @@ pub async fn owns_mod(
         "SELECT developer_id FROM mods_developers
         WHERE developer_id = $1
         AND mod_id = $2
-        AND is_owner = true",
+        AND is_owner = true
+        UNION
+        SELECT om.developer_id FROM organization_members om
+        INNER JOIN mods m ON m.organization_id = om.organization_id
+        WHERE om.developer_id = $1
+        AND m.id = $2
+        AND om.role >= 'admin'",
         dev_id,
         mod_id
     )
@@ -373,6 +422,108 @@ pub async fn update_profile(
     .map_err(|e| e.into())
 }
 
+#[derive(Default)]
+pub struct ProfileDetails {
+    pub bio: Option<String>,
+    /// Path of the avatar inside public storage
+    pub avatar: Option<String>,
+    pub links: DeveloperLinks,
+}
+
+pub async fn get_profile_details(
+    dev_id: i32,
+    conn: &mut PgConnection,
+) -> Result<Option<ProfileDetails>, DatabaseError> {
+    sqlx::query!(
+        "SELECT bio, avatar, website, social_links
+        FROM developers
+        WHERE id = $1",
+        dev_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch profile details for {dev_id}: {e}"))
+    .map(|x| {
+        x.map(|row| ProfileDetails {
+            bio: row.bio,
+            avatar: row.avatar,
+            links: DeveloperLinks {
+                website: row.website,
+                social: row.social_links,
+            },
+        })
+    })
+    .map_err(|e| e.into())
+}
+
+pub async fn update_profile_details(
+    dev_id: i32,
+    bio: Option<&str>,
+    links: &DeveloperLinks,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE developers
+        SET bio = $1,
+            website = $2,
+            social_links = $3
+        WHERE id = $4",
+        bio,
+        links.website,
+        &links.social,
+        dev_id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to update profile details for {dev_id}: {e}"))?;
+
+    Ok(())
+}
+
+pub async fn update_avatar(
+    dev_id: i32,
+    avatar: Option<&str>,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE developers
+        SET avatar = $1
+        WHERE id = $2",
+        avatar,
+        dev_id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to update avatar for {dev_id}: {e}"))?;
+
+    Ok(())
+}
+
+/// Aggregates over the accepted mods a developer is listed on
+pub async fn get_stats(dev_id: i32, conn: &mut PgConnection) -> Result<DeveloperStats, DatabaseError> {
+    sqlx::query_as!(
+        DeveloperStats,
+        r#"SELECT
+            COUNT(m.id) as "mod_count!",
+            COALESCE(SUM(m.download_count), 0)::BIGINT as "total_downloads!",
+            MIN(m.created_at) as first_published_at
+        FROM mods m
+        INNER JOIN mods_developers md ON md.mod_id = m.id
+        WHERE md.developer_id = $1
+        AND EXISTS (
+            SELECT 1 FROM mod_versions mv
+            INNER JOIN mod_version_statuses mvs ON mvs.mod_version_id = mv.id
+            WHERE mv.mod_id = m.id
+            AND mvs.status = 'accepted'
+        )"#,
+        dev_id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch stats for developer {dev_id}: {e}"))
+    .map_err(|e| e.into())
+}
+
 pub async fn find_by_refresh_token(
     uuid: Uuid,
     conn: &mut PgConnection,
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use super::mod_downloads;
+use crate::database::DatabaseError;
+use crate::types::models::download_flags::{DiscountedDownloads, FlaggedDownloads};
+use sqlx::PgConnection;
+
+/// Number of downloads flagged for each reason
+#[derive(Debug, Default)]
+pub struct AnalyzeResult {
+    pub subnet: u64,
+    pub publish_burst: u64,
+    pub no_user_agent: u64,
+}
+
+/// Re-evaluates the flags of every download that hasn't been discounted yet
+pub async fn analyze(
+    subnet_threshold: i64,
+    burst_threshold: i64,
+    conn: &mut PgConnection,
+) -> Result<AnalyzeResult, DatabaseError> {
+    sqlx::query!(
+        "UPDATE mod_downloads SET flags = '{}'
+        WHERE NOT discounted
+        AND cardinality(flags) > 0"
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::analyze reset failed: {e}"))?;
+
+    // subnet hashes use the salt of the day, so this groups per day
+    let subnet = sqlx::query!(
+        "UPDATE mod_downloads md
+        SET flags = array_append(md.flags, 'subnet')
+        FROM (
+            SELECT mod_version_id, subnet_hash
+            FROM mod_downloads
+            WHERE subnet_hash IS NOT NULL
+            GROUP BY 1, 2
+            HAVING COUNT(*) >= $1
+        ) s
+        WHERE md.mod_version_id = s.mod_version_id
+        AND md.subnet_hash = s.subnet_hash
+        AND NOT md.discounted",
+        subnet_threshold
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::analyze subnet query failed: {e}"))?
+    .rows_affected();
+
+    // a burst is more downloads in the first hour than the other versions of
+    // the mod usually get in two days
+    let publish_burst = sqlx::query!(
+        "WITH published AS (
+            SELECT mv.id, mv.mod_id, mvs.updated_at AS published_at
+            FROM mod_versions mv
+            INNER JOIN mod_version_statuses mvs ON mvs.id = mv.status_id
+            WHERE mvs.status = 'accepted'
+        ),
+        usual AS (
+            SELECT p.id, SUM(s.downloads) / 30.0 AS daily
+            FROM published p
+            INNER JOIN mod_versions o ON o.mod_id = p.mod_id AND o.id <> p.id
+            INNER JOIN mod_version_download_stats s ON s.mod_version_id = o.id
+            WHERE s.day > (NOW() AT TIME ZONE 'UTC')::date - 30
+            GROUP BY p.id
+        ),
+        bursts AS (
+            SELECT p.id, p.published_at
+            FROM published p
+            INNER JOIN mod_downloads md ON md.mod_version_id = p.id
+            LEFT JOIN usual u ON u.id = p.id
+            WHERE md.time_downloaded >= p.published_at
+            AND md.time_downloaded < p.published_at + INTERVAL '1 hour'
+            GROUP BY p.id, p.published_at, u.daily
+            HAVING COUNT(*) > GREATEST($1, 2 * COALESCE(u.daily, 0))
+        )
+        UPDATE mod_downloads md
+        SET flags = array_append(md.flags, 'publish_burst')
+        FROM bursts b
+        WHERE md.mod_version_id = b.id
+        AND md.time_downloaded >= b.published_at
+        AND md.time_downloaded < b.published_at + INTERVAL '1 hour'
+        AND NOT md.discounted",
+        burst_threshold as f64
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::analyze burst query failed: {e}"))?
+    .rows_affected();
+
+    let no_user_agent = sqlx::query!(
+        "UPDATE mod_downloads
+        SET flags = array_append(flags, 'no_user_agent')
+        WHERE has_user_agent = FALSE
+        AND NOT discounted"
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::analyze user agent query failed: {e}"))?
+    .rows_affected();
+
+    Ok(AnalyzeResult {
+        subnet,
+        publish_burst,
+        no_user_agent,
+    })
+}
+
+/// Versions with flagged downloads, most flagged first
+pub async fn get_report(
+    mod_id: Option<&str>,
+    conn: &mut PgConnection,
+) -> Result<Vec<FlaggedDownloads>, DatabaseError> {
+    sqlx::query_as!(
+        FlaggedDownloads,
+        r#"SELECT
+            mv.mod_id,
+            mv.version,
+            COUNT(*) as "total!",
+            COUNT(*) FILTER (WHERE cardinality(md.flags) > 0) as "flagged!",
+            COUNT(*) FILTER (WHERE md.discounted) as "discounted!",
+            COUNT(*) FILTER (WHERE 'subnet' = ANY(md.flags)) as "subnet!",
+            COUNT(*) FILTER (WHERE 'publish_burst' = ANY(md.flags)) as "publish_burst!",
+            COUNT(*) FILTER (WHERE 'no_user_agent' = ANY(md.flags)) as "no_user_agent!"
+        FROM mod_downloads md
+        INNER JOIN mod_versions mv ON mv.id = md.mod_version_id
+        WHERE ($1::text IS NULL OR mv.mod_id = $1)
+        GROUP BY mv.mod_id, mv.version
+        HAVING COUNT(*) FILTER (WHERE cardinality(md.flags) > 0) > 0
+        ORDER BY 4 DESC, mv.mod_id, mv.version"#,
+        mod_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::get_report query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Takes the flagged downloads of a mod out of its download counts, including
+/// the daily ones that were already rolled up. Only downloads that added to the
+/// mod's own count (`counted_for_mod`) are taken out of it, and not even those
+/// if the same client has another download of the mod left, which then carries
+/// the count instead.
+pub async fn discount(
+    mod_id: &str,
+    conn: &mut PgConnection,
+) -> Result<DiscountedDownloads, DatabaseError> {
+    // `old` still has the values from before the update
+    let rows = sqlx::query!(
+        r#"UPDATE mod_downloads md
+        SET discounted = TRUE, counted_for_mod = FALSE
+        FROM mod_versions mv, mod_downloads old
+        WHERE mv.id = md.mod_version_id
+        AND old.mod_version_id = md.mod_version_id
+        AND old.ip_hash = md.ip_hash
+        AND mv.mod_id = $1
+        AND cardinality(md.flags) > 0
+        AND NOT md.discounted
+        RETURNING md.mod_version_id, md.ip_hash as "ip_hash!", old.counted_for_mod"#,
+        mod_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::discount update failed: {e}"))?;
+
+    let mut per_version: HashMap<i32, i32> = HashMap::new();
+    for row in &rows {
+        *per_version.entry(row.mod_version_id).or_default() += 1;
+    }
+
+    for (id, count) in per_version {
+        sqlx::query!(
+            "UPDATE mod_versions
+            SET download_count = GREATEST(download_count - $1, 0)
+            WHERE id = $2",
+            count,
+            id
+        )
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("download_flags::discount version update failed: {e}"))?;
+    }
+
+    let (version_ids, hashes): (Vec<i32>, Vec<String>) = rows
+        .iter()
+        .map(|r| (r.mod_version_id, r.ip_hash.clone()))
+        .unzip();
+    mod_downloads::unroll(&version_ids, &hashes, conn).await?;
+
+    let version_downloads = rows.len() as i64;
+    let counted: Vec<String> = rows
+        .into_iter()
+        .filter(|r| r.counted_for_mod)
+        .map(|r| r.ip_hash)
+        .collect();
+
+    // a client that still has a download of the mod left keeps being counted,
+    // by the oldest of those downloads
+    let moved = sqlx::query!(
+        "UPDATE mod_downloads md
+        SET counted_for_mod = TRUE
+        FROM (
+            SELECT DISTINCT ON (d.ip_hash) d.mod_version_id, d.ip_hash
+            FROM mod_downloads d
+            INNER JOIN mod_versions mv ON mv.id = d.mod_version_id
+            WHERE mv.mod_id = $1
+            AND d.ip_hash = ANY($2)
+            AND NOT d.discounted
+            ORDER BY d.ip_hash, d.time_downloaded
+        ) survivor
+        WHERE md.mod_version_id = survivor.mod_version_id
+        AND md.ip_hash = survivor.ip_hash",
+        mod_id,
+        &counted
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::discount move failed: {e}"))?
+    .rows_affected();
+
+    let mod_downloads = counted.len() as i64 - moved as i64;
+
+    sqlx::query!(
+        "UPDATE mods
+        SET download_count = GREATEST(download_count - $1, 0)
+        WHERE id = $2",
+        i32::try_from(mod_downloads).unwrap_or(i32::MAX),
+        mod_id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("download_flags::discount mod update failed: {e}"))?;
+
+    Ok(DiscountedDownloads {
+        mod_id: mod_id.into(),
+        version_downloads,
+        mod_downloads,
+    })
+}
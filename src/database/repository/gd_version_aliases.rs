@@ -0,0 +1,163 @@
+use sqlx::PgConnection;
+
+use crate::{
+    database::DatabaseError,
+    types::models::{
+        gd_version_alias::{GDVersionAlias, GDVersionAliasCreate},
+        mod_gd_version::GDVersion,
+    },
+};
+
+pub async fn get_all(conn: &mut PgConnection) -> Result<Vec<GDVersionAlias>, DatabaseError> {
+    sqlx::query_as!(
+        GDVersionAlias,
+        r#"SELECT
+            a.version_name as "version_name: _",
+            a.mac_arm_uuid::text,
+            a.mac_intel_uuid::text,
+            a.android_manifest_id,
+            a.windows_timestamp,
+            a.ios_bundle_version,
+            a.added_at
+        FROM gd_version_aliases a
+        INNER JOIN gd_versions g ON g.name = a.version_name
+        ORDER BY g.sort_order DESC"#
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_version_aliases::get_all failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_one(
+    version: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<GDVersionAlias>, DatabaseError> {
+    sqlx::query_as!(
+        GDVersionAlias,
+        r#"SELECT
+            version_name as "version_name: _",
+            mac_arm_uuid::text,
+            mac_intel_uuid::text,
+            android_manifest_id,
+            windows_timestamp,
+            ios_bundle_version,
+            added_at
+        FROM gd_version_aliases
+        WHERE version_name = $1"#,
+        version
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_version_aliases::get_one failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Returns the version another alias already uses one of the identifiers for, if any
+pub async fn find_conflict(
+    version: &str,
+    alias: &GDVersionAliasCreate,
+    conn: &mut PgConnection,
+) -> Result<Option<GDVersion>, DatabaseError> {
+    sqlx::query_scalar!(
+        r#"SELECT version_name as "version_name: GDVersion"
+        FROM gd_version_aliases
+        WHERE version_name <> $1
+        AND (
+            mac_arm_uuid = $2
+            OR mac_intel_uuid = $3
+            OR android_manifest_id = $4
+            OR windows_timestamp = $5
+            OR ios_bundle_version = $6
+        )
+        LIMIT 1"#,
+        version,
+        alias.mac_arm_uuid,
+        alias.mac_intel_uuid,
+        alias.android_manifest_id,
+        alias.windows_timestamp,
+        alias.ios_bundle_version
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_version_aliases::find_conflict failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn create(
+    version: &GDVersion,
+    alias: &GDVersionAliasCreate,
+    conn: &mut PgConnection,
+) -> Result<GDVersionAlias, DatabaseError> {
+    sqlx::query_as!(
+        GDVersionAlias,
+        r#"INSERT INTO gd_version_aliases
+            (version_name, mac_arm_uuid, mac_intel_uuid, android_manifest_id, windows_timestamp, ios_bundle_version)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING
+            version_name as "version_name: _",
+            mac_arm_uuid::text,
+            mac_intel_uuid::text,
+            android_manifest_id,
+            windows_timestamp,
+            ios_bundle_version,
+            added_at"#,
+        version as &GDVersion,
+        alias.mac_arm_uuid,
+        alias.mac_intel_uuid,
+        alias.android_manifest_id,
+        alias.windows_timestamp,
+        alias.ios_bundle_version
+    )
+    .fetch_one(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_version_aliases::create failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn update(
+    version: &str,
+    alias: &GDVersionAliasCreate,
+    conn: &mut PgConnection,
+) -> Result<GDVersionAlias, DatabaseError> {
+    sqlx::query_as!(
+        GDVersionAlias,
+        r#"UPDATE gd_version_aliases
+        SET mac_arm_uuid = $2,
+            mac_intel_uuid = $3,
+            android_manifest_id = $4,
+            windows_timestamp = $5,
+            ios_bundle_version = $6
+        WHERE version_name = $1
+        RETURNING
+            version_name as "version_name: _",
+            mac_arm_uuid::text,
+            mac_intel_uuid::text,
+            android_manifest_id,
+            windows_timestamp,
+            ios_bundle_version,
+            added_at"#,
+        version,
+        alias.mac_arm_uuid,
+        alias.mac_intel_uuid,
+        alias.android_manifest_id,
+        alias.windows_timestamp,
+        alias.ios_bundle_version
+    )
+    .fetch_one(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_version_aliases::update failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn delete(version: &str, conn: &mut PgConnection) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "DELETE FROM gd_version_aliases WHERE version_name = $1",
+        version
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_version_aliases::delete failed: {e}"))
+    .map(|_| ())
+    .map_err(|e| e.into())
+}
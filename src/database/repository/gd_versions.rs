@@ -0,0 +1,239 @@
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+
+use crate::{
+    database::DatabaseError,
+    types::models::mod_gd_version::{GDRelease, GDVersion, VerPlatform},
+};
+
+struct GDReleaseRow {
+    name: GDVersion,
+    sort_order: i32,
+    released_at: Option<NaiveDate>,
+    platforms: Vec<VerPlatform>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl GDReleaseRow {
+    fn into_release(self) -> GDRelease {
+        GDRelease {
+            name: self.name,
+            sort_order: self.sort_order,
+            released_at: self.released_at,
+            platforms: self.platforms,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// Lists known GD releases, newest first. `*` is not a release and is left out.
+pub async fn get_all(
+    platform: Option<VerPlatform>,
+    conn: &mut PgConnection,
+) -> Result<Vec<GDRelease>, DatabaseError> {
+    sqlx::query_as!(
+        GDReleaseRow,
+        r#"SELECT
+            name as "name: _",
+            sort_order,
+            released_at,
+            platforms as "platforms: _",
+            created_at
+        FROM gd_versions
+        WHERE name <> '*'
+        AND ($1::gd_ver_platform IS NULL OR $1 = ANY(platforms))
+        ORDER BY sort_order DESC"#,
+        platform as Option<VerPlatform>
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_versions::get_all failed: {e}"))
+    .map(|rows| rows.into_iter().map(|x| x.into_release()).collect())
+    .map_err(|e| e.into())
+}
+
+pub async fn get_one(
+    name: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<GDRelease>, DatabaseError> {
+    sqlx::query_as!(
+        GDReleaseRow,
+        r#"SELECT
+            name as "name: _",
+            sort_order,
+            released_at,
+            platforms as "platforms: _",
+            created_at
+        FROM gd_versions
+        WHERE name = $1"#,
+        name
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_versions::get_one failed: {e}"))
+    .map(|row| row.map(|x| x.into_release()))
+    .map_err(|e| e.into())
+}
+
+/// Returns the versions from `versions` that aren't in the table
+pub async fn get_unknown(
+    versions: &[GDVersion],
+    conn: &mut PgConnection,
+) -> Result<Vec<String>, DatabaseError> {
+    if versions.is_empty() {
+        return Ok(vec![]);
+    }
+
+    sqlx::query_scalar!(
+        r#"SELECT DISTINCT v.name as "name!"
+        FROM UNNEST($1::text[]) v(name)
+        WHERE NOT EXISTS (SELECT 1 FROM gd_versions g WHERE g.name = v.name)
+        ORDER BY 1"#,
+        versions as &[GDVersion]
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_versions::get_unknown failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Fails with [`DatabaseError::InvalidInput`] if any of `versions` isn't a known release
+pub async fn check_known(
+    versions: &[GDVersion],
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    let unknown = get_unknown(versions, conn).await?;
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(DatabaseError::InvalidInput(format!(
+            "Unknown GD version(s): {}",
+            unknown.join(", ")
+        )))
+    }
+}
+
+/// Inserts a release. Without `sort_order` it's placed after every other release,
+/// otherwise releases at or after `sort_order` are moved back one place.
+pub async fn create(
+    name: &GDVersion,
+    sort_order: Option<i32>,
+    released_at: Option<NaiveDate>,
+    platforms: &[VerPlatform],
+    conn: &mut PgConnection,
+) -> Result<GDRelease, DatabaseError> {
+    let sort_order = match sort_order {
+        Some(s) => {
+            make_room(s, conn).await?;
+            s
+        }
+        None => last_sort_order(conn).await? + 1,
+    };
+
+    sqlx::query_as!(
+        GDReleaseRow,
+        r#"INSERT INTO gd_versions (name, sort_order, released_at, platforms)
+        VALUES ($1, $2, $3, $4)
+        RETURNING
+            name as "name: _",
+            sort_order,
+            released_at,
+            platforms as "platforms: _",
+            created_at"#,
+        name as &GDVersion,
+        sort_order,
+        released_at,
+        platforms as &[VerPlatform]
+    )
+    .fetch_one(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_versions::create failed: {e}"))
+    .map(|x| x.into_release())
+    .map_err(|e| e.into())
+}
+
+/// Updates the release date and platforms of a release (`None` leaves them as they are),
+/// optionally moving it to `sort_order`
+pub async fn update(
+    name: &str,
+    sort_order: Option<i32>,
+    released_at: Option<NaiveDate>,
+    platforms: Option<&[VerPlatform]>,
+    conn: &mut PgConnection,
+) -> Result<GDRelease, DatabaseError> {
+    if let Some(s) = sort_order {
+        // take it out of the ordering, close the gap, then make room at the new spot
+        let old = sqlx::query_scalar!(
+            "UPDATE gd_versions g SET sort_order = -1
+            FROM gd_versions old
+            WHERE g.name = $1 AND old.name = g.name
+            RETURNING old.sort_order",
+            name
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("gd_versions::update failed to move {name}: {e}"))?;
+
+        sqlx::query!(
+            "UPDATE gd_versions SET sort_order = sort_order - 1 WHERE sort_order > $1",
+            old
+        )
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("gd_versions::update failed to move {name}: {e}"))?;
+
+        let s = s.min(last_sort_order(conn).await? + 1);
+        make_room(s, conn).await?;
+
+        sqlx::query!(
+            "UPDATE gd_versions SET sort_order = $2 WHERE name = $1",
+            name,
+            s
+        )
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("gd_versions::update failed to move {name}: {e}"))?;
+    }
+
+    sqlx::query_as!(
+        GDReleaseRow,
+        r#"UPDATE gd_versions
+        SET released_at = COALESCE($2, released_at),
+        platforms = COALESCE($3, platforms)
+        WHERE name = $1
+        RETURNING
+            name as "name: _",
+            sort_order,
+            released_at,
+            platforms as "platforms: _",
+            created_at"#,
+        name,
+        released_at,
+        platforms as Option<&[VerPlatform]>
+    )
+    .fetch_one(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_versions::update failed: {e}"))
+    .map(|x| x.into_release())
+    .map_err(|e| e.into())
+}
+
+async fn last_sort_order(conn: &mut PgConnection) -> Result<i32, DatabaseError> {
+    sqlx::query_scalar!(r#"SELECT COALESCE(MAX(sort_order), 0) as "max!" FROM gd_versions"#)
+        .fetch_one(conn)
+        .await
+        .inspect_err(|e| log::error!("gd_versions::last_sort_order failed: {e}"))
+        .map_err(|e| e.into())
+}
+
+async fn make_room(sort_order: i32, conn: &mut PgConnection) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE gd_versions SET sort_order = sort_order + 1 WHERE sort_order >= $1",
+        sort_order
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|e| log::error!("gd_versions::make_room failed: {e}"))
+    .map(|_| ())
+    .map_err(|e| e.into())
+}
@@ -1,15 +1,26 @@
 pub mod auth_tokens;
 pub mod dependencies;
+pub mod gd_version_aliases;
+pub mod gd_versions;
 pub mod deprecations;
 pub mod developers;
+pub mod download_flags;
 pub mod github_login_attempts;
 pub mod github_web_logins;
 pub mod incompatibilities;
 pub mod mod_downloads;
 pub mod mod_gd_versions;
 pub mod mod_links;
+pub mod mod_localizations;
+pub mod mod_logo_renditions;
+pub mod mod_media;
 pub mod mod_tags;
+pub mod mod_trending_scores;
+pub mod mod_version_files;
 pub mod mod_version_statuses;
 pub mod mod_versions;
 pub mod mods;
 pub mod refresh_tokens;
+pub mod stats_snapshots;
+pub mod mod_transfers;
+pub mod organizations;
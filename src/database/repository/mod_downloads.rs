@@ -1,19 +1,106 @@
 use crate::database::DatabaseError;
-use chrono::{Days, Utc};
-use sqlx::types::ipnetwork::IpNetwork;
+use crate::types::models::download_stats::{DownloadBreakdowns, StatsGranularity};
+use crate::types::models::mod_gd_version::{GDVersion, VerPlatform};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 use sqlx::PgConnection;
+use std::net::IpAddr;
 
+/// What we know about the client when downloading
+#[derive(Default)]
+pub struct DownloadDetails {
+    /// Can be `android` or `mac` if the client didn't say which architecture it's on
+    pub platform: Option<VerPlatform>,
+    pub gd_version: Option<GDVersion>,
+    pub loader_version: Option<String>,
+    /// See [`hash_subnet`]
+    pub subnet_hash: Option<String>,
+    pub has_user_agent: bool,
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Key for hashing the IPs of downloads made on `day`. Derived from the server's
+/// DOWNLOAD_HASH_SECRET, so nothing in the database is enough to brute-force
+/// the hashes, and a day's hashes can't be linked to another day's.
+fn day_key(secret: &[u8], day: NaiveDate) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(format!("download-ip:{day}").as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn keyed_hash(key: &[u8], value: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(value.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Keyed hash of a client IP, one for each day of the dedup window. The
+/// first one (made with today's key) is the one that gets stored.
+pub fn hash_ip(ip: IpAddr, secret: &[u8], window_days: u32) -> Vec<String> {
+    let today = Utc::now().date_naive();
+    (0..=window_days)
+        .filter_map(|days| today.checked_sub_days(Days::new(days.into())))
+        .map(|day| hash_ip_on(ip, secret, day))
+        .collect()
+}
+
+/// Keyed hash of the IP of a download made on `day`
+pub fn hash_ip_on(ip: IpAddr, secret: &[u8], day: NaiveDate) -> String {
+    keyed_hash(&day_key(secret, day), &ip.to_string())
+}
+
+/// Keyed hash of the /24 (IPv4) or /48 (IPv6) network the IP is in, so the
+/// download analyzer can spot many downloads coming from the same place
+pub fn hash_subnet(ip: IpAddr, secret: &[u8]) -> String {
+    let subnet = match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            format!("{a}.{b}.{c}.0/24")
+        }
+        IpAddr::V6(v6) => {
+            let [a, b, c, ..] = v6.segments();
+            format!("{a:x}:{b:x}:{c:x}::/48")
+        }
+    };
+
+    keyed_hash(&day_key(secret, Utc::now().date_naive()), &subnet)
+}
+
+/// Doesn't insert anything if any of `ip_hashes` already downloaded the version.
+/// `counted_for_mod` records whether the download also counts for the mod's total.
 pub async fn create(
-    ip: IpNetwork,
+    ip_hashes: &[String],
     mod_version_id: i32,
+    details: &DownloadDetails,
+    counted_for_mod: bool,
     conn: &mut PgConnection,
 ) -> Result<bool, DatabaseError> {
+    let Some(ip_hash) = ip_hashes.first() else {
+        return Ok(false);
+    };
+
     let result = sqlx::query!(
-        "INSERT INTO mod_downloads (mod_version_id, ip)
-        VALUES ($1, $2)
+        "INSERT INTO mod_downloads
+            (mod_version_id, ip_hash, platform, gd_version, loader_version, subnet_hash, has_user_agent,
+            counted_for_mod)
+        SELECT $1, $2, $3, $4, $5, $6, $7, $9
+        WHERE NOT EXISTS (
+            SELECT 1 FROM mod_downloads
+            WHERE mod_version_id = $1
+            AND ip_hash = ANY($8)
+        )
         ON CONFLICT DO NOTHING",
         mod_version_id,
-        ip
+        ip_hash,
+        details.platform.map(platform_name),
+        details.gd_version.as_ref().map(|g| g.as_str()),
+        details.loader_version,
+        details.subnet_hash,
+        details.has_user_agent,
+        ip_hashes,
+        counted_for_mod
     )
     .execute(&mut *conn)
     .await
@@ -25,18 +112,18 @@ pub async fn create(
 }
 
 pub async fn has_downloaded_mod(
-    ip: IpNetwork,
+    ip_hashes: &[String],
     mod_id: &str,
     conn: &mut PgConnection,
 ) -> Result<bool, DatabaseError> {
     sqlx::query!(
-        "SELECT ip FROM mod_downloads md
+        "SELECT ip_hash FROM mod_downloads md
         INNER JOIN mod_versions mv ON md.mod_version_id = mv.id
         WHERE mv.mod_id = $1
-        AND md.ip = $2
+        AND md.ip_hash = ANY($2)
         LIMIT 1",
         mod_id,
-        ip
+        ip_hashes
     )
     .fetch_optional(&mut *conn)
     .await
@@ -45,12 +132,139 @@ pub async fn has_downloaded_mod(
     .map(|x| x.is_some())
 }
 
-pub async fn cleanup(conn: &mut PgConnection) -> Result<(), DatabaseError> {
-    let date = Utc::now().checked_sub_days(Days::new(30)).unwrap();
+pub struct UnhashedDownload {
+    pub mod_version_id: i32,
+    pub ip: IpAddr,
+    pub day: NaiveDate,
+}
+
+// The ip column is dropped once the downloads from before IPs were hashed are
+// converted, so the queries touching it can't be checked at compile time
+
+/// Downloads that still have a plain IP instead of a hash, `None` once
+/// [`drop_ips`] has run
+pub async fn get_unhashed(
+    conn: &mut PgConnection,
+) -> Result<Option<Vec<UnhashedDownload>>, DatabaseError> {
+    let has_ips = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+            SELECT 1 FROM information_schema.columns
+            WHERE table_schema = current_schema()
+            AND table_name = 'mod_downloads'
+            AND column_name = 'ip'
+        ) as "exists!""#
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::get_unhashed column query failed: {e}"))?;
+
+    if !has_ips {
+        return Ok(None);
+    }
+
+    let rows: Vec<(i32, String, NaiveDate)> = sqlx::query_as(
+        "SELECT mod_version_id, host(ip), (time_downloaded AT TIME ZONE 'UTC')::date
+        FROM mod_downloads
+        WHERE ip_hash IS NULL",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::get_unhashed query failed: {e}"))?;
+
+    rows.into_iter()
+        .map(|(mod_version_id, ip, day)| {
+            let ip = ip.parse().map_err(|_| {
+                DatabaseError::InvalidInput(format!("Invalid download IP {ip}"))
+            })?;
+            Ok(UnhashedDownload {
+                mod_version_id,
+                ip,
+                day,
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+/// Stores the hashes of [`get_unhashed`] downloads, in the same order. The first
+/// download of each IP for a mod gets `counted_for_mod`, like the migration adding
+/// it guessed for downloads that were already hashed.
+pub async fn set_hashes(
+    downloads: &[UnhashedDownload],
+    ip_hashes: &[String],
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    let version_ids: Vec<i32> = downloads.iter().map(|d| d.mod_version_id).collect();
+    let ips: Vec<String> = downloads.iter().map(|d| d.ip.to_string()).collect();
+
+    sqlx::query(
+        "UPDATE mod_downloads md
+        SET counted_for_mod = TRUE
+        FROM (
+            SELECT DISTINCT ON (mv.mod_id, d.ip) d.mod_version_id, d.ip
+            FROM mod_downloads d
+            INNER JOIN mod_versions mv ON mv.id = d.mod_version_id
+            WHERE d.ip_hash IS NULL
+            AND NOT d.discounted
+            ORDER BY mv.mod_id, d.ip, d.time_downloaded
+        ) first
+        WHERE md.mod_version_id = first.mod_version_id
+        AND md.ip = first.ip",
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::set_hashes counted query failed: {e}"))?;
+
+    sqlx::query(
+        "UPDATE mod_downloads md
+        SET ip_hash = k.ip_hash
+        FROM UNNEST($1::int[], $2::text[], $3::text[]) k(mod_version_id, ip, ip_hash)
+        WHERE md.mod_version_id = k.mod_version_id
+        AND md.ip = k.ip::inet",
+    )
+    .bind(&version_ids)
+    .bind(&ips)
+    .bind(ip_hashes)
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::set_hashes query failed: {e}"))?;
+
+    Ok(())
+}
+
+/// Drops the plain IPs once every download has a hash
+pub async fn drop_ips(conn: &mut PgConnection) -> Result<(), DatabaseError> {
+    sqlx::query(
+        "ALTER TABLE mod_downloads
+            DROP COLUMN ip,
+            ALTER COLUMN ip_hash SET NOT NULL,
+            ADD CONSTRAINT mod_downloads_pkey PRIMARY KEY USING INDEX idx_mod_downloads_ip_hash",
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::drop_ips query failed: {e}"))?;
+
+    Ok(())
+}
+
+/// Downloads from before this are removed by [`cleanup`]
+fn cleanup_cutoff(window_days: u32) -> DateTime<Utc> {
+    Utc::now()
+        .checked_sub_days(Days::new(window_days.into()))
+        .unwrap()
+}
+
+/// First day that [`cleanup`] hasn't removed any downloads of yet
+fn first_whole_day(window_days: u32) -> NaiveDate {
+    let cutoff = cleanup_cutoff(window_days);
+    cutoff.date_naive().succ_opt().unwrap_or(NaiveDate::MAX)
+}
+
+pub async fn cleanup(window_days: u32, conn: &mut PgConnection) -> Result<(), DatabaseError> {
     sqlx::query!(
         "DELETE FROM mod_downloads md
         WHERE md.time_downloaded <= $1",
-        date
+        cleanup_cutoff(window_days)
     )
     .execute(&mut *conn)
     .await
@@ -58,3 +272,277 @@ pub async fn cleanup(conn: &mut PgConnection) -> Result<(), DatabaseError> {
 
     Ok(())
 }
+
+/// Rolls the complete days that are still wholly in mod_downloads up into
+/// mod_version_download_stats. Has to run before [`cleanup`] removes them, at
+/// least daily, which leaves each day a whole day to be rolled up as long as
+/// the window is 2 days or more. Discounted downloads are left out.
+pub async fn rollup(window_days: u32, conn: &mut PgConnection) -> Result<u64, DatabaseError> {
+    sqlx::query!(
+        "INSERT INTO mod_version_download_stats (mod_version_id, day, downloads)
+        SELECT mod_version_id, (time_downloaded AT TIME ZONE 'UTC')::date, COUNT(*)
+        FROM mod_downloads
+        WHERE (time_downloaded AT TIME ZONE 'UTC')::date >= $1
+        AND time_downloaded < date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+        AND NOT discounted
+        GROUP BY 1, 2
+        ON CONFLICT (mod_version_id, day) DO UPDATE SET downloads = EXCLUDED.downloads",
+        first_whole_day(window_days)
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::rollup query failed: {e}"))
+    .map(|x| x.rows_affected())
+    .map_err(|e| e.into())
+}
+
+/// Same as [`rollup`], for the platform, GD version and loader version counts
+pub async fn rollup_breakdowns(
+    window_days: u32,
+    conn: &mut PgConnection,
+) -> Result<u64, DatabaseError> {
+    sqlx::query!(
+        "INSERT INTO mod_version_download_breakdowns (mod_version_id, day, kind, value, downloads)
+        SELECT md.mod_version_id, (md.time_downloaded AT TIME ZONE 'UTC')::date, b.kind, b.value, COUNT(*)
+        FROM mod_downloads md
+        CROSS JOIN LATERAL (VALUES
+            ('platform'::download_breakdown_kind, md.platform::text),
+            ('gd'::download_breakdown_kind, md.gd_version),
+            ('loader'::download_breakdown_kind, md.loader_version)
+        ) b(kind, value)
+        WHERE b.value IS NOT NULL
+        AND (md.time_downloaded AT TIME ZONE 'UTC')::date >= $1
+        AND md.time_downloaded < date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+        AND NOT md.discounted
+        GROUP BY 1, 2, 3, 4
+        ON CONFLICT (mod_version_id, day, kind, value) DO UPDATE SET downloads = EXCLUDED.downloads",
+        first_whole_day(window_days)
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::rollup_breakdowns query failed: {e}"))
+    .map(|x| x.rows_affected())
+    .map_err(|e| e.into())
+}
+
+/// Takes downloads that were discounted after their day got rolled up back out
+/// of the rolled up counts, dropping counts that reach zero. Days that haven't
+/// been rolled up are counted from mod_downloads, where they're already left out.
+/// Subtracting rather than counting again keeps days that [`cleanup`] has
+/// started removing intact.
+pub async fn unroll(
+    mod_version_ids: &[i32],
+    ip_hashes: &[String],
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mod_version_download_stats s
+        SET downloads = s.downloads - d.downloads
+        FROM (
+            SELECT md.mod_version_id, (md.time_downloaded AT TIME ZONE 'UTC')::date AS day, COUNT(*)::int AS downloads
+            FROM mod_downloads md
+            INNER JOIN UNNEST($1::int[], $2::text[]) k(mod_version_id, ip_hash)
+                ON k.mod_version_id = md.mod_version_id AND k.ip_hash = md.ip_hash
+            GROUP BY 1, 2
+        ) d
+        WHERE s.mod_version_id = d.mod_version_id
+        AND s.day = d.day",
+        mod_version_ids,
+        ip_hashes
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::unroll stats query failed: {e}"))?;
+
+    sqlx::query!(
+        "UPDATE mod_version_download_breakdowns s
+        SET downloads = s.downloads - d.downloads
+        FROM (
+            SELECT md.mod_version_id, (md.time_downloaded AT TIME ZONE 'UTC')::date AS day, b.kind, b.value, COUNT(*)::int AS downloads
+            FROM mod_downloads md
+            INNER JOIN UNNEST($1::int[], $2::text[]) k(mod_version_id, ip_hash)
+                ON k.mod_version_id = md.mod_version_id AND k.ip_hash = md.ip_hash
+            CROSS JOIN LATERAL (VALUES
+                ('platform'::download_breakdown_kind, md.platform::text),
+                ('gd'::download_breakdown_kind, md.gd_version),
+                ('loader'::download_breakdown_kind, md.loader_version)
+            ) b(kind, value)
+            WHERE b.value IS NOT NULL
+            GROUP BY 1, 2, 3, 4
+        ) d
+        WHERE s.mod_version_id = d.mod_version_id
+        AND s.day = d.day
+        AND s.kind = d.kind
+        AND s.value = d.value",
+        mod_version_ids,
+        ip_hashes
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::unroll breakdowns query failed: {e}"))?;
+
+    sqlx::query!(
+        "DELETE FROM mod_version_download_stats
+        WHERE mod_version_id = ANY($1)
+        AND downloads <= 0",
+        mod_version_ids
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::unroll stats cleanup failed: {e}"))?;
+
+    sqlx::query!(
+        "DELETE FROM mod_version_download_breakdowns
+        WHERE mod_version_id = ANY($1)
+        AND downloads <= 0",
+        mod_version_ids
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::unroll breakdowns cleanup failed: {e}"))?;
+
+    Ok(())
+}
+
+pub struct VersionDownloads {
+    /// First day of the period
+    pub date: NaiveDate,
+    pub version: String,
+    pub downloads: i64,
+}
+
+/// Downloads of a mod's versions per period. Days that haven't been rolled up
+/// yet are counted from mod_downloads directly.
+pub async fn get_stats_for_mod(
+    mod_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: StatsGranularity,
+    conn: &mut PgConnection,
+) -> Result<Vec<VersionDownloads>, DatabaseError> {
+    sqlx::query_as!(
+        VersionDownloads,
+        r#"WITH versions AS (
+            SELECT id, version FROM mod_versions WHERE mod_id = $1
+        ),
+        daily AS (
+            SELECT s.mod_version_id, s.day, s.downloads::bigint AS downloads
+            FROM mod_version_download_stats s
+            WHERE s.mod_version_id IN (SELECT id FROM versions)
+            AND s.day BETWEEN $2 AND $3
+            UNION ALL
+            SELECT md.mod_version_id, (md.time_downloaded AT TIME ZONE 'UTC')::date AS day, COUNT(*)
+            FROM mod_downloads md
+            WHERE md.mod_version_id IN (SELECT id FROM versions)
+            AND (md.time_downloaded AT TIME ZONE 'UTC')::date BETWEEN $2 AND $3
+            AND NOT md.discounted
+            AND NOT EXISTS (
+                SELECT 1 FROM mod_version_download_stats s
+                WHERE s.mod_version_id = md.mod_version_id
+                AND s.day = (md.time_downloaded AT TIME ZONE 'UTC')::date
+            )
+            GROUP BY 1, 2
+        )
+        SELECT
+            date_trunc($4, d.day)::date as "date!",
+            v.version,
+            SUM(d.downloads)::bigint as "downloads!"
+        FROM daily d
+        INNER JOIN versions v ON v.id = d.mod_version_id
+        GROUP BY 1, 2
+        ORDER BY 1, 2"#,
+        mod_id,
+        from,
+        to,
+        granularity.as_str()
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::get_stats_for_mod query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+#[derive(sqlx::Type, Debug)]
+#[sqlx(type_name = "download_breakdown_kind", rename_all = "lowercase")]
+enum BreakdownKind {
+    Platform,
+    Gd,
+    Loader,
+}
+
+/// Platform, GD version and loader version counts for a mod over the whole
+/// range. Uses the same rolled up / live split as [`get_stats_for_mod`].
+pub async fn get_breakdowns_for_mod(
+    mod_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    conn: &mut PgConnection,
+) -> Result<DownloadBreakdowns, DatabaseError> {
+    let rows = sqlx::query!(
+        r#"WITH versions AS (
+            SELECT id FROM mod_versions WHERE mod_id = $1
+        ),
+        counts AS (
+            SELECT s.kind, s.value, s.downloads::bigint AS downloads
+            FROM mod_version_download_breakdowns s
+            WHERE s.mod_version_id IN (SELECT id FROM versions)
+            AND s.day BETWEEN $2 AND $3
+            UNION ALL
+            SELECT b.kind, b.value, COUNT(*)
+            FROM mod_downloads md
+            CROSS JOIN LATERAL (VALUES
+                ('platform'::download_breakdown_kind, md.platform::text),
+                ('gd'::download_breakdown_kind, md.gd_version),
+                ('loader'::download_breakdown_kind, md.loader_version)
+            ) b(kind, value)
+            WHERE md.mod_version_id IN (SELECT id FROM versions)
+            AND b.value IS NOT NULL
+            AND (md.time_downloaded AT TIME ZONE 'UTC')::date BETWEEN $2 AND $3
+            AND NOT md.discounted
+            AND NOT EXISTS (
+                SELECT 1 FROM mod_version_download_breakdowns s
+                WHERE s.mod_version_id = md.mod_version_id
+                AND s.day = (md.time_downloaded AT TIME ZONE 'UTC')::date
+            )
+            GROUP BY 1, 2
+        )
+        SELECT
+            kind as "kind!: BreakdownKind",
+            value as "value!",
+            SUM(downloads)::bigint as "downloads!"
+        FROM counts
+        GROUP BY 1, 2"#,
+        mod_id,
+        from,
+        to
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_downloads::get_breakdowns_for_mod query failed: {e}"))?;
+
+    let mut ret = DownloadBreakdowns::default();
+    for row in rows {
+        let map = match row.kind {
+            BreakdownKind::Platform => &mut ret.platforms,
+            BreakdownKind::Gd => &mut ret.gd_versions,
+            BreakdownKind::Loader => &mut ret.loader_versions,
+        };
+        map.insert(row.value, row.downloads);
+    }
+
+    Ok(ret)
+}
+
+/// How a platform is stored in mod_downloads.platform
+fn platform_name(platform: VerPlatform) -> &'static str {
+    match platform {
+        VerPlatform::Android => "android",
+        VerPlatform::Android32 => "android32",
+        VerPlatform::Android64 => "android64",
+        VerPlatform::Ios => "ios",
+        VerPlatform::Mac => "mac",
+        VerPlatform::MacArm => "mac-arm",
+        VerPlatform::MacIntel => "mac-intel",
+        VerPlatform::Win => "win",
+    }
+}
@@ -1,10 +1,10 @@
 use sqlx::PgConnection;
 
 use crate::{
-    database::DatabaseError,
+    database::{DatabaseError, repository::gd_versions},
     types::{
         mod_json::ModJson,
-        models::mod_gd_version::{DetailedGDVersion, GDVersionEnum, VerPlatform},
+        models::mod_gd_version::{self, DetailedGDVersion, GDVersion, VerPlatform},
     },
 };
 
@@ -13,25 +13,27 @@ pub async fn create(
     json: &ModJson,
     conn: &mut PgConnection,
 ) -> Result<DetailedGDVersion, DatabaseError> {
-    let create = json.gd.to_create_payload(json);
+    let create = mod_gd_version::to_create_payload(&json.gd, json);
 
-    let gd: Vec<GDVersionEnum> = create.iter().map(|x| x.gd).collect();
+    let gd: Vec<GDVersion> = create.iter().map(|x| x.gd.clone()).collect();
     let platform: Vec<VerPlatform> = create.iter().map(|x| x.platform).collect();
     let mod_id = vec![mod_version_id; create.len()];
 
+    gd_versions::check_known(&gd, conn).await?;
+
     sqlx::query!(
         "INSERT INTO mod_gd_versions
         (gd, platform, mod_id)
         SELECT * FROM UNNEST(
-            $1::gd_version[],
+            $1::text[],
             $2::gd_ver_platform[],
             $3::int4[]
         )",
-        &gd as &[GDVersionEnum],
+        &gd as &[GDVersion],
         &platform as &[VerPlatform],
         &mod_id
     )
-    .execute(conn)
+    .execute(&mut *conn)
     .await
     .inspect_err(|e| log::error!("mod_gd_versions::create query failed: {e}"))?;
 
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use sqlx::PgConnection;
+
+use crate::database::DatabaseError;
+use crate::markdown;
+use crate::types::mod_json::LocalizedMarkdown;
+
+pub struct ModLocalization {
+    pub lang: String,
+    pub about: Option<String>,
+    pub changelog: Option<String>,
+    pub about_html: Option<String>,
+    pub changelog_html: Option<String>,
+}
+
+pub async fn get_for_mod(
+    mod_id: &str,
+    conn: &mut PgConnection,
+) -> Result<Vec<ModLocalization>, DatabaseError> {
+    sqlx::query_as!(
+        ModLocalization,
+        "SELECT lang, about, changelog, about_html, changelog_html
+        FROM mod_localizations
+        WHERE mod_id = $1
+        ORDER BY lang",
+        mod_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_localizations::get_for_mod query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Replaces the mod's translations with the ones from a newly accepted .geode,
+/// rendering HTML for them like `mods::update_with_json` does for about.md
+pub async fn replace(
+    mod_id: &str,
+    localized: &BTreeMap<String, LocalizedMarkdown>,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!("DELETE FROM mod_localizations WHERE mod_id = $1", mod_id)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("mod_localizations::replace delete failed: {e}"))?;
+
+    for (lang, text) in localized {
+        sqlx::query!(
+            "INSERT INTO mod_localizations (mod_id, lang, about, changelog, about_html, changelog_html)
+            VALUES ($1, $2, $3, $4, $5, $6)",
+            mod_id,
+            lang,
+            text.about,
+            text.changelog,
+            text.about.as_deref().map(markdown::render_html),
+            text.changelog.as_deref().map(markdown::render_html)
+        )
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("mod_localizations::replace insert failed: {e}"))?;
+    }
+
+    Ok(())
+}
+
+pub struct LocalizationToRender {
+    pub mod_id: String,
+    pub lang: String,
+    pub about: Option<String>,
+    pub changelog: Option<String>,
+}
+
+/// Same as `mods::get_markdown_to_render`, for translations
+pub async fn get_to_render(
+    all: bool,
+    conn: &mut PgConnection,
+) -> Result<Vec<LocalizationToRender>, DatabaseError> {
+    sqlx::query_as!(
+        LocalizationToRender,
+        "SELECT mod_id, lang, about, changelog
+        FROM mod_localizations
+        WHERE (about IS NOT NULL AND ($1 OR about_html IS NULL))
+        OR (changelog IS NOT NULL AND ($1 OR changelog_html IS NULL))
+        ORDER BY mod_id, lang",
+        all
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_localizations::get_to_render query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn update_rendered(
+    mod_id: &str,
+    lang: &str,
+    about_html: Option<&str>,
+    changelog_html: Option<&str>,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mod_localizations
+        SET about_html = $3,
+        changelog_html = $4
+        WHERE mod_id = $1
+        AND lang = $2",
+        mod_id,
+        lang,
+        about_html,
+        changelog_html
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_localizations::update_rendered query failed: {e}"))?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use sqlx::PgConnection;
+
+use crate::database::DatabaseError;
+use crate::types::models::mod_logo::LogoFormat;
+
+pub struct StoredRendition {
+    pub size: i32,
+    pub format: LogoFormat,
+    /// Path inside public storage
+    pub path: String,
+}
+
+pub async fn get_path(
+    mod_id: &str,
+    size: i32,
+    format: LogoFormat,
+    conn: &mut PgConnection,
+) -> Result<Option<String>, DatabaseError> {
+    sqlx::query_scalar!(
+        "SELECT path
+        FROM mod_logo_renditions
+        WHERE mod_id = $1
+        AND size = $2
+        AND format = $3",
+        mod_id,
+        size,
+        format as LogoFormat
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_logo_renditions::get_path query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Replaces every rendition of the mod's logo. Passing nothing removes them.
+pub async fn replace(
+    mod_id: &str,
+    renditions: &[StoredRendition],
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!("DELETE FROM mod_logo_renditions WHERE mod_id = $1", mod_id)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("mod_logo_renditions::replace delete failed: {e}"))?;
+
+    for rendition in renditions {
+        sqlx::query!(
+            "INSERT INTO mod_logo_renditions (mod_id, size, format, path)
+            VALUES ($1, $2, $3, $4)",
+            mod_id,
+            rendition.size,
+            rendition.format as LogoFormat,
+            rendition.path
+        )
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("mod_logo_renditions::replace insert failed: {e}"))?;
+    }
+
+    Ok(())
+}
+
+/// Mods that have a logo, optionally only the ones without any renditions
+pub async fn get_mods_with_logo(
+    only_missing: bool,
+    conn: &mut PgConnection,
+) -> Result<Vec<String>, DatabaseError> {
+    sqlx::query_scalar!(
+        "SELECT m.id
+        FROM mods m
+        WHERE m.image IS NOT NULL
+        AND length(m.image) > 0
+        AND (
+            NOT $1
+            OR NOT EXISTS (SELECT 1 FROM mod_logo_renditions r WHERE r.mod_id = m.id)
+        )
+        ORDER BY m.id",
+        only_missing
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_logo_renditions::get_mods_with_logo query failed: {e}"))
+    .map_err(|e| e.into())
+}
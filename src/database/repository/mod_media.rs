@@ -0,0 +1,200 @@
+use sqlx::PgConnection;
+
+use crate::database::DatabaseError;
+use crate::types::models::mod_media::{ModMedia, ModMediaKind, ModMediaSource};
+
+// `url` holds the stored location, which for screenshots is a path inside
+// public storage. Callers turn those into URLs.
+
+pub async fn get_for_mod(
+    mod_id: &str,
+    conn: &mut PgConnection,
+) -> Result<Vec<ModMedia>, DatabaseError> {
+    sqlx::query_as!(
+        ModMedia,
+        r#"SELECT
+            id,
+            kind as "kind: ModMediaKind",
+            source as "source: ModMediaSource",
+            location as url,
+            caption,
+            position
+        FROM mod_media
+        WHERE mod_id = $1
+        ORDER BY position, id"#,
+        mod_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_media::get_for_mod query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_one(
+    mod_id: &str,
+    id: i32,
+    conn: &mut PgConnection,
+) -> Result<Option<ModMedia>, DatabaseError> {
+    sqlx::query_as!(
+        ModMedia,
+        r#"SELECT
+            id,
+            kind as "kind: ModMediaKind",
+            source as "source: ModMediaSource",
+            location as url,
+            caption,
+            position
+        FROM mod_media
+        WHERE mod_id = $1
+        AND id = $2"#,
+        mod_id,
+        id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_media::get_one query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Locks the mod's row until the transaction ends, so concurrent additions
+/// can't both fit under the limit, then counts its media from `source`, or
+/// all of it without one
+pub async fn lock_and_count(
+    mod_id: &str,
+    source: Option<ModMediaSource>,
+    conn: &mut PgConnection,
+) -> Result<i64, DatabaseError> {
+    sqlx::query!("SELECT id FROM mods WHERE id = $1 FOR UPDATE", mod_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("mod_media::lock_and_count lock failed: {e}"))?;
+
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!"
+        FROM mod_media
+        WHERE mod_id = $1
+        AND ($2::mod_media_source IS NULL OR source = $2)"#,
+        mod_id,
+        source as Option<ModMediaSource>
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_media::lock_and_count query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Adds the media after everything the mod already has
+pub async fn create(
+    mod_id: &str,
+    kind: ModMediaKind,
+    source: ModMediaSource,
+    location: &str,
+    caption: Option<&str>,
+    conn: &mut PgConnection,
+) -> Result<ModMedia, DatabaseError> {
+    sqlx::query_as!(
+        ModMedia,
+        r#"INSERT INTO mod_media (mod_id, kind, source, location, caption, position)
+        VALUES (
+            $1, $2, $3, $4, $5,
+            (SELECT COALESCE(MAX(position) + 1, 0) FROM mod_media WHERE mod_id = $1)
+        )
+        RETURNING
+            id,
+            kind as "kind: ModMediaKind",
+            source as "source: ModMediaSource",
+            location as url,
+            caption,
+            position"#,
+        mod_id,
+        kind as ModMediaKind,
+        source as ModMediaSource,
+        location,
+        caption
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_media::create query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn update_caption(
+    id: i32,
+    caption: Option<&str>,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mod_media SET caption = $1 WHERE id = $2",
+        caption,
+        id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_media::update_caption query failed: {e}"))?;
+
+    Ok(())
+}
+
+pub async fn delete(id: i32, conn: &mut PgConnection) -> Result<(), DatabaseError> {
+    sqlx::query!("DELETE FROM mod_media WHERE id = $1", id)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("mod_media::delete query failed: {e}"))?;
+
+    Ok(())
+}
+
+/// Sets the positions of a mod's media to the order of `ids`
+pub async fn reorder(ids: &[i32], conn: &mut PgConnection) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mod_media m
+        SET position = o.ord - 1
+        FROM unnest($1::int[]) WITH ORDINALITY o(id, ord)
+        WHERE m.id = o.id",
+        ids
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_media::reorder query failed: {e}"))?;
+
+    Ok(())
+}
+
+/// Replaces the screenshots that came from a previous .geode. The new ones
+/// are put in front of everything else.
+pub async fn replace_from_mod_file(
+    mod_id: &str,
+    locations: &[String],
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "DELETE FROM mod_media WHERE mod_id = $1 AND source = 'mod_file'",
+        mod_id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_media::replace_from_mod_file delete failed: {e}"))?;
+
+    let existing: Vec<i32> = get_for_mod(mod_id, conn)
+        .await?
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+
+    let mut ids = Vec::with_capacity(locations.len() + existing.len());
+    for location in locations {
+        let media = create(
+            mod_id,
+            ModMediaKind::Screenshot,
+            ModMediaSource::ModFile,
+            location,
+            None,
+            conn,
+        )
+        .await?;
+        ids.push(media.id);
+    }
+    ids.extend(existing);
+
+    reorder(&ids, conn).await
+}
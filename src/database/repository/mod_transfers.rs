@@ -0,0 +1,122 @@
+use crate::database::DatabaseError;
+use crate::types::models::mod_transfer::{ModTransfer, ModTransferStatus};
+use chrono::Utc;
+use sqlx::PgConnection;
+
+pub async fn get_pending_for_mod(
+    mod_id: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<ModTransfer>, DatabaseError> {
+    sqlx::query_as!(
+        ModTransfer,
+        r#"SELECT
+            id, mod_id, from_developer_id, to_developer_id, initiated_by,
+            status as "status: _", created_at, resolved_at
+        FROM mod_ownership_transfers
+        WHERE mod_id = $1
+        AND status = 'pending'"#,
+        mod_id
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch pending transfer for mod {mod_id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_pending_for_developer(
+    developer_id: i32,
+    conn: &mut PgConnection,
+) -> Result<Vec<ModTransfer>, DatabaseError> {
+    sqlx::query_as!(
+        ModTransfer,
+        r#"SELECT
+            id, mod_id, from_developer_id, to_developer_id, initiated_by,
+            status as "status: _", created_at, resolved_at
+        FROM mod_ownership_transfers
+        WHERE to_developer_id = $1
+        AND status = 'pending'
+        ORDER BY created_at DESC"#,
+        developer_id
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| {
+        log::error!("Failed to fetch pending transfers for developer {developer_id}: {e}")
+    })
+    .map_err(|e| e.into())
+}
+
+/// Creates a transfer. Forced transfers are created already resolved.
+pub async fn create(
+    mod_id: &str,
+    from_developer_id: Option<i32>,
+    to_developer_id: i32,
+    initiated_by: i32,
+    status: ModTransferStatus,
+    conn: &mut PgConnection,
+) -> Result<ModTransfer, DatabaseError> {
+    let resolved_at = (status != ModTransferStatus::Pending).then(Utc::now);
+
+    sqlx::query_as!(
+        ModTransfer,
+        r#"INSERT INTO mod_ownership_transfers
+            (mod_id, from_developer_id, to_developer_id, initiated_by, status, resolved_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING
+            id, mod_id, from_developer_id, to_developer_id, initiated_by,
+            status as "status: _", created_at, resolved_at"#,
+        mod_id,
+        from_developer_id,
+        to_developer_id,
+        initiated_by,
+        status as ModTransferStatus,
+        resolved_at
+    )
+    .fetch_one(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to create transfer for mod {mod_id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn resolve(
+    id: i32,
+    status: ModTransferStatus,
+    conn: &mut PgConnection,
+) -> Result<ModTransfer, DatabaseError> {
+    sqlx::query_as!(
+        ModTransfer,
+        r#"UPDATE mod_ownership_transfers
+        SET status = $1,
+            resolved_at = NOW()
+        WHERE id = $2
+        RETURNING
+            id, mod_id, from_developer_id, to_developer_id, initiated_by,
+            status as "status: _", created_at, resolved_at"#,
+        status as ModTransferStatus,
+        id
+    )
+    .fetch_one(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to resolve transfer {id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Cancels the pending transfer of a mod, if there is one
+pub async fn cancel_pending_for_mod(
+    mod_id: &str,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mod_ownership_transfers
+        SET status = 'cancelled',
+            resolved_at = NOW()
+        WHERE mod_id = $1
+        AND status = 'pending'",
+        mod_id
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to cancel pending transfer for mod {mod_id}: {e}"))?;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::database::DatabaseError;
+use sqlx::PgConnection;
+
+/// Recomputes the scores used by the `trending` and `popular_this_week` sorts
+/// from the last 28 days of downloads. Mods without downloads in the last 14
+/// days don't get a row.
+///
+/// The trending score weighs each day's downloads with a half-life of 2 days,
+/// divided by the square root of the mod's average daily downloads in the 14
+/// days before that, so mods that are picking up beat ones that have always
+/// been downloaded a lot.
+pub async fn refresh(conn: &mut PgConnection) -> Result<u64, DatabaseError> {
+    sqlx::query!("DELETE FROM mod_trending_scores")
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| log::error!("mod_trending_scores::refresh delete failed: {e}"))?;
+
+    sqlx::query!(
+        "WITH today AS (
+            SELECT (NOW() AT TIME ZONE 'UTC')::date AS day
+        ),
+        daily AS (
+            SELECT mv.mod_id, s.day, s.downloads::bigint AS downloads
+            FROM mod_version_download_stats s
+            INNER JOIN mod_versions mv ON mv.id = s.mod_version_id
+            WHERE s.day > (SELECT day FROM today) - 28
+            UNION ALL
+            SELECT mv.mod_id, (md.time_downloaded AT TIME ZONE 'UTC')::date, COUNT(*)
+            FROM mod_downloads md
+            INNER JOIN mod_versions mv ON mv.id = md.mod_version_id
+            WHERE (md.time_downloaded AT TIME ZONE 'UTC')::date > (SELECT day FROM today) - 28
+            AND NOT md.discounted
+            AND NOT EXISTS (
+                SELECT 1 FROM mod_version_download_stats s
+                WHERE s.mod_version_id = md.mod_version_id
+                AND s.day = (md.time_downloaded AT TIME ZONE 'UTC')::date
+            )
+            GROUP BY 1, 2
+        )
+        INSERT INTO mod_trending_scores (mod_id, weekly_downloads, trending_score)
+        SELECT
+            d.mod_id,
+            COALESCE(SUM(d.downloads) FILTER (WHERE d.day > t.day - 7), 0),
+            COALESCE(
+                SUM(d.downloads * power(0.5, (t.day - d.day) / 2.0))
+                    FILTER (WHERE d.day > t.day - 14),
+                0
+            ) / sqrt(1 + COALESCE(SUM(d.downloads) FILTER (WHERE d.day <= t.day - 14), 0) / 14.0)
+        FROM daily d
+        CROSS JOIN today t
+        GROUP BY d.mod_id, t.day
+        HAVING COUNT(*) FILTER (WHERE d.day > t.day - 14) > 0"
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_trending_scores::refresh insert failed: {e}"))
+    .map(|x| x.rows_affected())
+    .map_err(|e| e.into())
+}
@@ -0,0 +1,72 @@
+use sqlx::PgConnection;
+
+use crate::database::DatabaseError;
+use crate::types::models::mod_version_file::ModVersionFile;
+
+pub async fn get_for_version(
+    mod_version_id: i32,
+    conn: &mut PgConnection,
+) -> Result<Vec<ModVersionFile>, DatabaseError> {
+    struct Row {
+        name: String,
+        size: i64,
+        crc32: i64,
+    }
+
+    sqlx::query_as!(
+        Row,
+        "SELECT name, size, crc32
+        FROM mod_version_files
+        WHERE mod_version_id = $1
+        ORDER BY position",
+        mod_version_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_version_files::get_for_version query failed: {e}"))?
+    .into_iter()
+    .map(|r| {
+        Ok(ModVersionFile {
+            crc32: u32::try_from(r.crc32).map_err(|_| {
+                DatabaseError::InvalidInput(format!("Invalid CRC32 {} for {}", r.crc32, r.name))
+            })?,
+            name: r.name,
+            size: r.size,
+        })
+    })
+    .collect()
+}
+
+/// Replaces the listing, for when a pending version gets a new .geode
+pub async fn replace(
+    mod_version_id: i32,
+    files: &[ModVersionFile],
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "DELETE FROM mod_version_files WHERE mod_version_id = $1",
+        mod_version_id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_version_files::replace delete failed: {e}"))?;
+
+    let names: Vec<String> = files.iter().map(|f| f.name.clone()).collect();
+    let sizes: Vec<i64> = files.iter().map(|f| f.size).collect();
+    let crcs: Vec<i64> = files.iter().map(|f| i64::from(f.crc32)).collect();
+
+    sqlx::query!(
+        "INSERT INTO mod_version_files (mod_version_id, position, name, size, crc32)
+        SELECT $1, f.ord, f.name, f.size, f.crc32
+        FROM unnest($2::text[], $3::bigint[], $4::bigint[]) WITH ORDINALITY f(name, size, crc32, ord)",
+        mod_version_id,
+        &names,
+        &sizes,
+        &crcs
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_version_files::replace insert failed: {e}"))?;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use super::mod_version_statuses;
+use super::{mod_version_files, mod_version_statuses};
 use crate::database::DatabaseError;
 use crate::types::{
     mod_json::ModJson,
@@ -143,6 +143,142 @@ pub async fn increment_downloads(id: i32, conn: &mut PgConnection) -> Result<(),
     Ok(())
 }
 
+/// Length of the review queue
+pub async fn count_pending(conn: &mut PgConnection) -> Result<i64, DatabaseError> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!"
+        FROM mod_versions mv
+        INNER JOIN mod_version_statuses mvs ON mvs.id = mv.status_id
+        WHERE mvs.status = 'pending'"#
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_versions::count_pending query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub struct FeedEntry {
+    pub mod_id: String,
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    /// The version's changelog, or the mod's current one for versions accepted before
+    /// changelogs were kept per version
+    pub changelog: Option<String>,
+    pub accepted_at: DateTime<Utc>,
+    /// First accepted version of the mod
+    pub is_new_mod: bool,
+    pub owner_username: Option<String>,
+    pub owner_display_name: Option<String>,
+}
+
+/// Accepted versions, newest first. Filters are optional and can be combined.
+/// The developer filter also matches mods of organizations the developer is a member of.
+pub async fn get_feed(
+    mod_id: Option<&str>,
+    developer_id: Option<i32>,
+    only_new_mods: bool,
+    limit: i64,
+    conn: &mut PgConnection,
+) -> Result<Vec<FeedEntry>, DatabaseError> {
+    sqlx::query_as!(
+        FeedEntry,
+        r#"SELECT
+            q.mod_id, q.name, q.version, q.description, q.changelog,
+            q.accepted_at,
+            q.is_new_mod as "is_new_mod!",
+            q.owner_username as "owner_username?",
+            q.owner_display_name as "owner_display_name?"
+        FROM (
+            SELECT
+                mv.mod_id, mv.name, mv.version, mv.description,
+                COALESCE(mv.changelog, m.changelog) as changelog,
+                mvs.updated_at as accepted_at,
+                NOT EXISTS (
+                    SELECT 1 FROM mod_versions o
+                    INNER JOIN mod_version_statuses os ON os.id = o.status_id
+                    WHERE o.mod_id = mv.mod_id
+                    AND os.status = 'accepted'
+                    AND o.id < mv.id
+                ) as is_new_mod,
+                d.username as owner_username,
+                d.display_name as owner_display_name
+            FROM mod_versions mv
+            INNER JOIN mods m ON m.id = mv.mod_id
+            INNER JOIN mod_version_statuses mvs ON mvs.id = mv.status_id
+            LEFT JOIN mods_developers md ON md.mod_id = mv.mod_id AND md.is_owner = true
+            LEFT JOIN developers d ON d.id = md.developer_id
+            WHERE mvs.status = 'accepted'
+            AND ($1::text IS NULL OR mv.mod_id = $1)
+            AND (
+                $2::int IS NULL
+                OR EXISTS (
+                    SELECT 1 FROM mods_developers f
+                    WHERE f.mod_id = mv.mod_id
+                    AND f.developer_id = $2
+                    UNION
+                    SELECT 1 FROM organization_members om
+                    WHERE om.organization_id = m.organization_id
+                    AND om.developer_id = $2
+                )
+            )
+        ) q
+        WHERE (NOT $3 OR q.is_new_mod)
+        ORDER BY q.accepted_at DESC, q.mod_id, q.version DESC
+        LIMIT $4"#,
+        mod_id,
+        developer_id,
+        only_new_mods,
+        limit
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_versions::get_feed query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub struct VersionChangelog {
+    pub version: String,
+    pub changelog: Option<String>,
+}
+
+/// Changelogs of every accepted version, most recently accepted first
+pub async fn get_changelogs(
+    mod_id: &str,
+    conn: &mut PgConnection,
+) -> Result<Vec<VersionChangelog>, DatabaseError> {
+    sqlx::query_as!(
+        VersionChangelog,
+        "SELECT mv.version, mv.changelog
+        FROM mod_versions mv
+        INNER JOIN mod_version_statuses mvs ON mvs.id = mv.status_id
+        WHERE mv.mod_id = $1
+        AND mvs.status = 'accepted'
+        ORDER BY mv.id DESC",
+        mod_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_versions::get_changelogs query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// The mod.json as it was submitted, if it was kept for this version
+pub async fn get_mod_json(
+    version_id: i32,
+    conn: &mut PgConnection,
+) -> Result<Option<String>, DatabaseError> {
+    sqlx::query_scalar!(
+        "SELECT mod_json FROM mod_versions WHERE id = $1",
+        version_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mod_versions::get_mod_json query failed: {e}"))
+    .map(Option::flatten)
+    .map_err(|e| e.into())
+}
+
 pub async fn create_from_json(
     json: &ModJson,
     make_accepted: bool,
@@ -168,9 +304,9 @@ pub async fn create_from_json(
         (name, version, description, download_link,
         hash, geode_major, geode_minor, geode_patch, geode_meta,
         early_load, api, mod_id, status_id, requires_patching,
-        created_at, updated_at)
+        changelog, mod_json, created_at, updated_at)
         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, 0, $13,
-        NOW(), NOW())
+        $14, $15, NOW(), NOW())
         RETURNING
             id, name, version, description,
             download_link, hash,
@@ -188,7 +324,9 @@ pub async fn create_from_json(
         json.early_load,
         json.api.is_some(),
         json.id,
-        json.requires_patching
+        json.requires_patching,
+        json.changelog,
+        json.raw
     )
     .fetch_one(&mut *conn)
     .await
@@ -196,6 +334,8 @@ pub async fn create_from_json(
 
     let id = row.id;
 
+    mod_version_files::replace(id, &json.files, conn).await?;
+
     let status = match make_accepted {
         true => ModVersionStatusEnum::Accepted,
         false => ModVersionStatusEnum::Pending,
@@ -271,6 +411,8 @@ pub async fn update_pending_version(
             early_load = $9,
             api = $10,
             description = $11,
+            changelog = $13,
+            mod_json = $14,
             updated_at = NOW()
         FROM mod_version_statuses mvs
         WHERE mv.status_id = mvs.id
@@ -301,7 +443,9 @@ pub async fn update_pending_version(
         &json.early_load,
         &json.api.is_some(),
         json.description.clone().unwrap_or_default(),
-        version_id
+        version_id,
+        json.changelog,
+        json.raw
     )
     .fetch_one(&mut *conn)
     .await
@@ -313,6 +457,8 @@ pub async fn update_pending_version(
         )
     })?;
 
+    mod_version_files::replace(version_id, &json.files, conn).await?;
+
     if make_accepted {
         sqlx::query!(
             "UPDATE mod_version_statuses
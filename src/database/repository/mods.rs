@@ -1,6 +1,10 @@
 use crate::{
     database::DatabaseError,
-    types::{mod_json::ModJson, models::mod_entity::Mod},
+    markdown,
+    types::{
+        mod_json::ModJson,
+        models::{developer::ModDeveloperRole, mod_entity::Mod},
+    },
 };
 use chrono::{DateTime, Utc};
 use sqlx::PgConnection;
@@ -35,6 +39,8 @@ impl ModRecordGetOne {
             about: self.about.clone(),
             changelog: self.changelog.clone(),
             links: None,
+            organization: None,
+            media: vec![],
         }
     }
 }
@@ -89,8 +95,10 @@ pub async fn create(json: &ModJson, conn: &mut PgConnection) -> Result<Mod, Data
             repository,
             changelog,
             about,
+            changelog_html,
+            about_html,
             image
-        ) VALUES ($1, $2, $3, $4, $5)
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
         RETURNING
             id, repository, about,
             changelog, featured,
@@ -100,6 +108,8 @@ pub async fn create(json: &ModJson, conn: &mut PgConnection) -> Result<Mod, Data
         json.repository,
         json.changelog,
         json.about,
+        json.changelog.as_deref().map(markdown::render_html),
+        json.about.as_deref().map(markdown::render_html),
         &vec![]
     )
     .fetch_one(conn)
@@ -114,21 +124,23 @@ pub async fn assign_owner(
     developer_id: i32,
     conn: &mut PgConnection,
 ) -> Result<(), DatabaseError> {
-    assign_developer(id, developer_id, true, conn).await
+    assign_developer(id, developer_id, true, ModDeveloperRole::Maintainer, conn).await
 }
 
 pub async fn assign_developer(
     id: &str,
     developer_id: i32,
     owner: bool,
+    role: ModDeveloperRole,
     conn: &mut PgConnection,
 ) -> Result<(), DatabaseError> {
     sqlx::query!(
-        "INSERT INTO mods_developers (mod_id, developer_id, is_owner)
-        VALUES ($1, $2, $3)",
+        "INSERT INTO mods_developers (mod_id, developer_id, is_owner, role)
+        VALUES ($1, $2, $3, $4)",
         id,
         developer_id,
-        owner
+        owner,
+        role as ModDeveloperRole
     )
     .execute(conn)
     .await
@@ -139,6 +151,83 @@ pub async fn assign_developer(
     .map_err(|e| e.into())
 }
 
+pub async fn update_developer_role(
+    id: &str,
+    developer_id: i32,
+    role: ModDeveloperRole,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mods_developers
+        SET role = $1
+        WHERE mod_id = $2
+        AND developer_id = $3",
+        role as ModDeveloperRole,
+        id,
+        developer_id
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|x| {
+        log::error!("Couldn't update role of developer {developer_id} on mod {id}: {x}")
+    })
+    .map(|_| ())
+    .map_err(|e| e.into())
+}
+
+pub async fn set_organization(
+    id: &str,
+    organization_id: Option<i32>,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mods
+        SET organization_id = $1
+        WHERE id = $2",
+        organization_id,
+        id
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|x| log::error!("Couldn't set organization of mod {id}: {x}"))
+    .map(|_| ())
+    .map_err(|e| e.into())
+}
+
+/// Makes a developer the sole owner of a mod. The previous owner stays on as a regular developer.
+pub async fn transfer_ownership(
+    id: &str,
+    developer_id: i32,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mods_developers
+        SET is_owner = false
+        WHERE mod_id = $1
+        AND is_owner = true",
+        id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|x| log::error!("Couldn't remove current owner of mod {id}: {x}"))?;
+
+    sqlx::query!(
+        "INSERT INTO mods_developers (mod_id, developer_id, is_owner)
+        VALUES ($1, $2, true)
+        ON CONFLICT (mod_id, developer_id) DO UPDATE
+        SET is_owner = true",
+        id,
+        developer_id
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|x| {
+        log::error!("Couldn't transfer ownership of mod {id} to developer {developer_id}: {x}")
+    })?;
+
+    Ok(())
+}
+
 pub async fn unassign_developer(
     id: &str,
     developer_id: i32,
@@ -264,13 +353,17 @@ pub async fn update_with_json(
         about = $2,
         changelog = $3,
         image = $4,
+        about_html = $6,
+        changelog_html = $7,
         updated_at = NOW()
         WHERE id = $5",
         json.repository,
         json.about,
         json.changelog,
         json.logo,
-        the_mod.id
+        the_mod.id,
+        json.about.as_deref().map(markdown::render_html),
+        json.changelog.as_deref().map(markdown::render_html)
     )
     .execute(conn)
     .await
@@ -294,13 +387,17 @@ pub async fn update_with_json_moved(
         about = $2,
         changelog = $3,
         image = $4,
+        about_html = $6,
+        changelog_html = $7,
         updated_at = NOW()
         WHERE id = $5",
         json.repository,
         json.about,
         json.changelog,
         json.logo,
-        the_mod.id
+        the_mod.id,
+        json.about.as_deref().map(markdown::render_html),
+        json.changelog.as_deref().map(markdown::render_html)
     )
     .execute(conn)
     .await
@@ -328,3 +425,72 @@ pub async fn touch_created_at(id: &str, conn: &mut PgConnection) -> Result<(), D
 
     Ok(())
 }
+
+pub struct RenderedMarkdown {
+    pub id: String,
+    pub about: Option<String>,
+    pub changelog: Option<String>,
+    pub about_html: Option<String>,
+    pub changelog_html: Option<String>,
+}
+
+/// HTML versions of about.md and changelog.md, along with the markdown for mods
+/// that haven't been rendered yet
+pub async fn get_rendered_markdown(
+    ids: &[String],
+    conn: &mut PgConnection,
+) -> Result<Vec<RenderedMarkdown>, DatabaseError> {
+    sqlx::query_as!(
+        RenderedMarkdown,
+        "SELECT id, about, changelog, about_html, changelog_html
+        FROM mods
+        WHERE id = ANY($1)",
+        ids
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mods::get_rendered_markdown query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Mods with markdown that was never rendered, or every mod with markdown if `all` is set
+pub async fn get_markdown_to_render(
+    all: bool,
+    conn: &mut PgConnection,
+) -> Result<Vec<RenderedMarkdown>, DatabaseError> {
+    sqlx::query_as!(
+        RenderedMarkdown,
+        "SELECT id, about, changelog, about_html, changelog_html
+        FROM mods
+        WHERE (about IS NOT NULL AND ($1 OR about_html IS NULL))
+        OR (changelog IS NOT NULL AND ($1 OR changelog_html IS NULL))
+        ORDER BY id",
+        all
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mods::get_markdown_to_render query failed: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn update_rendered_markdown(
+    id: &str,
+    about_html: Option<&str>,
+    changelog_html: Option<&str>,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "UPDATE mods
+        SET about_html = $2,
+        changelog_html = $3
+        WHERE id = $1",
+        id,
+        about_html,
+        changelog_html
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("mods::update_rendered_markdown query failed: {e}"))?;
+
+    Ok(())
+}
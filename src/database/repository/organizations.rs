@@ -0,0 +1,253 @@
+use crate::database::DatabaseError;
+use crate::types::models::organization::{
+    DeveloperTeam, Organization, OrganizationMember, OrganizationRole,
+};
+use sqlx::PgConnection;
+use std::collections::HashMap;
+
+/// Creates an organization and makes `owner_id` its owner
+pub async fn create(
+    name: &str,
+    display_name: &str,
+    owner_id: i32,
+    conn: &mut PgConnection,
+) -> Result<Organization, DatabaseError> {
+    let organization = sqlx::query_as!(
+        Organization,
+        "INSERT INTO organizations (name, display_name)
+        VALUES ($1, $2)
+        RETURNING id, name, display_name, created_at",
+        name,
+        display_name
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to create organization {name}: {e}"))?;
+
+    set_member(organization.id, owner_id, OrganizationRole::Owner, conn).await?;
+
+    Ok(organization)
+}
+
+pub async fn get_one(
+    id: i32,
+    conn: &mut PgConnection,
+) -> Result<Option<Organization>, DatabaseError> {
+    sqlx::query_as!(
+        Organization,
+        "SELECT id, name, display_name, created_at
+        FROM organizations
+        WHERE id = $1",
+        id
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch organization {id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_one_by_name(
+    name: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<Organization>, DatabaseError> {
+    sqlx::query_as!(
+        Organization,
+        "SELECT id, name, display_name, created_at
+        FROM organizations
+        WHERE LOWER(name) = LOWER($1)",
+        name
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch organization {name}: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_members(
+    id: i32,
+    conn: &mut PgConnection,
+) -> Result<Vec<OrganizationMember>, DatabaseError> {
+    sqlx::query_as!(
+        OrganizationMember,
+        r#"SELECT
+            d.id,
+            d.username,
+            d.display_name,
+            om.role as "role: _"
+        FROM organization_members om
+        INNER JOIN developers d ON d.id = om.developer_id
+        WHERE om.organization_id = $1
+        ORDER BY om.role DESC, d.id ASC"#,
+        id
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch members of organization {id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_role(
+    id: i32,
+    developer_id: i32,
+    conn: &mut PgConnection,
+) -> Result<Option<OrganizationRole>, DatabaseError> {
+    sqlx::query_scalar!(
+        r#"SELECT role as "role: OrganizationRole"
+        FROM organization_members
+        WHERE organization_id = $1
+        AND developer_id = $2"#,
+        id,
+        developer_id
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| {
+        log::error!("Failed to fetch role of developer {developer_id} in organization {id}: {e}")
+    })
+    .map_err(|e| e.into())
+}
+
+pub async fn count_owners(id: i32, conn: &mut PgConnection) -> Result<i64, DatabaseError> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!"
+        FROM organization_members
+        WHERE organization_id = $1
+        AND role = 'owner'"#,
+        id
+    )
+    .fetch_one(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to count owners of organization {id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+/// Adds a member, or updates their role if they already are one
+pub async fn set_member(
+    id: i32,
+    developer_id: i32,
+    role: OrganizationRole,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "INSERT INTO organization_members (organization_id, developer_id, role)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (organization_id, developer_id) DO UPDATE
+        SET role = EXCLUDED.role",
+        id,
+        developer_id,
+        role as OrganizationRole
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|e| {
+        log::error!("Failed to set developer {developer_id} as member of organization {id}: {e}")
+    })?;
+
+    Ok(())
+}
+
+pub async fn remove_member(
+    id: i32,
+    developer_id: i32,
+    conn: &mut PgConnection,
+) -> Result<bool, DatabaseError> {
+    let result = sqlx::query!(
+        "DELETE FROM organization_members
+        WHERE organization_id = $1
+        AND developer_id = $2",
+        id,
+        developer_id
+    )
+    .execute(conn)
+    .await
+    .inspect_err(|e| {
+        log::error!("Failed to remove developer {developer_id} from organization {id}: {e}")
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_for_developer(
+    developer_id: i32,
+    conn: &mut PgConnection,
+) -> Result<Vec<DeveloperTeam>, DatabaseError> {
+    sqlx::query_as!(
+        DeveloperTeam,
+        r#"SELECT
+            o.id,
+            o.name,
+            o.display_name,
+            om.role as "role: _"
+        FROM organizations o
+        INNER JOIN organization_members om ON om.organization_id = o.id
+        WHERE om.developer_id = $1
+        ORDER BY o.name ASC"#,
+        developer_id
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch organizations of developer {developer_id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_for_mod(
+    mod_id: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<Organization>, DatabaseError> {
+    sqlx::query_as!(
+        Organization,
+        "SELECT o.id, o.name, o.display_name, o.created_at
+        FROM organizations o
+        INNER JOIN mods m ON m.organization_id = o.id
+        WHERE m.id = $1",
+        mod_id
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch organization for mod {mod_id}: {e}"))
+    .map_err(|e| e.into())
+}
+
+pub async fn get_for_mods(
+    mod_ids: &[String],
+    conn: &mut PgConnection,
+) -> Result<HashMap<String, Organization>, DatabaseError> {
+    if mod_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    struct QueryResult {
+        mod_id: String,
+        id: i32,
+        name: String,
+        display_name: String,
+        created_at: chrono::DateTime<chrono::Utc>,
+    }
+
+    let result = sqlx::query_as!(
+        QueryResult,
+        "SELECT m.id as mod_id, o.id, o.name, o.display_name, o.created_at
+        FROM organizations o
+        INNER JOIN mods m ON m.organization_id = o.id
+        WHERE m.id = ANY($1)",
+        mod_ids
+    )
+    .fetch_all(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch organizations for mods: {e}"))?;
+
+    Ok(result
+        .into_iter()
+        .map(|x| {
+            (
+                x.mod_id,
+                Organization {
+                    id: x.id,
+                    name: x.name,
+                    display_name: x.display_name,
+                    created_at: x.created_at,
+                },
+            )
+        })
+        .collect())
+}
@@ -3,8 +3,10 @@ use chrono::{Days, Utc};
 use sqlx::PgConnection;
 use uuid::Uuid;
 
+/// Issues a refresh token for the session identified by `auth_token_id`
 pub async fn generate_token(
     developer_id: i32,
+    auth_token_id: i32,
     conn: &mut PgConnection,
 ) -> Result<Uuid, DatabaseError> {
     let token = Uuid::new_v4();
@@ -12,11 +14,12 @@ pub async fn generate_token(
     let expiry = Utc::now().checked_add_days(Days::new(30)).unwrap();
 
     sqlx::query!(
-        "INSERT INTO refresh_tokens (token, developer_id, expires_at)
-        VALUES ($1, $2, $3)",
+        "INSERT INTO refresh_tokens (token, developer_id, expires_at, auth_token_id)
+        VALUES ($1, $2, $3, $4)",
         hash,
         developer_id,
-        expiry
+        expiry,
+        auth_token_id
     )
     .execute(conn)
     .await
@@ -25,6 +28,27 @@ pub async fn generate_token(
     Ok(token)
 }
 
+/// Returns the session a valid refresh token belongs to.
+/// Tokens issued before sessions were tracked don't have one.
+pub async fn get_session_id(
+    token: Uuid,
+    conn: &mut PgConnection,
+) -> Result<Option<i32>, DatabaseError> {
+    let hash = sha256::digest(token.to_string());
+
+    sqlx::query_scalar!(
+        "SELECT auth_token_id FROM refresh_tokens
+        WHERE token = $1
+        AND expires_at > NOW()",
+        hash
+    )
+    .fetch_optional(conn)
+    .await
+    .inspect_err(|e| log::error!("Failed to fetch refresh token session: {e}"))
+    .map(|x| x.flatten())
+    .map_err(|e| e.into())
+}
+
 pub async fn remove_token(token: Uuid, conn: &mut PgConnection) -> Result<(), DatabaseError> {
     let hash = sha256::digest(token.to_string());
     sqlx::query!(
@@ -0,0 +1,62 @@
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+
+use crate::database::DatabaseError;
+use crate::types::models::stats::StatsSnapshot;
+
+/// Replaces the snapshot for the same day if there already is one
+pub async fn upsert(
+    snapshot: &StatsSnapshot,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        "INSERT INTO stats_snapshots
+            (day, total_mod_count, total_mod_downloads, total_registered_developers,
+            pending_mod_versions, total_geode_downloads)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (day) DO UPDATE SET
+            total_mod_count = EXCLUDED.total_mod_count,
+            total_mod_downloads = EXCLUDED.total_mod_downloads,
+            total_registered_developers = EXCLUDED.total_registered_developers,
+            pending_mod_versions = EXCLUDED.pending_mod_versions,
+            total_geode_downloads = EXCLUDED.total_geode_downloads,
+            created_at = NOW()",
+        snapshot.date,
+        snapshot.total_mod_count,
+        snapshot.total_mod_downloads,
+        snapshot.total_registered_developers,
+        snapshot.pending_mod_versions,
+        snapshot.total_geode_downloads
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("stats_snapshots::upsert query failed: {e}"))?;
+
+    Ok(())
+}
+
+pub async fn get_range(
+    from: NaiveDate,
+    to: NaiveDate,
+    conn: &mut PgConnection,
+) -> Result<Vec<StatsSnapshot>, DatabaseError> {
+    sqlx::query_as!(
+        StatsSnapshot,
+        "SELECT
+            day as date,
+            total_mod_count,
+            total_mod_downloads,
+            total_registered_developers,
+            pending_mod_versions,
+            total_geode_downloads
+        FROM stats_snapshots
+        WHERE day BETWEEN $1 AND $2
+        ORDER BY day",
+        from,
+        to
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .inspect_err(|e| log::error!("stats_snapshots::get_range query failed: {e}"))
+    .map_err(|e| e.into())
+}
@@ -11,6 +11,7 @@ use crate::database::repository::{
 };
 use crate::endpoints::auth::TokensResponse;
 use crate::endpoints::ApiError;
+use crate::extractors::client_info::ClientInfo;
 use crate::{auth::github, types::api::ApiResponse};
 
 #[derive(Deserialize, ToSchema)]
@@ -109,6 +110,7 @@ pub async fn start_github_web_login(data: web::Data<AppData>) -> Result<impl Res
 pub async fn github_web_callback(
     json: web::Json<CallbackParams>,
     data: web::Data<AppData>,
+    client_info: ClientInfo,
 ) -> Result<impl Responder, ApiError> {
     let mut pool = data.db().acquire().await?;
 
@@ -136,15 +138,15 @@ pub async fn github_web_callback(
 
     let developer = developers::fetch_or_insert_github(user.id, &user.username, &mut tx).await?;
 
-    let token = auth_tokens::generate_token(developer.id, true, &mut tx).await?;
-    let refresh = refresh_tokens::generate_token(developer.id, &mut tx).await?;
+    let token = auth_tokens::generate_token(developer.id, true, &client_info, &mut tx).await?;
+    let refresh = refresh_tokens::generate_token(developer.id, token.id, &mut tx).await?;
 
     tx.commit().await?;
 
     Ok(web::Json(ApiResponse {
         error: "".to_string(),
         payload: TokensResponse {
-            access_token: token.to_string(),
+            access_token: token.token.to_string(),
             refresh_token: refresh.to_string(),
         },
     }))
@@ -167,6 +169,7 @@ pub async fn poll_github_login(
     json: web::Json<PollParams>,
     data: web::Data<AppData>,
     connection_info: ConnectionInfo,
+    client_info: ClientInfo,
 ) -> Result<impl Responder, ApiError> {
     let mut pool = data.db().acquire().await?;
 
@@ -228,10 +231,10 @@ pub async fn poll_github_login(
 
     let expiry = json.expiry.is_some_and(|e| e);
 
-    let token = auth_tokens::generate_token(developer.id, expiry, &mut tx).await?;
+    let token = auth_tokens::generate_token(developer.id, expiry, &client_info, &mut tx).await?;
     let refresh = {
         if expiry {
-            Some(refresh_tokens::generate_token(developer.id, &mut tx).await?)
+            Some(refresh_tokens::generate_token(developer.id, token.id, &mut tx).await?)
         } else {
             None
         }
@@ -243,14 +246,14 @@ pub async fn poll_github_login(
         Ok(HttpResponse::build(StatusCode::OK).json(ApiResponse {
             error: "".to_string(),
             payload: TokensResponse {
-                access_token: token.to_string(),
+                access_token: token.token.to_string(),
                 refresh_token: refresh.unwrap().to_string(),
             },
         }))
     } else {
         Ok(HttpResponse::build(StatusCode::OK).json(ApiResponse {
             error: "".to_string(),
-            payload: token.to_string(),
+            payload: token.token.to_string(),
         }))
     }
 }
@@ -270,6 +273,7 @@ pub async fn poll_github_login(
 pub async fn github_token_login(
     json: web::Json<TokenLoginParams>,
     data: web::Data<AppData>,
+    client_info: ClientInfo,
 ) -> Result<impl Responder, ApiError> {
     let client = github::GithubClient::new(
         data.github().client_id().to_string(),
@@ -289,12 +293,12 @@ pub async fn github_token_login(
     let mut tx = pool.begin().await?;
 
     let developer = developers::fetch_or_insert_github(user.id, &user.username, &mut tx).await?;
-    let token = auth_tokens::generate_token(developer.id, true, &mut tx).await?;
+    let token = auth_tokens::generate_token(developer.id, true, &client_info, &mut tx).await?;
 
     tx.commit().await?;
 
     Ok(web::Json(ApiResponse {
         error: "".to_string(),
-        payload: token.to_string(),
+        payload: token.token.to_string(),
     }))
 }
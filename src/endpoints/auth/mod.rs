@@ -2,6 +2,7 @@ use crate::config::AppData;
 use crate::database::repository::{auth_tokens, developers, refresh_tokens};
 use crate::endpoints::ApiError;
 use crate::extractors::auth::Auth;
+use crate::extractors::client_info::ClientInfo;
 use crate::types::api::ApiResponse;
 use actix_web::{post, web, Responder};
 use serde::{Deserialize, Serialize};
@@ -38,6 +39,7 @@ pub async fn refresh_token(
     json: web::Json<RefreshBody>,
     data: web::Data<AppData>,
     auth: Auth,
+    client: ClientInfo,
 ) -> Result<impl Responder, ApiError> {
     let auth_token = auth.token().ok();
 
@@ -52,14 +54,27 @@ pub async fn refresh_token(
             "Invalid or expired refresh token".into(),
         ))?;
 
+    let session = refresh_tokens::get_session_id(refresh_token, &mut conn).await?;
+
     let mut tx = conn.begin().await?;
 
-    let new_auth = auth_tokens::generate_token(found.id, true, &mut tx).await?;
-    let new_refresh = refresh_tokens::generate_token(found.id, &mut tx).await?;
+    // Keep the same session when possible, so it shows up as a single device
+    let rotated = match session {
+        Some(id) => auth_tokens::rotate_token(id, &mut tx).await?,
+        None => None,
+    };
+
+    let new_auth = match rotated {
+        Some(t) => t,
+        None => {
+            if let Some(auth) = auth_token {
+                auth_tokens::remove_token(auth, &mut tx).await?;
+            }
+            auth_tokens::generate_token(found.id, true, &client, &mut tx).await?
+        }
+    };
+    let new_refresh = refresh_tokens::generate_token(found.id, new_auth.id, &mut tx).await?;
 
-    if let Some(auth) = auth_token {
-        auth_tokens::remove_token(auth, &mut tx).await?;
-    }
     refresh_tokens::remove_token(refresh_token, &mut tx).await?;
 
     tx.commit().await?;
@@ -67,7 +82,7 @@ pub async fn refresh_token(
     Ok(web::Json(ApiResponse {
         error: "".into(),
         payload: TokensResponse {
-            access_token: new_auth.to_string(),
+            access_token: new_auth.token.to_string(),
             refresh_token: new_refresh.to_string(),
         },
     }))
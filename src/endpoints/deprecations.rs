@@ -5,6 +5,7 @@ use crate::{
     extractors::auth::Auth,
     types::api::ApiResponse,
     types::models::deprecations::Deprecation,
+    types::models::developer::ModDeveloperRole,
 };
 use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
 use serde::Deserialize;
@@ -55,7 +56,7 @@ pub async fn index(
     let mut pool = data.db().acquire().await?;
 
     if !mods::exists(&path.id, &mut pool).await? {
-        return Err(ApiError::NotFound(format!("Mod id {} not found", path.id)));
+        return Err(ApiError::ModNotFound(path.id.clone()));
     }
 
     let deps = deprecations::get_for_mods(std::slice::from_ref(&path.id), &mut pool).await?;
@@ -95,10 +96,12 @@ pub async fn store(
     let mut tx = pool.begin().await?;
 
     if !mods::exists(&path.id, &mut tx).await? {
-        return Err(ApiError::NotFound(format!("Mod id {} not found", path.id)));
+        return Err(ApiError::ModNotFound(path.id.clone()));
     }
 
-    if !dev.admin && !developers::owns_mod(dev.id, &path.id, &mut tx).await? {
+    if !dev.admin
+        && !developers::has_mod_role(dev.id, &path.id, ModDeveloperRole::Editor, &mut tx).await?
+    {
         return Err(ApiError::Authorization);
     }
 
@@ -142,7 +145,7 @@ pub async fn update(
     let mut tx = pool.begin().await?;
 
     if !mods::exists(&path.id, &mut tx).await? {
-        return Err(ApiError::NotFound(format!("Mod id {} not found", path.id)));
+        return Err(ApiError::ModNotFound(path.id.clone()));
     }
 
     if let Some(by) = &json.by {
@@ -164,7 +167,9 @@ pub async fn update(
         )));
     }
 
-    if !dev.admin && !developers::owns_mod(dev.id, &path.id, &mut tx).await? {
+    if !dev.admin
+        && !developers::has_mod_role(dev.id, &path.id, ModDeveloperRole::Editor, &mut tx).await?
+    {
         return Err(ApiError::Authorization);
     }
 
@@ -210,7 +215,7 @@ pub async fn delete(
     let mut pool = data.db().acquire().await?;
 
     if !mods::exists(&path.id, &mut pool).await? {
-        return Err(ApiError::NotFound(format!("Mod id {} not found", path.id)));
+        return Err(ApiError::ModNotFound(path.id.clone()));
     }
 
     let deprecation = deprecations::get(path.deprecation_id, &mut pool)
@@ -228,7 +233,9 @@ pub async fn delete(
         )));
     }
 
-    if !dev.admin && !developers::owns_mod(dev.id, &path.id, &mut pool).await? {
+    if !dev.admin
+        && !developers::has_mod_role(dev.id, &path.id, ModDeveloperRole::Editor, &mut pool).await?
+    {
         return Err(ApiError::Authorization);
     }
 
@@ -263,10 +270,12 @@ pub async fn clear_all(
     let mut pool = data.db().acquire().await?;
 
     if !mods::exists(&path.id, &mut pool).await? {
-        return Err(ApiError::NotFound(format!("Mod id {} not found", path.id)));
+        return Err(ApiError::ModNotFound(path.id.clone()));
     }
 
-    if !dev.admin && !developers::owns_mod(dev.id, &path.id, &mut pool).await? {
+    if !dev.admin
+        && !developers::has_mod_role(dev.id, &path.id, ModDeveloperRole::Editor, &mut pool).await?
+    {
         return Err(ApiError::Authorization);
     }
 
@@ -1,20 +1,24 @@
 use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
+use sqlx::Acquire;
 use utoipa::{ToSchema, IntoParams};
+use validator::Validate;
 
 use super::ApiError;
 use crate::config::AppData;
-use crate::database::repository::{auth_tokens, developers, mods, refresh_tokens};
+use crate::database::repository::{auth_tokens, developers, mods, organizations, refresh_tokens};
+use crate::images;
+use crate::storage::StorageDisk;
 use crate::types::api::{ApiResponse, PaginatedData};
 use crate::{
     extractors::auth::Auth,
-    types::{
-        models::{
-            developer::{ModDeveloper, Developer},
-            mod_entity::Mod,
+    types::models::{
+            developer::{ModDeveloper, ModDeveloperRole, Developer, DeveloperLinks, DeveloperProfile},
+            mod_entity::{Mod, ModQueries},
             mod_version_status::ModVersionStatusEnum,
+            session::Session,
         },
-    },
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
@@ -50,6 +54,23 @@ struct RemoveDevPath {
 #[derive(Deserialize, ToSchema)]
 struct AddDevPayload {
     username: String,
+    #[serde(default = "default_mod_developer_role")]
+    role: ModDeveloperRole,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct UpdateDevRolePath {
+    id: String,
+    username: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct UpdateDevRolePayload {
+    role: ModDeveloperRole,
+}
+
+fn default_mod_developer_role() -> ModDeveloperRole {
+    ModDeveloperRole::Maintainer
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -63,6 +84,11 @@ struct UpdateDeveloperPath {
     id: i32,
 }
 
+#[derive(Deserialize, IntoParams)]
+struct SessionPath {
+    id: i32,
+}
+
 #[derive(Deserialize, IntoParams)]
 struct DeveloperIndexQuery {
     query: Option<String>,
@@ -127,7 +153,7 @@ pub async fn add_developer_to_mod(
     let mut pool = data.db().acquire().await?;
 
     if !mods::exists(&path.id, &mut pool).await? {
-        return Err(ApiError::NotFound(format!("Mod id {} not found", path.id)));
+        return Err(ApiError::ModNotFound(path.id.clone()));
     }
     if !developers::owns_mod(dev.id, &path.id, &mut pool).await? {
         return Err(ApiError::Authorization);
@@ -140,7 +166,7 @@ pub async fn add_developer_to_mod(
             json.username
         )))?;
 
-    mods::assign_developer(&path.id, target.id, false, &mut pool).await?;
+    mods::assign_developer(&path.id, target.id, false, json.role, &mut pool).await?;
 
     Ok(HttpResponse::NoContent())
 }
@@ -172,7 +198,7 @@ pub async fn remove_dev_from_mod(
     let mut pool = data.db().acquire().await?;
 
     if !mods::exists(&path.id, &mut pool).await? {
-        return Err(ApiError::NotFound(format!("Mod id {} not found", path.id)));
+        return Err(ApiError::ModNotFound(path.id.clone()));
     }
 
     if !developers::owns_mod(dev.id, &path.id, &mut pool).await? {
@@ -187,10 +213,9 @@ pub async fn remove_dev_from_mod(
         )))?;
 
     if target.id == dev.id {
-        return Ok(HttpResponse::Conflict().json(ApiResponse {
-            error: "Cannot remove self from mod developer list".into(),
-            payload: "",
-        }));
+        return Err(ApiError::Conflict(
+            "Cannot remove self from mod developer list".into(),
+        ));
     }
 
     if !developers::has_access_to_mod(target.id, &path.id, &mut pool).await? {
@@ -205,6 +230,60 @@ pub async fn remove_dev_from_mod(
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// Change the role of a developer on a mod
+#[utoipa::path(
+    put,
+    path = "/v1/mods/{id}/developers/{username}",
+    tag = "developers",
+    params(UpdateDevRolePath),
+    request_body = UpdateDevRolePayload,
+    responses(
+        (status = 204, description = "Role updated successfully"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod or developer not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[put("v1/mods/{id}/developers/{username}")]
+pub async fn update_dev_role(
+    data: web::Data<AppData>,
+    path: web::Path<UpdateDevRolePath>,
+    json: web::Json<UpdateDevRolePayload>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    if !mods::exists(&path.id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(path.id.clone()));
+    }
+
+    if !developers::owns_mod(dev.id, &path.id, &mut pool).await? {
+        return Err(ApiError::Authorization);
+    }
+
+    let target = developers::get_one_by_username(&path.username, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound(format!(
+            "No developer found with username {}",
+            path.username
+        )))?;
+
+    if !developers::has_access_to_mod(target.id, &path.id, &mut pool).await? {
+        return Err(ApiError::NotFound(format!(
+            "{} is not a developer for this mod",
+            target.username
+        )));
+    }
+
+    mods::update_developer_role(&path.id, target.id, json.role, &mut pool).await?;
+
+    Ok(HttpResponse::NoContent())
+}
+
 /// Delete the current API token
 #[utoipa::path(
     delete,
@@ -258,11 +337,78 @@ pub async fn delete_tokens(
     Ok(HttpResponse::NoContent())
 }
 
-#[derive(Deserialize, ToSchema)]
+/// List active sessions for the current developer
+#[utoipa::path(
+    get,
+    path = "/v1/me/sessions",
+    tag = "developers",
+    responses(
+        (status = 200, description = "Active sessions", body = inline(ApiResponse<Vec<Session>>)),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[get("v1/me/sessions")]
+pub async fn get_sessions(
+    data: web::Data<AppData>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let token = auth.token()?;
+    let mut pool = data.db().acquire().await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: auth_tokens::get_sessions_for_developer(dev.id, token, &mut pool).await?,
+    }))
+}
+
+/// Revoke a session of the current developer, including its refresh token
+#[utoipa::path(
+    delete,
+    path = "/v1/me/sessions/{id}",
+    tag = "developers",
+    params(SessionPath),
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Session not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[delete("v1/me/sessions/{id}")]
+pub async fn delete_session(
+    data: web::Data<AppData>,
+    path: web::Path<SessionPath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    if !auth_tokens::remove_session(dev.id, path.id, &mut pool).await? {
+        return Err(ApiError::NotFound(format!("Session {} not found", path.id)));
+    }
+
+    Ok(HttpResponse::NoContent())
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
 struct UploadProfilePayload {
     display_name: String,
+    /// Omit to keep the current bio, pass an empty string to remove it
+    #[validate(length(max = 2000))]
+    bio: Option<String>,
+    /// Omit to keep the current links
+    #[validate(nested)]
+    links: Option<DeveloperLinks>,
 }
 
+const MAX_AVATAR_BYTES: usize = 2 * 1024 * 1024;
+
 /// Update the current developer's profile
 #[utoipa::path(
     put,
@@ -303,18 +449,122 @@ pub async fn update_profile(
         ));
     }
 
+    json.validate()?;
+
+    let mut tx = pool.begin().await?;
+
+    if json.bio.is_some() || json.links.is_some() {
+        let current = developers::get_profile_details(dev.id, &mut tx)
+            .await?
+            .unwrap_or_default();
+
+        let bio = match &json.bio {
+            Some(bio) => Some(bio.trim()).filter(|b| !b.is_empty()),
+            None => current.bio.as_deref(),
+        };
+        let links = json.links.as_ref().unwrap_or(&current.links);
+
+        developers::update_profile_details(dev.id, bio, links, &mut tx).await?;
+    }
+
+    let developer = developers::update_profile(dev.id, &json.display_name, &mut tx).await?;
+
+    tx.commit().await?;
+
     Ok(web::Json(ApiResponse {
         error: "".into(),
-        payload: developers::update_profile(dev.id, &json.display_name, &mut pool).await?,
+        payload: developer,
     }))
 }
 
+/// Upload a new avatar for the current developer
+#[utoipa::path(
+    put,
+    path = "/v1/me/avatar",
+    tag = "developers",
+    request_body(content = Vec<u8>, description = "PNG image, cropped to a square and scaled down to 256x256", content_type = "image/png"),
+    responses(
+        (status = 200, description = "Avatar updated, returns its URL", body = inline(ApiResponse<String>)),
+        (status = 400, description = "Invalid image"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[put("v1/me/avatar")]
+pub async fn update_avatar(
+    data: web::Data<AppData>,
+    mut payload: web::Payload,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|_| ApiError::BadRequest("Failed to read avatar".into()))?;
+        if body.len() + chunk.len() > MAX_AVATAR_BYTES {
+            return Err(ApiError::BadRequest(format!(
+                "Avatar is too large (max {} MB)",
+                MAX_AVATAR_BYTES / 1024 / 1024
+            )));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let avatar = images::process_avatar(&body).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    // Avatars are content-addressed and may be shared, so old files are left in place
+    let path = data
+        .public_storage()
+        .store_hashed_with_extension("avatars", &avatar, Some("png"))
+        .await
+        .inspect_err(|e| log::error!("Failed to store avatar for developer {}: {e}", dev.id))
+        .or(Err(ApiError::InternalError("Failed to store avatar".into())))?;
+
+    let mut pool = data.db().acquire().await?;
+    developers::update_avatar(dev.id, Some(&path), &mut pool).await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: data.public_storage().asset_url(&path),
+    }))
+}
+
+/// Remove the current developer's avatar
+#[utoipa::path(
+    delete,
+    path = "/v1/me/avatar",
+    tag = "developers",
+    responses(
+        (status = 204, description = "Avatar removed"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[delete("v1/me/avatar")]
+pub async fn delete_avatar(
+    data: web::Data<AppData>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    developers::update_avatar(dev.id, None, &mut pool).await?;
+
+    Ok(HttpResponse::NoContent())
+}
+
 #[derive(Deserialize, IntoParams)]
 struct GetOwnModsQuery {
     #[serde(default = "default_own_mods_status")]
     status: ModVersionStatusEnum,
     #[serde(default)]
     only_owner: bool,
+    /// Only include mods where the developer has at least this role
+    role: Option<ModDeveloperRole>,
 }
 
 pub fn default_own_mods_status() -> ModVersionStatusEnum {
@@ -344,7 +594,8 @@ pub async fn get_own_mods(
     let dev = auth.developer()?;
     let mut pool = data.db().acquire().await?;
     let mods: Vec<SimpleDevMod> =
-        Mod::get_all_for_dev(dev.id, query.status, query.only_owner, &mut pool).await?;
+        Mod::get_all_for_dev(dev.id, query.status, query.only_owner, query.role, &mut pool)
+            .await?;
     Ok(HttpResponse::Ok().json(ApiResponse {
         error: "".to_string(),
         payload: mods,
@@ -385,7 +636,7 @@ struct GetDeveloperPath {
     tag = "developers",
     params(GetDeveloperPath),
     responses(
-        (status = 200, description = "Developer details", body = inline(ApiResponse<Developer>)),
+        (status = 200, description = "Developer details", body = inline(ApiResponse<DeveloperProfile>)),
         (status = 404, description = "Developer not found")
     )
 )]
@@ -395,13 +646,27 @@ pub async fn get_developer(
     path: web::Path<GetDeveloperPath>,
 ) -> Result<impl Responder, ApiError> {
     let mut pool = data.db().acquire().await?;
-    let result = developers::get_one(path.id, &mut pool)
+    let developer = developers::get_one(path.id, &mut pool)
         .await?
         .ok_or(ApiError::NotFound("Developer not found".into()))?;
+    let details = developers::get_profile_details(developer.id, &mut pool)
+        .await?
+        .unwrap_or_default();
+    let stats = developers::get_stats(developer.id, &mut pool).await?;
+    let organizations = organizations::get_for_developer(developer.id, &mut pool).await?;
 
     Ok(web::Json(ApiResponse {
         error: "".to_string(),
-        payload: result,
+        payload: DeveloperProfile {
+            developer,
+            bio: details.bio,
+            avatar_url: details
+                .avatar
+                .map(|path| data.public_storage().asset_url(&path)),
+            links: details.links,
+            stats,
+            organizations,
+        },
     }))
 }
 
@@ -0,0 +1,103 @@
+use actix_web::{HttpResponse, Responder, get, post, web};
+use serde::Deserialize;
+use sqlx::Acquire;
+use utoipa::IntoParams;
+
+use crate::{
+    config::AppData,
+    database::repository::{download_flags, mods},
+    endpoints::ApiError,
+    extractors::auth::Auth,
+    types::{
+        api::ApiResponse,
+        models::download_flags::{DiscountedDownloads, FlaggedDownloads},
+    },
+};
+
+#[derive(Deserialize, IntoParams)]
+struct FlaggedQuery {
+    /// Only show versions of this mod
+    mod_id: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct DiscountPath {
+    id: String,
+}
+
+/// List mod versions with downloads flagged by the download analyzer (admin only)
+#[utoipa::path(
+    get,
+    path = "/v1/downloads/flagged",
+    tag = "downloads",
+    params(FlaggedQuery),
+    responses(
+        (status = 200, description = "Versions with flagged downloads", body = inline(ApiResponse<Vec<FlaggedDownloads>>)),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[get("v1/downloads/flagged")]
+pub async fn flagged(
+    data: web::Data<AppData>,
+    query: web::Query<FlaggedQuery>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    let mut pool = data.db().acquire().await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: download_flags::get_report(query.mod_id.as_deref(), &mut pool).await?,
+    }))
+}
+
+/// Remove a mod's flagged downloads from its download counts (admin only)
+#[utoipa::path(
+    post,
+    path = "/v1/mods/{id}/downloads/discount",
+    tag = "downloads",
+    params(DiscountPath),
+    responses(
+        (status = 200, description = "Downloads taken out of the counts", body = inline(ApiResponse<DiscountedDownloads>)),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/mods/{id}/downloads/discount")]
+pub async fn discount(
+    data: web::Data<AppData>,
+    path: web::Path<DiscountPath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    let mut pool = data.db().acquire().await?;
+
+    if !mods::exists(&path.id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(path.id.clone()));
+    }
+
+    let mut tx = pool.begin().await?;
+    let result = download_flags::discount(&path.id, &mut tx).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        error: "".into(),
+        payload: result,
+    }))
+}
@@ -0,0 +1,186 @@
+use actix_web::http::header;
+use actix_web::{HttpResponse, Responder, get, web};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    config::AppData,
+    database::repository::{developers, mod_versions, mods},
+    endpoints::ApiError,
+    feeds::{AtomAuthor, AtomEntry, AtomFeed, changelog_excerpt},
+};
+
+/// Number of entries in every feed
+const FEED_LENGTH: i64 = 50;
+const FEED_MAX_AGE: u32 = 5 * 60;
+
+#[derive(Deserialize, IntoParams)]
+struct ModFeedPath {
+    id: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct DeveloperFeedPath {
+    id: i32,
+}
+
+/// Atom feed of newly accepted mods
+#[utoipa::path(
+    get,
+    path = "/v1/feeds/mods.atom",
+    tag = "feeds",
+    responses(
+        (status = 200, description = "Atom feed", content_type = "application/atom+xml")
+    )
+)]
+#[get("v1/feeds/mods.atom")]
+pub async fn new_mods(data: web::Data<AppData>) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+    let entries = mod_versions::get_feed(None, None, true, FEED_LENGTH, &mut pool).await?;
+
+    Ok(feed_response(AtomFeed {
+        id: format!("{}/v1/feeds/mods.atom", data.app_url()),
+        title: "Geode Index: new mods".into(),
+        self_url: format!("{}/v1/feeds/mods.atom", data.app_url()),
+        alternate_url: format!("{}/mods", data.front_url()),
+        logo: None,
+        entries: to_atom_entries(&data, entries),
+    }))
+}
+
+/// Atom feed of all accepted mod versions
+#[utoipa::path(
+    get,
+    path = "/v1/feeds/updates.atom",
+    tag = "feeds",
+    responses(
+        (status = 200, description = "Atom feed", content_type = "application/atom+xml")
+    )
+)]
+#[get("v1/feeds/updates.atom")]
+pub async fn updates(data: web::Data<AppData>) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+    let entries = mod_versions::get_feed(None, None, false, FEED_LENGTH, &mut pool).await?;
+
+    Ok(feed_response(AtomFeed {
+        id: format!("{}/v1/feeds/updates.atom", data.app_url()),
+        title: "Geode Index: updates".into(),
+        self_url: format!("{}/v1/feeds/updates.atom", data.app_url()),
+        alternate_url: format!("{}/mods", data.front_url()),
+        logo: None,
+        entries: to_atom_entries(&data, entries),
+    }))
+}
+
+/// Atom feed of a mod's accepted versions
+#[utoipa::path(
+    get,
+    path = "/v1/mods/{id}/feed.atom",
+    tag = "feeds",
+    params(ModFeedPath),
+    responses(
+        (status = 200, description = "Atom feed", content_type = "application/atom+xml"),
+        (status = 404, description = "Mod not found")
+    )
+)]
+#[get("v1/mods/{id}/feed.atom")]
+pub async fn mod_feed(
+    data: web::Data<AppData>,
+    path: web::Path<ModFeedPath>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+    if !mods::exists(&path.id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(path.id.clone()));
+    }
+
+    let entries =
+        mod_versions::get_feed(Some(&path.id), None, false, FEED_LENGTH, &mut pool).await?;
+    let title = match entries.first() {
+        Some(e) => format!("{} releases", e.name),
+        None => format!("{} releases", path.id),
+    };
+
+    Ok(feed_response(AtomFeed {
+        id: format!("{}/v1/mods/{}/feed.atom", data.app_url(), path.id),
+        title,
+        self_url: format!("{}/v1/mods/{}/feed.atom", data.app_url(), path.id),
+        alternate_url: format!("{}/mods/{}", data.front_url(), path.id),
+        logo: Some(logo_url(&data, &path.id)),
+        entries: to_atom_entries(&data, entries),
+    }))
+}
+
+/// Atom feed of accepted versions of every mod a developer works on
+#[utoipa::path(
+    get,
+    path = "/v1/developers/{id}/feed.atom",
+    tag = "feeds",
+    params(DeveloperFeedPath),
+    responses(
+        (status = 200, description = "Atom feed", content_type = "application/atom+xml"),
+        (status = 404, description = "Developer not found")
+    )
+)]
+#[get("v1/developers/{id}/feed.atom")]
+pub async fn developer_feed(
+    data: web::Data<AppData>,
+    path: web::Path<DeveloperFeedPath>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+    let developer = developers::get_one(path.id, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound("Developer not found".into()))?;
+
+    let entries =
+        mod_versions::get_feed(None, Some(developer.id), false, FEED_LENGTH, &mut pool).await?;
+
+    Ok(feed_response(AtomFeed {
+        id: format!("{}/v1/developers/{}/feed.atom", data.app_url(), developer.id),
+        title: format!("Mods by {}", developer.display_name),
+        self_url: format!("{}/v1/developers/{}/feed.atom", data.app_url(), developer.id),
+        alternate_url: format!("{}/developers/{}", data.front_url(), developer.id),
+        logo: None,
+        entries: to_atom_entries(&data, entries),
+    }))
+}
+
+/// Titles mirror the Discord messages sent for `NewModAcceptedEvent` and `NewModVersionAcceptedEvent`
+fn to_atom_entries(data: &AppData, entries: Vec<mod_versions::FeedEntry>) -> Vec<AtomEntry> {
+    entries
+        .into_iter()
+        .map(|e| AtomEntry {
+            id: format!("{}/v1/mods/{}/versions/{}", data.app_url(), e.mod_id, e.version),
+            title: if e.is_new_mod {
+                format!("New mod: {} {}", e.name, e.version)
+            } else {
+                format!("Updated {} to {}", e.name, e.version)
+            },
+            link: format!("{}/mods/{}", data.front_url(), e.mod_id),
+            updated: e.accepted_at,
+            author: e
+                .owner_username
+                .zip(e.owner_display_name)
+                .map(|(username, name)| AtomAuthor {
+                    name,
+                    uri: format!("https://github.com/{username}"),
+                }),
+            summary: e.description,
+            content: e
+                .changelog
+                .as_deref()
+                .and_then(|c| changelog_excerpt(c, &e.version)),
+            thumbnail: Some(logo_url(data, &e.mod_id)),
+        })
+        .collect()
+}
+
+fn logo_url(data: &AppData, mod_id: &str) -> String {
+    format!("{}/v1/mods/{}/logo?size=128", data.app_url(), mod_id)
+}
+
+fn feed_response(feed: AtomFeed) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/atom+xml; charset=utf-8")
+        .append_header((header::CACHE_CONTROL, format!("public, max-age={FEED_MAX_AGE}")))
+        .body(feed.render())
+}
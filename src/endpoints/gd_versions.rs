@@ -0,0 +1,511 @@
+use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::Acquire;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    config::AppData,
+    database::repository::{gd_version_aliases, gd_versions},
+    endpoints::ApiError,
+    extractors::auth::Auth,
+    types::{
+        api::ApiResponse,
+        models::{
+            gd_version_alias::{self, GDVersionAlias, GDVersionAliasCreate},
+            mod_gd_version::{GDRelease, GDVersion, VerPlatform},
+        },
+    },
+};
+
+#[derive(Deserialize, IntoParams)]
+struct IndexQuery {
+    /// Only list releases available on this platform
+    platform: Option<VerPlatform>,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct GDVersionPath {
+    name: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct DetectQuery {
+    platform: VerPlatform,
+    /// Windows executable timestamp, Android manifest id, Mac binary UUID or iOS bundle version
+    identifier: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct AliasPath {
+    version: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct AliasIdentifiers {
+    mac_arm_uuid: Option<String>,
+    mac_intel_uuid: Option<String>,
+    android_manifest_id: Option<i32>,
+    windows_timestamp: Option<i32>,
+    ios_bundle_version: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct CreateAliasBody {
+    #[schema(value_type = String, example = "2.2074")]
+    version: GDVersion,
+    #[serde(flatten)]
+    identifiers: AliasIdentifiers,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct CreateGDVersionBody {
+    #[schema(value_type = String, example = "2.2083")]
+    name: GDVersion,
+    /// Position to insert the release at. Defaults to after the newest release.
+    sort_order: Option<i32>,
+    released_at: Option<NaiveDate>,
+    #[serde(default)]
+    platforms: Vec<VerPlatform>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct UpdateGDVersionBody {
+    /// Moves the release to this position
+    sort_order: Option<i32>,
+    /// Left unchanged when omitted
+    released_at: Option<NaiveDate>,
+    /// Left unchanged when omitted
+    platforms: Option<Vec<VerPlatform>>,
+}
+
+/// List known Geometry Dash releases, newest first
+#[utoipa::path(
+    get,
+    path = "/v1/gd/versions",
+    tag = "gd",
+    params(IndexQuery),
+    responses(
+        (status = 200, description = "List of GD releases", body = inline(ApiResponse<Vec<GDRelease>>)),
+        (status = 400, description = "Invalid platform")
+    )
+)]
+#[get("v1/gd/versions")]
+pub async fn index(
+    data: web::Data<AppData>,
+    query: web::Query<IndexQuery>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+    let platform = query.platform.map(normalize_platform);
+
+    let versions = gd_versions::get_all(platform, &mut pool).await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: versions,
+    }))
+}
+
+/// Add a Geometry Dash release (admin only)
+#[utoipa::path(
+    post,
+    path = "/v1/gd/versions",
+    tag = "gd",
+    request_body = CreateGDVersionBody,
+    responses(
+        (status = 201, description = "GD release added", body = inline(ApiResponse<GDRelease>)),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 409, description = "Release already exists")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/gd/versions")]
+pub async fn create(
+    data: web::Data<AppData>,
+    json: web::Json<CreateGDVersionBody>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    if json.name.is_all() {
+        return Err(ApiError::BadRequest("* is not a GD release".into()));
+    }
+    check_sort_order(json.sort_order)?;
+
+    let mut pool = data.db().acquire().await?;
+    let mut tx = pool.begin().await?;
+
+    if gd_versions::get_one(json.name.as_str(), &mut tx)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(format!(
+            "GD version {} already exists",
+            json.name
+        )));
+    }
+
+    let platforms = normalize_platforms(&json.platforms);
+    let release = gd_versions::create(
+        &json.name,
+        json.sort_order,
+        json.released_at,
+        &platforms,
+        &mut tx,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        error: "".into(),
+        payload: release,
+    }))
+}
+
+/// Update a Geometry Dash release (admin only)
+#[utoipa::path(
+    put,
+    path = "/v1/gd/versions/{name}",
+    tag = "gd",
+    params(GDVersionPath),
+    request_body = UpdateGDVersionBody,
+    responses(
+        (status = 200, description = "GD release updated", body = inline(ApiResponse<GDRelease>)),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Release not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[put("v1/gd/versions/{name}")]
+pub async fn update(
+    data: web::Data<AppData>,
+    path: web::Path<GDVersionPath>,
+    json: web::Json<UpdateGDVersionBody>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    check_sort_order(json.sort_order)?;
+
+    let mut pool = data.db().acquire().await?;
+    let mut tx = pool.begin().await?;
+
+    if path.name == "*" || gd_versions::get_one(&path.name, &mut tx).await?.is_none() {
+        return Err(ApiError::NotFound(format!(
+            "GD version {} not found",
+            path.name
+        )));
+    }
+
+    let platforms = json.platforms.as_deref().map(normalize_platforms);
+    let release = gd_versions::update(
+        &path.name,
+        json.sort_order,
+        json.released_at,
+        platforms.as_deref(),
+        &mut tx,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: release,
+    }))
+}
+
+/// Detect the GD version of a game binary from a platform identifier
+#[utoipa::path(
+    get,
+    path = "/v1/gd/detect",
+    tag = "gd",
+    params(DetectQuery),
+    responses(
+        (status = 200, description = "Detected GD release", body = inline(ApiResponse<GDRelease>)),
+        (status = 400, description = "Invalid platform or identifier"),
+        (status = 404, description = "Unknown identifier")
+    )
+)]
+#[get("v1/gd/detect")]
+pub async fn detect(
+    data: web::Data<AppData>,
+    query: web::Query<DetectQuery>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+
+    let release = match GDVersionAlias::find(query.platform, &query.identifier, &mut pool).await? {
+        Some(version) => gd_versions::get_one(version.as_str(), &mut pool).await?,
+        None => None,
+    }
+    .ok_or_else(|| ApiError::NotFound("No GD version matches this identifier".into()))?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: release,
+    }))
+}
+
+/// List the platform identifiers of GD releases
+#[utoipa::path(
+    get,
+    path = "/v1/gd/aliases",
+    tag = "gd",
+    responses(
+        (status = 200, description = "List of aliases", body = inline(ApiResponse<Vec<GDVersionAlias>>))
+    )
+)]
+#[get("v1/gd/aliases")]
+pub async fn alias_index(data: web::Data<AppData>) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+    let aliases = gd_version_aliases::get_all(&mut pool).await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: aliases,
+    }))
+}
+
+/// Add the platform identifiers of a GD release (admin only)
+#[utoipa::path(
+    post,
+    path = "/v1/gd/aliases",
+    tag = "gd",
+    request_body = CreateAliasBody,
+    responses(
+        (status = 201, description = "Alias created", body = inline(ApiResponse<GDVersionAlias>)),
+        (status = 400, description = "Invalid identifier or unknown GD version"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 409, description = "Alias or identifier already exists")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/gd/aliases")]
+pub async fn create_alias(
+    data: web::Data<AppData>,
+    json: web::Json<CreateAliasBody>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    let alias = parse_identifiers(&json.identifiers)?;
+
+    let mut pool = data.db().acquire().await?;
+    let mut tx = pool.begin().await?;
+
+    gd_versions::check_known(std::slice::from_ref(&json.version), &mut tx).await?;
+    if gd_version_aliases::get_one(json.version.as_str(), &mut tx)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(format!(
+            "GD version {} already has an alias",
+            json.version
+        )));
+    }
+    check_alias_conflict(json.version.as_str(), &alias, &mut tx).await?;
+
+    let created = gd_version_aliases::create(&json.version, &alias, &mut tx).await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        error: "".into(),
+        payload: created,
+    }))
+}
+
+/// Replace the platform identifiers of a GD release (admin only)
+#[utoipa::path(
+    put,
+    path = "/v1/gd/aliases/{version}",
+    tag = "gd",
+    params(AliasPath),
+    request_body = AliasIdentifiers,
+    responses(
+        (status = 200, description = "Alias updated", body = inline(ApiResponse<GDVersionAlias>)),
+        (status = 400, description = "Invalid identifier"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Alias not found"),
+        (status = 409, description = "Identifier already used by another version")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[put("v1/gd/aliases/{version}")]
+pub async fn update_alias(
+    data: web::Data<AppData>,
+    path: web::Path<AliasPath>,
+    json: web::Json<AliasIdentifiers>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    let alias = parse_identifiers(&json)?;
+
+    let mut pool = data.db().acquire().await?;
+    let mut tx = pool.begin().await?;
+
+    if gd_version_aliases::get_one(&path.version, &mut tx)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::NotFound(format!(
+            "No alias for GD version {}",
+            path.version
+        )));
+    }
+    check_alias_conflict(&path.version, &alias, &mut tx).await?;
+
+    let updated = gd_version_aliases::update(&path.version, &alias, &mut tx).await?;
+
+    tx.commit().await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: updated,
+    }))
+}
+
+/// Delete the platform identifiers of a GD release (admin only)
+#[utoipa::path(
+    delete,
+    path = "/v1/gd/aliases/{version}",
+    tag = "gd",
+    params(AliasPath),
+    responses(
+        (status = 204, description = "Alias deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Alias not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[delete("v1/gd/aliases/{version}")]
+pub async fn delete_alias(
+    data: web::Data<AppData>,
+    path: web::Path<AliasPath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    let mut pool = data.db().acquire().await?;
+
+    if gd_version_aliases::get_one(&path.version, &mut pool)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::NotFound(format!(
+            "No alias for GD version {}",
+            path.version
+        )));
+    }
+
+    gd_version_aliases::delete(&path.version, &mut pool).await?;
+
+    Ok(HttpResponse::NoContent())
+}
+
+/// Validates identifiers the same way lookups through [`GDVersionAlias::find`] do
+fn parse_identifiers(identifiers: &AliasIdentifiers) -> Result<GDVersionAliasCreate, ApiError> {
+    Ok(GDVersionAliasCreate {
+        mac_arm_uuid: identifiers
+            .mac_arm_uuid
+            .as_deref()
+            .map(gd_version_alias::parse_uuid)
+            .transpose()?,
+        mac_intel_uuid: identifiers
+            .mac_intel_uuid
+            .as_deref()
+            .map(gd_version_alias::parse_uuid)
+            .transpose()?,
+        android_manifest_id: identifiers
+            .android_manifest_id
+            .map(|x| gd_version_alias::parse_manifest_id(&x.to_string()))
+            .transpose()?,
+        windows_timestamp: identifiers
+            .windows_timestamp
+            .map(|x| gd_version_alias::parse_timestamp(&x.to_string()))
+            .transpose()?,
+        ios_bundle_version: identifiers
+            .ios_bundle_version
+            .as_deref()
+            .map(gd_version_alias::parse_bundle_version)
+            .transpose()?,
+    })
+}
+
+async fn check_alias_conflict(
+    version: &str,
+    alias: &GDVersionAliasCreate,
+    conn: &mut sqlx::PgConnection,
+) -> Result<(), ApiError> {
+    match gd_version_aliases::find_conflict(version, alias, conn).await? {
+        Some(other) => Err(ApiError::Conflict(format!(
+            "An identifier is already used by GD version {other}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn check_sort_order(sort_order: Option<i32>) -> Result<(), ApiError> {
+    match sort_order {
+        // 0 is taken by *
+        Some(s) if s < 1 => Err(ApiError::BadRequest("sort_order must be at least 1".into())),
+        _ => Ok(()),
+    }
+}
+
+/// `android` and `mac` aren't stored, only their architectures are
+fn normalize_platform(platform: VerPlatform) -> VerPlatform {
+    match platform {
+        VerPlatform::Android => VerPlatform::Android64,
+        VerPlatform::Mac => VerPlatform::MacArm,
+        p => p,
+    }
+}
+
+fn normalize_platforms(platforms: &[VerPlatform]) -> Vec<VerPlatform> {
+    let mut ret = vec![];
+    for p in platforms {
+        match p {
+            VerPlatform::Android => ret.extend([VerPlatform::Android32, VerPlatform::Android64]),
+            VerPlatform::Mac => ret.extend([VerPlatform::MacArm, VerPlatform::MacIntel]),
+            p => ret.push(*p),
+        }
+    }
+    ret.sort_by_key(|p| *p as i32);
+    ret.dedup();
+    ret
+}
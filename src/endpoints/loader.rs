@@ -1,4 +1,4 @@
-use actix_web::{get, post, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
 use serde::Deserialize;
 use std::str::FromStr;
 use utoipa::{ToSchema, IntoParams};
@@ -8,13 +8,17 @@ use sqlx::Acquire;
 use crate::endpoints::ApiError;
 use crate::{
     config::AppData,
+    database::repository::gd_versions,
     extractors::auth::Auth,
     types::{
         api::{ApiResponse, PaginatedData},
         models::{
             gd_version_alias::GDVersionAlias,
-            loader_version::{GetVersionsQuery, LoaderVersion, LoaderVersionCreate},
-            mod_gd_version::{DetailedGDVersion, GDVersionEnum, VerPlatform},
+            loader_version::{
+                GetVersionsQuery, LoaderAsset, LoaderChannel, LoaderVersion, LoaderVersionCreate,
+                LoaderVersionQueries,
+            },
+            mod_gd_version::{DetailedGDVersion, GDVersion, VerPlatform},
         },
     },
 };
@@ -23,8 +27,11 @@ use crate::{
 struct GetOneQuery {
     platform: Option<VerPlatform>,
     gd: Option<String>,
+    /// Same as `channel=beta`
     #[serde(default)]
     prerelease: bool,
+    /// Latest channel to accept, defaults to stable
+    channel: Option<LoaderChannel>,
 }
 
 #[derive(Deserialize, IntoParams)]
@@ -53,7 +60,7 @@ pub async fn get_one(
 
     let version = if path.version == "latest" {
         let gd = if let Some(i) = &query.gd {
-            if let Ok(g) = GDVersionEnum::from_str(i) {
+            if let Ok(g) = GDVersion::from_str(i) {
                 Some(g)
             } else {
                 let platform = query.platform.ok_or_else(|| {
@@ -67,7 +74,8 @@ pub async fn get_one(
             None
         };
 
-        LoaderVersion::get_latest(gd, query.platform, query.prerelease, &mut pool)
+        let channel = effective_channel(query.channel, query.prerelease);
+        LoaderVersion::get_latest(gd, query.platform, channel, &mut pool)
             .await?
             .ok_or(ApiError::NotFound("Latest version not found".into()))?
     } else {
@@ -85,10 +93,20 @@ pub async fn get_one(
 #[derive(Deserialize, ToSchema)]
 struct CreateVersionBody {
     pub tag: String,
+    /// Same as `channel: "beta"`
     #[serde(default)]
     pub prerelease: bool,
+    pub channel: Option<LoaderChannel>,
     pub commit_hash: String,
     pub gd: DetailedGDVersion,
+    pub changelog: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<LoaderAsset>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct YankBody {
+    reason: Option<String>,
 }
 
 /// Create a new loader version (admin only)
@@ -120,16 +138,29 @@ pub async fn create_version(
         return Err(ApiError::Authorization);
     }
 
+    let assets = normalize_assets(&payload.assets)?;
+
     let mut tx = pool.begin().await?;
+
+    let gd: Vec<GDVersion> = [&payload.gd.win, &payload.gd.mac, &payload.gd.android, &payload.gd.ios]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    gd_versions::check_known(&gd, &mut tx).await?;
+
     LoaderVersion::create_version(
         LoaderVersionCreate {
             tag: payload.tag.trim_start_matches('v').to_string(),
-            prerelease: payload.prerelease,
+            channel: effective_channel(payload.channel, payload.prerelease),
             commit_hash: payload.commit_hash.clone(),
-            win: payload.gd.win,
-            mac: payload.gd.mac,
-            android: payload.gd.android,
-            ios: payload.gd.ios,
+            changelog: payload.changelog.clone(),
+            win: payload.gd.win.clone(),
+            mac: payload.gd.mac.clone(),
+            android: payload.gd.android.clone(),
+            ios: payload.gd.ios.clone(),
+            created_at: None,
+            assets,
         },
         &mut tx,
     )
@@ -142,11 +173,15 @@ pub async fn create_version(
 
 #[derive(Deserialize, IntoParams)]
 struct GetManyQuery {
-    pub gd: Option<GDVersionEnum>,
+    pub gd: Option<GDVersion>,
     pub platform: Option<VerPlatform>,
     pub per_page: Option<i64>,
     pub page: Option<i64>,
-    pub prerelease: Option<bool>,
+    /// Same as `channel=beta`
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Latest channel to include, defaults to stable
+    pub channel: Option<LoaderChannel>,
 }
 
 /// Get all loader versions with optional filtering
@@ -168,9 +203,9 @@ pub async fn get_many(
 
     let versions = LoaderVersion::get_many(
         GetVersionsQuery {
-            gd: query.gd,
+            gd: query.gd.clone(),
             platform: query.platform,
-            prerelease: query.prerelease.unwrap_or_default(),
+            channel: effective_channel(query.channel, query.prerelease),
         },
         query.per_page.unwrap_or(10),
         query.page.unwrap_or(1),
@@ -183,3 +218,135 @@ pub async fn get_many(
         payload: versions,
     }))
 }
+
+/// Yank a loader version so it's no longer served as the latest version (admin only)
+#[utoipa::path(
+    post,
+    path = "/v1/loader/versions/{version}/yank",
+    tag = "loader",
+    params(GetOnePath),
+    request_body = YankBody,
+    responses(
+        (status = 204, description = "Loader version yanked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Version not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/loader/versions/{version}/yank")]
+pub async fn yank(
+    data: web::Data<AppData>,
+    path: web::Path<GetOnePath>,
+    payload: web::Json<YankBody>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    set_yanked(&data, &path.version, true, payload.reason.as_deref(), auth).await
+}
+
+/// Restore a yanked loader version (admin only)
+#[utoipa::path(
+    delete,
+    path = "/v1/loader/versions/{version}/yank",
+    tag = "loader",
+    params(GetOnePath),
+    responses(
+        (status = 204, description = "Loader version restored"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Version not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[delete("v1/loader/versions/{version}/yank")]
+pub async fn unyank(
+    data: web::Data<AppData>,
+    path: web::Path<GetOnePath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    set_yanked(&data, &path.version, false, None, auth).await
+}
+
+async fn set_yanked(
+    data: &AppData,
+    version: &str,
+    yanked: bool,
+    reason: Option<&str>,
+    auth: Auth,
+) -> Result<HttpResponse, ApiError> {
+    let dev = auth.developer()?;
+    if !dev.admin {
+        return Err(ApiError::Authorization);
+    }
+
+    let tag = version.trim_start_matches('v');
+    let mut pool = data.db().acquire().await?;
+
+    if LoaderVersion::get_one(tag, &mut pool).await?.is_none() {
+        return Err(ApiError::NotFound("Not found".into()));
+    }
+
+    LoaderVersion::set_yanked(tag, yanked, reason, &mut pool).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Legacy `prerelease` means beta everywhere, since existing prereleases were migrated to beta
+fn effective_channel(channel: Option<LoaderChannel>, prerelease: bool) -> LoaderChannel {
+    channel.unwrap_or(if prerelease {
+        LoaderChannel::Beta
+    } else {
+        LoaderChannel::Stable
+    })
+}
+
+/// Validates assets and splits `android` and `mac` into their architectures
+fn normalize_assets(assets: &[LoaderAsset]) -> Result<Vec<LoaderAsset>, ApiError> {
+    let mut ret: Vec<LoaderAsset> = vec![];
+
+    for asset in assets {
+        if !asset.url.starts_with("https://") && !asset.url.starts_with("http://") {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid asset URL {}",
+                asset.url
+            )));
+        }
+        if asset.size <= 0 {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid size for asset {}",
+                asset.url
+            )));
+        }
+        if asset.sha256.len() != 64 || !asset.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid SHA-256 for asset {}",
+                asset.url
+            )));
+        }
+
+        let platforms = match asset.platform {
+            VerPlatform::Android => vec![VerPlatform::Android32, VerPlatform::Android64],
+            VerPlatform::Mac => vec![VerPlatform::MacArm, VerPlatform::MacIntel],
+            p => vec![p],
+        };
+        for platform in platforms {
+            if ret.iter().any(|a| a.platform == platform) {
+                return Err(ApiError::BadRequest(format!(
+                    "Asset {} is for a platform that already has one",
+                    asset.url
+                )));
+            }
+            ret.push(LoaderAsset {
+                platform,
+                sha256: asset.sha256.to_lowercase(),
+                ..asset.clone()
+            });
+        }
+    }
+
+    Ok(ret)
+}
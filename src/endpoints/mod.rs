@@ -1,16 +1,28 @@
 use crate::{
+    auth::AuthenticationError,
+    database::DatabaseError,
     mod_zip::ModZipError,
-    types::{api::ApiResponse, models::mod_gd_version::PlatformParseError},
+    types::{
+        api::{ApiErrorResponse, ErrorCode},
+        mod_json::{extract_field_errors, extract_validation_error},
+        models::mod_gd_version::PlatformParseError,
+    },
 };
 use actix_web::{http::StatusCode, HttpResponse};
 
 pub mod auth;
 pub mod developers;
+pub mod downloads;
+pub mod feeds;
+pub mod gd_versions;
 pub mod health;
 pub mod loader;
+pub mod mod_media;
 pub mod mod_versions;
 pub mod mod_status_badge;
+pub mod mod_transfers;
 pub mod mods;
+pub mod organizations;
 pub mod stats;
 pub mod tags;
 pub mod deprecations;
@@ -31,12 +43,26 @@ pub enum ApiError {
     Json(#[from] serde_json::Error),
     #[error("{0}")]
     BadRequest(String),
+    #[error("Invalid query parameters: {0}")]
+    InvalidQuery(String),
+    #[error("Validation failed: {}", extract_validation_error(.0))]
+    Validation(#[from] validator::ValidationErrors),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    ModExists(String),
+    #[error("{0}")]
+    VersionExists(String),
     #[error("{0}")]
     TooManyRequests(String),
     #[error("Internal error: {0}")]
     InternalError(String),
     #[error("{0}")]
     NotFound(String),
+    #[error("Mod {0} not found")]
+    ModNotFound(String),
+    #[error("{0}")]
+    VersionNotFound(String),
     #[error("Error: {0}")]
     PlatformParseError(#[from] PlatformParseError),
     #[error("Unable to unzip archive")]
@@ -46,9 +72,64 @@ pub enum ApiError {
 }
 
 impl ApiError {
-    pub fn as_response(&self) -> ApiResponse<String> {
-        ApiResponse {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::Authentication(e) => match e {
+                AuthenticationError::NoToken => ErrorCode::Unauthenticated,
+                AuthenticationError::InvalidToken => ErrorCode::InvalidToken,
+                AuthenticationError::UserAuthPending => ErrorCode::AuthPending,
+                AuthenticationError::RequestError(_) => ErrorCode::ExternalServiceError,
+                _ => ErrorCode::InternalError,
+            },
+            ApiError::Authorization => ErrorCode::Forbidden,
+            ApiError::Database(DatabaseError::InvalidInput(_)) => ErrorCode::BadRequest,
+            ApiError::Database(_) | ApiError::SqlxError(_) | ApiError::InternalError(_) => {
+                ErrorCode::InternalError
+            }
+            ApiError::ModZip(e) => match e {
+                ModZipError::IoError(_) => ErrorCode::InternalError,
+                ModZipError::ImageError(_) | ModZipError::InvalidLogo(_) => ErrorCode::LogoInvalid,
+                ModZipError::InvalidScreenshot(_) => ErrorCode::ScreenshotInvalid,
+                ModZipError::ZipError(_) => ErrorCode::InvalidModFile,
+                ModZipError::SerdeJsonError(_)
+                | ModZipError::InvalidModJson(_)
+                | ModZipError::ModJsonValidation(_) => ErrorCode::InvalidModJson,
+                ModZipError::ModFileHashMismatch(..) => ErrorCode::HashMismatch,
+                ModZipError::ModFileFetchError(_) => ErrorCode::ModFileFetchFailed,
+                ModZipError::ModFileTooLarge(..) | ModZipError::ModFileTooLargeUncompressed(..) => {
+                    ErrorCode::ModFileTooLarge
+                }
+                ModZipError::InvalidBinaries(_) => ErrorCode::InvalidBinaries,
+            },
+            ApiError::Json(_) => ErrorCode::InvalidJson,
+            ApiError::BadRequest(_) => ErrorCode::BadRequest,
+            ApiError::InvalidQuery(_) => ErrorCode::InvalidQuery,
+            ApiError::Validation(_) => ErrorCode::ValidationFailed,
+            ApiError::Conflict(_) => ErrorCode::Conflict,
+            ApiError::ModExists(_) => ErrorCode::ModExists,
+            ApiError::VersionExists(_) => ErrorCode::VersionExists,
+            ApiError::TooManyRequests(_) => ErrorCode::TooManyRequests,
+            ApiError::NotFound(_) => ErrorCode::NotFound,
+            ApiError::ModNotFound(_) => ErrorCode::ModNotFound,
+            ApiError::VersionNotFound(_) => ErrorCode::VersionNotFound,
+            ApiError::PlatformParseError(_) => ErrorCode::InvalidPlatform,
+            ApiError::Zip(_) => ErrorCode::InvalidModFile,
+            ApiError::Reqwest(_) => ErrorCode::ExternalServiceError,
+        }
+    }
+
+    pub fn as_response(&self) -> ApiErrorResponse {
+        let details = match self {
+            ApiError::Validation(e) | ApiError::ModZip(ModZipError::ModJsonValidation(e)) => {
+                extract_field_errors(e)
+            }
+            _ => vec![],
+        };
+
+        ApiErrorResponse {
             error: self.to_string(),
+            code: self.code(),
+            details,
             payload: "".into(),
         }
     }
@@ -56,13 +137,34 @@ impl ApiError {
 
 impl actix_web::ResponseError for ApiError {
     fn status_code(&self) -> StatusCode {
-        match self {
-            ApiError::Authentication(..) => StatusCode::UNAUTHORIZED,
-            ApiError::Authorization => StatusCode::FORBIDDEN,
-            ApiError::Json(..) => StatusCode::BAD_REQUEST,
-            ApiError::TooManyRequests(..) => StatusCode::TOO_MANY_REQUESTS,
-            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
-            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        match self.code() {
+            ErrorCode::BadRequest
+            | ErrorCode::InvalidQuery
+            | ErrorCode::InvalidJson
+            | ErrorCode::ValidationFailed
+            | ErrorCode::InvalidModJson
+            | ErrorCode::InvalidModFile
+            | ErrorCode::InvalidBinaries
+            | ErrorCode::LogoInvalid
+            | ErrorCode::ScreenshotInvalid
+            | ErrorCode::HashMismatch
+            | ErrorCode::ModFileFetchFailed
+            | ErrorCode::InvalidPlatform => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthenticated | ErrorCode::InvalidToken | ErrorCode::AuthPending => {
+                StatusCode::UNAUTHORIZED
+            }
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound | ErrorCode::ModNotFound | ErrorCode::VersionNotFound => {
+                StatusCode::NOT_FOUND
+            }
+            ErrorCode::Conflict | ErrorCode::ModExists | ErrorCode::VersionExists => {
+                StatusCode::CONFLICT
+            }
+            ErrorCode::ModFileTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::ExternalServiceError => StatusCode::BAD_GATEWAY,
+            // the server never sends `Unknown`, it's for clients decoding newer codes
+            ErrorCode::InternalError | ErrorCode::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
@@ -0,0 +1,459 @@
+use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
+use futures::StreamExt;
+use reqwest::Url;
+use serde::Deserialize;
+use sqlx::{Acquire, PgConnection};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    config::AppData,
+    database::repository::{developers, mod_media, mods},
+    endpoints::ApiError,
+    extractors::auth::Auth,
+    images,
+    storage::{PublicStorage, StorageDisk},
+    types::{
+        api::ApiResponse,
+        mod_json::MAX_MOD_MEDIA,
+        models::{
+            developer::ModDeveloperRole,
+            mod_media::{ModMedia, ModMediaKind, ModMediaSource},
+        },
+    },
+};
+
+#[derive(Deserialize, IntoParams)]
+struct ModPath {
+    id: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct MediaPath {
+    id: String,
+    media_id: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct ScreenshotQuery {
+    caption: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct CreateVideoPayload {
+    /// YouTube link
+    url: String,
+    caption: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct UpdateMediaPayload {
+    /// Empty string removes the caption
+    caption: Option<String>,
+    /// New index in the mod's media, everything after it moves back by one
+    position: Option<usize>,
+}
+
+const MAX_SCREENSHOT_BYTES: usize = 5 * 1024 * 1024;
+const MAX_CAPTION_LENGTH: usize = 200;
+const VIDEO_HOSTS: [&str; 4] = [
+    "youtube.com",
+    "www.youtube.com",
+    "m.youtube.com",
+    "youtu.be",
+];
+
+/// Get the screenshots and videos of a mod
+#[utoipa::path(
+    get,
+    path = "/v1/mods/{id}/media",
+    tag = "mods",
+    params(ModPath),
+    responses(
+        (status = 200, description = "Media in display order", body = inline(ApiResponse<Vec<ModMedia>>)),
+        (status = 404, description = "Mod not found")
+    )
+)]
+#[get("v1/mods/{id}/media")]
+pub async fn index(
+    data: web::Data<AppData>,
+    path: web::Path<ModPath>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+
+    if !mods::exists(&path.id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(path.id.clone()));
+    }
+
+    let media = mod_media::get_for_mod(&path.id, &mut pool).await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: with_urls(media, data.public_storage()),
+    }))
+}
+
+/// Upload a screenshot for a mod
+#[utoipa::path(
+    post,
+    path = "/v1/mods/{id}/media/screenshots",
+    tag = "mods",
+    params(ModPath, ScreenshotQuery),
+    request_body(content = Vec<u8>, description = "PNG image, scaled down to fit in 1920x1080", content_type = "image/png"),
+    responses(
+        (status = 201, description = "Screenshot added", body = inline(ApiResponse<ModMedia>)),
+        (status = 400, description = "Invalid image, caption or too many screenshots"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/mods/{id}/media/screenshots")]
+pub async fn upload_screenshot(
+    data: web::Data<AppData>,
+    path: web::Path<ModPath>,
+    query: web::Query<ScreenshotQuery>,
+    mut payload: web::Payload,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    check_access(&path.id, dev.id, dev.admin, &mut pool).await?;
+    let caption = normalize_caption(query.caption.as_deref())?;
+
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|_| ApiError::BadRequest("Failed to read screenshot".into()))?;
+        if body.len() + chunk.len() > MAX_SCREENSHOT_BYTES {
+            return Err(ApiError::BadRequest(format!(
+                "Screenshot is too large (max {} MB)",
+                MAX_SCREENSHOT_BYTES / 1024 / 1024
+            )));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let screenshot =
+        images::process_screenshot(&body).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let mut tx = pool.begin().await?;
+    check_room(&path.id, &mut tx).await?;
+    let location = store_screenshot(data.public_storage(), &path.id, &screenshot).await?;
+
+    let media = mod_media::create(
+        &path.id,
+        ModMediaKind::Screenshot,
+        ModMediaSource::Upload,
+        &location,
+        caption.as_deref(),
+        &mut tx,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        error: "".into(),
+        payload: with_url(media, data.public_storage()),
+    }))
+}
+
+/// Add a video link to a mod
+#[utoipa::path(
+    post,
+    path = "/v1/mods/{id}/media/videos",
+    tag = "mods",
+    params(ModPath),
+    request_body = CreateVideoPayload,
+    responses(
+        (status = 201, description = "Video added", body = inline(ApiResponse<ModMedia>)),
+        (status = 400, description = "Invalid link, caption or too many videos"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/mods/{id}/media/videos")]
+pub async fn add_video(
+    data: web::Data<AppData>,
+    path: web::Path<ModPath>,
+    json: web::Json<CreateVideoPayload>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    check_access(&path.id, dev.id, dev.admin, &mut pool).await?;
+    let caption = normalize_caption(json.caption.as_deref())?;
+    let url = parse_video_url(&json.url)?;
+
+    let mut tx = pool.begin().await?;
+    check_room(&path.id, &mut tx).await?;
+
+    let media = mod_media::create(
+        &path.id,
+        ModMediaKind::Video,
+        ModMediaSource::Upload,
+        &url,
+        caption.as_deref(),
+        &mut tx,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        error: "".into(),
+        payload: media,
+    }))
+}
+
+/// Change the caption or position of a screenshot or video
+#[utoipa::path(
+    put,
+    path = "/v1/mods/{id}/media/{media_id}",
+    tag = "mods",
+    params(MediaPath),
+    request_body = UpdateMediaPayload,
+    responses(
+        (status = 200, description = "Media updated", body = inline(ApiResponse<ModMedia>)),
+        (status = 400, description = "Invalid caption"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod or media not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[put("v1/mods/{id}/media/{media_id}")]
+pub async fn update(
+    data: web::Data<AppData>,
+    path: web::Path<MediaPath>,
+    json: web::Json<UpdateMediaPayload>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    check_access(&path.id, dev.id, dev.admin, &mut pool).await?;
+
+    let mut tx = pool.begin().await?;
+
+    if mod_media::get_one(&path.id, path.media_id, &mut tx)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::NotFound("Media not found".into()));
+    }
+
+    if let Some(caption) = &json.caption {
+        let caption = normalize_caption(Some(caption))?;
+        mod_media::update_caption(path.media_id, caption.as_deref(), &mut tx).await?;
+    }
+
+    if let Some(position) = json.position {
+        let mut ids: Vec<i32> = mod_media::get_for_mod(&path.id, &mut tx)
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .filter(|&id| id != path.media_id)
+            .collect();
+        ids.insert(position.min(ids.len()), path.media_id);
+        mod_media::reorder(&ids, &mut tx).await?;
+    }
+
+    let media = mod_media::get_one(&path.id, path.media_id, &mut tx)
+        .await?
+        .ok_or(ApiError::NotFound("Media not found".into()))?;
+
+    tx.commit().await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: with_url(media, data.public_storage()),
+    }))
+}
+
+/// Remove a screenshot or video from a mod
+#[utoipa::path(
+    delete,
+    path = "/v1/mods/{id}/media/{media_id}",
+    tag = "mods",
+    params(MediaPath),
+    responses(
+        (status = 204, description = "Media removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod or media not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[delete("v1/mods/{id}/media/{media_id}")]
+pub async fn delete(
+    data: web::Data<AppData>,
+    path: web::Path<MediaPath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    check_access(&path.id, dev.id, dev.admin, &mut pool).await?;
+
+    let mut tx = pool.begin().await?;
+
+    if mod_media::get_one(&path.id, path.media_id, &mut tx)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::NotFound("Media not found".into()));
+    }
+
+    // Screenshots are content-addressed and may be shared, so files are left in place
+    mod_media::delete(path.media_id, &mut tx).await?;
+
+    let ids: Vec<i32> = mod_media::get_for_mod(&path.id, &mut tx)
+        .await?
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    mod_media::reorder(&ids, &mut tx).await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::NoContent())
+}
+
+/// Rejects a submitted .geode whose screenshots don't fit under [`MAX_MOD_MEDIA`]
+/// together with the mod's uploaded media. Has to run in the transaction that
+/// adds the version.
+pub async fn check_mod_file_room(
+    mod_id: &str,
+    screenshots: usize,
+    conn: &mut PgConnection,
+) -> Result<(), ApiError> {
+    // the .geode's screenshots replace the previous ones, uploads stay
+    let uploaded = mod_media::lock_and_count(mod_id, Some(ModMediaSource::Upload), conn).await?;
+    if uploaded as usize + screenshots > MAX_MOD_MEDIA {
+        return Err(ApiError::BadRequest(format!(
+            "The .geode has {screenshots} screenshots, but only {} fit next to the {uploaded} uploaded screenshots and videos (max {MAX_MOD_MEDIA})",
+            MAX_MOD_MEDIA.saturating_sub(uploaded as usize)
+        )));
+    }
+
+    Ok(())
+}
+
+/// Stores the screenshots of a newly accepted .geode in place of the previous ones.
+/// The limit is checked on submission by [`check_mod_file_room`], but media can be
+/// uploaded while the version waits for review, so accepting it never fails over
+/// it: screenshots that no longer fit are left out instead.
+pub async fn replace_mod_file_screenshots(
+    storage: &PublicStorage,
+    mod_id: &str,
+    screenshots: &[Vec<u8>],
+    conn: &mut PgConnection,
+) -> Result<(), ApiError> {
+    let uploaded = mod_media::lock_and_count(mod_id, Some(ModMediaSource::Upload), conn).await?;
+    let room = MAX_MOD_MEDIA.saturating_sub(uploaded as usize);
+    if screenshots.len() > room {
+        log::warn!(
+            "Only storing {room} of the {} screenshots of {mod_id}, the rest don't fit next to its uploaded media",
+            screenshots.len()
+        );
+    }
+
+    let mut locations = Vec::with_capacity(screenshots.len().min(room));
+    for screenshot in screenshots.iter().take(room) {
+        locations.push(store_screenshot(storage, mod_id, screenshot).await?);
+    }
+
+    mod_media::replace_from_mod_file(mod_id, &locations, conn).await?;
+
+    Ok(())
+}
+
+/// Screenshots are stored as paths inside public storage
+pub fn with_urls(media: Vec<ModMedia>, storage: &PublicStorage) -> Vec<ModMedia> {
+    media.into_iter().map(|m| with_url(m, storage)).collect()
+}
+
+fn with_url(mut media: ModMedia, storage: &PublicStorage) -> ModMedia {
+    if media.kind == ModMediaKind::Screenshot {
+        media.url = storage.asset_url(&media.url);
+    }
+    media
+}
+
+async fn store_screenshot(
+    storage: &PublicStorage,
+    mod_id: &str,
+    screenshot: &[u8],
+) -> Result<String, ApiError> {
+    storage
+        .store_hashed_with_extension("screenshots", screenshot, Some("png"))
+        .await
+        .inspect_err(|e| log::error!("Failed to store screenshot for {mod_id}: {e}"))
+        .or(Err(ApiError::InternalError(
+            "Failed to store screenshot".into(),
+        )))
+}
+
+async fn check_access(
+    mod_id: &str,
+    dev_id: i32,
+    admin: bool,
+    conn: &mut PgConnection,
+) -> Result<(), ApiError> {
+    if !mods::exists(mod_id, conn).await? {
+        return Err(ApiError::ModNotFound(mod_id.into()));
+    }
+
+    if !admin && !developers::has_mod_role(dev_id, mod_id, ModDeveloperRole::Editor, conn).await? {
+        return Err(ApiError::Authorization);
+    }
+
+    Ok(())
+}
+
+/// Has to run in the transaction that adds the media, which then holds a lock on the mod
+async fn check_room(mod_id: &str, conn: &mut PgConnection) -> Result<(), ApiError> {
+    if mod_media::lock_and_count(mod_id, None, conn).await? as usize >= MAX_MOD_MEDIA {
+        return Err(ApiError::BadRequest(format!(
+            "Mods can have at most {MAX_MOD_MEDIA} screenshots and videos"
+        )));
+    }
+
+    Ok(())
+}
+
+fn normalize_caption(caption: Option<&str>) -> Result<Option<String>, ApiError> {
+    let Some(caption) = caption.map(str::trim).filter(|c| !c.is_empty()) else {
+        return Ok(None);
+    };
+
+    if caption.chars().count() > MAX_CAPTION_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "Caption is too long (max {MAX_CAPTION_LENGTH} characters)"
+        )));
+    }
+
+    Ok(Some(caption.into()))
+}
+
+fn parse_video_url(url: &str) -> Result<String, ApiError> {
+    let invalid = || ApiError::BadRequest("Videos must be https YouTube links".into());
+
+    let parsed = Url::parse(url.trim()).map_err(|_| invalid())?;
+    if parsed.scheme() != "https" || !parsed.host_str().is_some_and(|h| VIDEO_HOSTS.contains(&h)) {
+        return Err(invalid());
+    }
+
+    Ok(parsed.into())
+}
@@ -1,6 +1,12 @@
 use crate::config::AppData;
+use crate::database::repository::{mod_versions, mods};
 use crate::endpoints::ApiError;
+use crate::types::models::mod_gd_version::DetailedGDVersion;
+use crate::types::models::mod_version_status::ModVersionStatusEnum;
+use crate::xml::escape_xml;
+use actix_web::http::header;
 use actix_web::{HttpResponse, Responder, get, web};
+use geode_index_types::abbreviate::abbreviate_number;
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
@@ -10,6 +16,9 @@ use urlencoding;
 const LABEL_COLOR: &str = "#0c0811";
 const STAT_COLOR: &str = "#5f3d84";
 
+/// Same as what Shields.io uses for its own badges
+const BADGE_MAX_AGE: u32 = 5 * 60;
+
 #[derive(Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum StatusBadgeStat {
@@ -19,9 +28,34 @@ pub enum StatusBadgeStat {
     Downloads,
 }
 
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusBadgeStyle {
+    Flat,
+    FlatSquare,
+    #[default]
+    Plastic,
+}
+
+impl StatusBadgeStyle {
+    /// Name of the style on Shields.io
+    fn shields_name(self) -> &'static str {
+        match self {
+            StatusBadgeStyle::Flat => "flat",
+            StatusBadgeStyle::FlatSquare => "flat-square",
+            StatusBadgeStyle::Plastic => "plastic",
+        }
+    }
+}
+
 #[derive(Deserialize, IntoParams)]
 pub struct StatusBadgeQuery {
     pub stat: StatusBadgeStat,
+    /// Defaults to plastic
+    pub style: Option<StatusBadgeStyle>,
+    /// Redirect to a Shields.io badge instead of rendering it here
+    #[serde(default)]
+    pub redirect: bool,
 }
 
 #[utoipa::path(
@@ -33,7 +67,8 @@ pub struct StatusBadgeQuery {
         StatusBadgeQuery
     ),
     responses(
-        (status = 302, description = "Redirect to Shields.io badge"),
+        (status = 200, description = "Badge image", content_type = "image/svg+xml"),
+        (status = 302, description = "Redirect to Shields.io badge, if redirect is set"),
         (status = 400, description = "Invalid stat or missing parameter"),
         (status = 404, description = "Mod not found")
     )
@@ -71,20 +106,150 @@ pub async fn status_badge(
         .read(svg_path)
         .await
         .map_err(|_| ApiError::InternalError("Failed to read status badge file".into()))?;
-    let api_url = format!("{}/v1/mods/{}?abbreviate=true", data.app_url(), id);
-    let mod_link = format!("{}/mods/{}", data.front_url(), id);
     let svg_data_url = format!("data:image/svg+xml;utf8,{}", urlencoding::encode_binary(&svg));
-    let shields_url = format!(
-        "https://img.shields.io/badge/dynamic/json?url={}&query={}&label={}&labelColor={}&color={}&link={}&style=plastic&logo={}",
-        urlencoding::encode(&api_url),
-        urlencoding::encode(stat),
-        urlencoding::encode(label),
-        urlencoding::encode(LABEL_COLOR),
-        urlencoding::encode(STAT_COLOR),
-        urlencoding::encode(&mod_link),
-        urlencoding::encode(&svg_data_url)
+
+    if query.redirect {
+        let api_url = format!("{}/v1/mods/{}?abbreviate=true", data.app_url(), id);
+        let mod_link = format!("{}/mods/{}", data.front_url(), id);
+        let shields_url = format!(
+            "https://img.shields.io/badge/dynamic/json?url={}&query={}&label={}&labelColor={}&color={}&link={}&style={}&logo={}",
+            urlencoding::encode(&api_url),
+            urlencoding::encode(stat),
+            urlencoding::encode(label),
+            urlencoding::encode(LABEL_COLOR),
+            urlencoding::encode(STAT_COLOR),
+            urlencoding::encode(&mod_link),
+            query.style.unwrap_or_default().shields_name(),
+            urlencoding::encode(&svg_data_url)
+        );
+        return Ok(HttpResponse::Found()
+            .append_header(("Location", shields_url))
+            .finish());
+    }
+
+    let mut pool = data.db().acquire().await?;
+    let the_mod = mods::get_one(&id, false, &mut pool)
+        .await?
+        .ok_or(ApiError::ModNotFound(id.clone()))?;
+
+    let value = if query.stat == StatusBadgeStat::Downloads {
+        Some(abbreviate_number(the_mod.download_count.get()))
+    } else {
+        let latest = mod_versions::get_for_mod(
+            &the_mod.id,
+            Some(&[ModVersionStatusEnum::Accepted]),
+            &mut pool,
+        )
+        .await?
+        .into_iter()
+        .next();
+
+        latest.and_then(|v| match query.stat {
+            StatusBadgeStat::Version => Some(v.version),
+            StatusBadgeStat::GdVersion => gd_version(&v.gd),
+            StatusBadgeStat::GeodeVersion => Some(v.geode),
+            StatusBadgeStat::Downloads => None,
+        })
+    };
+
+    let badge = render_badge(
+        label,
+        value.as_deref().unwrap_or("none"),
+        &svg_data_url,
+        query.style.unwrap_or_default(),
     );
-    Ok(HttpResponse::Found()
-        .append_header(("Location", shields_url))
-        .finish())
+
+    Ok(HttpResponse::Ok()
+        .content_type("image/svg+xml")
+        .append_header((header::CACHE_CONTROL, format!("public, max-age={BADGE_MAX_AGE}")))
+        .body(badge))
+}
+
+/// Windows first, like the Shields.io badge did, then whatever else the version supports
+fn gd_version(gd: &DetailedGDVersion) -> Option<String> {
+    [
+        &gd.win,
+        &gd.mac_arm,
+        &gd.mac_intel,
+        &gd.android64,
+        &gd.android32,
+        &gd.ios,
+    ]
+    .into_iter()
+    .find_map(|v| v.as_ref().map(|v| v.to_string()))
+}
+
+const BADGE_HEIGHT: f32 = 20.0;
+const ICON_SIZE: f32 = 14.0;
+const PADDING: f32 = 5.0;
+
+/// Lays out a badge the way Shields.io does, with the icon on the label side
+fn render_badge(label: &str, value: &str, icon_url: &str, style: StatusBadgeStyle) -> String {
+    let label_text = text_width(label);
+    let value_text = text_width(value);
+
+    let label_width = PADDING + ICON_SIZE + 3.0 + label_text + PADDING;
+    let value_width = PADDING + value_text + PADDING;
+    let width = label_width + value_width;
+
+    let label_x = PADDING + ICON_SIZE + 3.0 + label_text / 2.0;
+    let value_x = label_width + value_width / 2.0;
+
+    let (radius, gradient) = match style {
+        StatusBadgeStyle::Flat => (
+            3.0,
+            r##"<stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/>"##,
+        ),
+        StatusBadgeStyle::FlatSquare => (0.0, ""),
+        StatusBadgeStyle::Plastic => (
+            4.0,
+            r##"<stop offset="0" stop-color="#fff" stop-opacity=".7"/><stop offset=".1" stop-color="#aaa" stop-opacity=".1"/><stop offset=".9" stop-opacity=".3"/><stop offset="1" stop-opacity=".5"/>"##,
+        ),
+    };
+    let shadow = style != StatusBadgeStyle::FlatSquare;
+
+    let label = escape_xml(label);
+    let value = escape_xml(value);
+    let icon_url = escape_xml(icon_url);
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{BADGE_HEIGHT}" role="img" aria-label="{label}: {value}"><title>{label}: {value}</title>"#
+    );
+    if !gradient.is_empty() {
+        svg += &format!(r#"<linearGradient id="s" x2="0" y2="100%">{gradient}</linearGradient>"#);
+    }
+    svg += &format!(
+        r##"<clipPath id="r"><rect width="{width}" height="{BADGE_HEIGHT}" rx="{radius}" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="{BADGE_HEIGHT}" fill="{LABEL_COLOR}"/><rect x="{label_width}" width="{value_width}" height="{BADGE_HEIGHT}" fill="{STAT_COLOR}"/>"##
+    );
+    if !gradient.is_empty() {
+        svg += &format!(r#"<rect width="{width}" height="{BADGE_HEIGHT}" fill="url(#s)"/>"#);
+    }
+    svg += r##"</g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">"##;
+    for (text, x, text_width) in [(&label, label_x, label_text), (&value, value_x, value_text)] {
+        if shadow {
+            svg += &format!(
+                r##"<text x="{x}" y="15" fill="#010101" fill-opacity=".3" textLength="{text_width}">{text}</text>"##
+            );
+        }
+        svg += &format!(r#"<text x="{x}" y="14" textLength="{text_width}">{text}</text>"#);
+    }
+    svg += &format!(
+        r#"</g><image x="{PADDING}" y="3" width="{ICON_SIZE}" height="{ICON_SIZE}" href="{icon_url}"/></svg>"#
+    );
+
+    svg
+}
+
+/// Rough widths of Verdana at 11px. Text is stretched to these with `textLength`,
+/// so they only need to be close enough to look right.
+fn text_width(text: &str) -> f32 {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '!' | '|' => 3.5,
+            ' ' | 'f' | 'r' | 't' | 'I' | '(' | ')' | '[' | ']' | '-' => 4.5,
+            'm' | 'w' | 'M' | 'W' => 10.0,
+            'A'..='Z' => 7.5,
+            _ => 7.0,
+        })
+        .sum()
 }
@@ -0,0 +1,236 @@
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::Acquire;
+use utoipa::{IntoParams, ToSchema};
+
+use super::ApiError;
+use crate::config::AppData;
+use crate::database::repository::{developers, mod_transfers, mods};
+use crate::extractors::auth::Auth;
+use crate::types::api::ApiResponse;
+use crate::types::models::mod_transfer::{ModTransfer, ModTransferStatus};
+
+#[derive(Deserialize, IntoParams)]
+struct TransferPath {
+    id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct TransferPayload {
+    /// Username of the new owner
+    username: String,
+    /// Admin only: transfer immediately without waiting for the recipient
+    #[serde(default)]
+    force: bool,
+}
+
+/// Propose a new owner for a mod, or force a transfer as an admin
+#[utoipa::path(
+    post,
+    path = "/v1/mods/{id}/transfer",
+    tag = "mods",
+    params(TransferPath),
+    request_body = TransferPayload,
+    responses(
+        (status = 201, description = "Transfer created", body = inline(ApiResponse<ModTransfer>)),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/mods/{id}/transfer")]
+pub async fn create_transfer(
+    data: web::Data<AppData>,
+    path: web::Path<TransferPath>,
+    json: web::Json<TransferPayload>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    if !mods::exists(&path.id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(path.id.clone()));
+    }
+
+    if json.force {
+        auth.check_admin()?;
+    } else if !developers::owns_mod(dev.id, &path.id, &mut pool).await? {
+        return Err(ApiError::Authorization);
+    }
+
+    let target = developers::get_one_by_username(&json.username, &mut pool)
+        .await?
+        .ok_or(ApiError::BadRequest(format!(
+            "No developer found with username {}",
+            json.username
+        )))?;
+
+    let owner = developers::get_owner_for_mod(&path.id, &mut pool).await?;
+
+    if owner.as_ref().is_some_and(|o| o.id == target.id) {
+        return Err(ApiError::BadRequest(format!(
+            "{} already owns this mod",
+            target.username
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    mod_transfers::cancel_pending_for_mod(&path.id, &mut tx).await?;
+
+    let status = if json.force {
+        mods::transfer_ownership(&path.id, target.id, &mut tx).await?;
+        ModTransferStatus::Forced
+    } else {
+        ModTransferStatus::Pending
+    };
+
+    let transfer = mod_transfers::create(
+        &path.id,
+        owner.map(|o| o.id),
+        target.id,
+        dev.id,
+        status,
+        &mut tx,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    log::info!(
+        "Mod {} transfer to {} created by {} (status {:?})",
+        path.id,
+        target.username,
+        dev.username,
+        transfer.status
+    );
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        error: "".into(),
+        payload: transfer,
+    }))
+}
+
+/// Accept a pending ownership transfer addressed to the current developer
+#[utoipa::path(
+    post,
+    path = "/v1/mods/{id}/transfer/accept",
+    tag = "mods",
+    params(TransferPath),
+    responses(
+        (status = 200, description = "Transfer accepted", body = inline(ApiResponse<ModTransfer>)),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No pending transfer for this developer")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/mods/{id}/transfer/accept")]
+pub async fn accept_transfer(
+    data: web::Data<AppData>,
+    path: web::Path<TransferPath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    let pending = mod_transfers::get_pending_for_mod(&path.id, &mut pool)
+        .await?
+        .filter(|t| t.to_developer_id == dev.id)
+        .ok_or(ApiError::NotFound(format!(
+            "No pending transfer of mod {} for you",
+            path.id
+        )))?;
+
+    let mut tx = pool.begin().await?;
+
+    mods::transfer_ownership(&path.id, dev.id, &mut tx).await?;
+    let transfer =
+        mod_transfers::resolve(pending.id, ModTransferStatus::Accepted, &mut tx).await?;
+
+    tx.commit().await?;
+
+    log::info!("Mod {} transferred to {}", path.id, dev.username);
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: transfer,
+    }))
+}
+
+/// Cancel (as the owner) or reject (as the recipient) a pending ownership transfer
+#[utoipa::path(
+    delete,
+    path = "/v1/mods/{id}/transfer",
+    tag = "mods",
+    params(TransferPath),
+    responses(
+        (status = 204, description = "Transfer cancelled or rejected"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "No pending transfer")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[delete("v1/mods/{id}/transfer")]
+pub async fn delete_transfer(
+    data: web::Data<AppData>,
+    path: web::Path<TransferPath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    let pending = mod_transfers::get_pending_for_mod(&path.id, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound(format!(
+            "No pending transfer for mod {}",
+            path.id
+        )))?;
+
+    let status = if pending.to_developer_id == dev.id {
+        ModTransferStatus::Rejected
+    } else if dev.admin || developers::owns_mod(dev.id, &path.id, &mut pool).await? {
+        ModTransferStatus::Cancelled
+    } else {
+        return Err(ApiError::Authorization);
+    };
+
+    mod_transfers::resolve(pending.id, status, &mut pool).await?;
+
+    Ok(HttpResponse::NoContent())
+}
+
+/// List pending ownership transfers addressed to the current developer
+#[utoipa::path(
+    get,
+    path = "/v1/me/transfers",
+    tag = "developers",
+    responses(
+        (status = 200, description = "Pending transfers", body = inline(ApiResponse<Vec<ModTransfer>>)),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[get("v1/me/transfers")]
+pub async fn get_own_transfers(
+    data: web::Data<AppData>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: mod_transfers::get_pending_for_developer(dev.id, &mut pool).await?,
+    }))
+}
@@ -1,16 +1,18 @@
 use std::str::FromStr;
 
-use actix_web::{dev::ConnectionInfo, get, post, put, web, HttpResponse, Responder};
+use actix_web::{dev::ConnectionInfo, get, post, put, web, HttpRequest, HttpResponse, Responder};
 use serde::Deserialize;
-use sqlx::{types::ipnetwork::IpNetwork, Acquire};
+use sqlx::Acquire;
+use std::net::IpAddr;
 use utoipa::{ToSchema, IntoParams};
 
 use crate::config::AppData;
 use crate::database::repository::{
-    dependencies, developers, incompatibilities, mod_downloads, mod_gd_versions, mod_links,
-    mod_tags, mod_versions, mods,
+    dependencies, developers, incompatibilities, mod_downloads, mod_gd_versions,
+    mod_links, mod_localizations, mod_tags, mod_version_files, mod_versions, mods,
 };
-use crate::endpoints::ApiError;
+use crate::endpoints::mods::store_logo_renditions;
+use crate::endpoints::{ApiError, mod_media};
 use crate::events::mod_created::{
     NewModAcceptedEvent, NewModVersionAcceptedEvent, NewModVersionVerification,
 };
@@ -23,8 +25,10 @@ use crate::{
         api::ApiResponse,
         mod_json::{split_version_and_compare, ModJson},
         models::{
-            mod_gd_version::{GDVersionEnum, VerPlatform},
-            mod_version::{self, ModVersion},
+            developer::ModDeveloperRole,
+            mod_gd_version::{GDVersion, VerPlatform},
+            mod_version::{self, ModVersion, ModVersionQueries},
+            mod_version_file::ModVersionFile,
             mod_version_status::ModVersionStatusEnum,
         },
     },
@@ -70,7 +74,7 @@ struct IndexQuery {
     page: Option<i64>,
     per_page: Option<i64>,
     #[serde(default)]
-    gd: Option<GDVersionEnum>,
+    gd: Option<GDVersion>,
     platforms: Option<String>,
     status: Option<ModVersionStatusEnum>,
     compare: Option<String>,
@@ -119,7 +123,7 @@ pub async fn get_version_index(
             page: query.page.unwrap_or(1),
             per_page: query.per_page.unwrap_or(10),
             compare,
-            gd: query.gd,
+            gd: query.gd.clone(),
             platforms,
             status: query.status.unwrap_or(ModVersionStatusEnum::Accepted),
         },
@@ -163,9 +167,9 @@ pub async fn get_one(
 
     let mut version = {
         if path.version == "latest" {
-            let gd: Option<GDVersionEnum> = match query.gd {
+            let gd: Option<GDVersion> = match query.gd {
                 Some(ref gd) => Some(
-                    GDVersionEnum::from_str(gd)
+                    GDVersion::from_str(gd)
                         .or(Err(ApiError::BadRequest("Invalid gd".to_string())))?,
                 ),
                 None => None,
@@ -179,7 +183,7 @@ pub async fn get_one(
             ModVersion::get_one(&path.id, &path.version, true, false, &mut pool).await?
         }
     }
-    .ok_or(ApiError::NotFound(
+    .ok_or(ApiError::VersionNotFound(
         "Couldn't find valid mod version for given filters".into(),
     ))?;
 
@@ -190,12 +194,125 @@ pub async fn get_one(
     }))
 }
 
+/// Get the mod.json of a version, exactly as it was submitted
+#[utoipa::path(
+    get,
+    path = "/v1/mods/{id}/versions/{version}/manifest",
+    tag = "mod_versions",
+    params(GetOnePath),
+    responses(
+        (status = 200, description = "The submitted mod.json", content_type = "application/json", body = String),
+        (status = 404, description = "Mod or version not found, or the version predates stored manifests")
+    )
+)]
+#[get("v1/mods/{id}/versions/{version}/manifest")]
+pub async fn get_manifest(
+    path: web::Path<GetOnePath>,
+    data: web::Data<AppData>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+
+    let version = ModVersion::get_one(&path.id, &path.version, false, false, &mut pool)
+        .await?
+        .ok_or(ApiError::VersionNotFound(format!(
+            "Couldn't find version {} of {}",
+            path.version, path.id
+        )))?;
+
+    let manifest = mod_versions::get_mod_json(version.id, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound(
+            "No mod.json was kept for this version".into(),
+        ))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .body(manifest))
+}
+
+/// Get the files inside the .geode of a version
+#[utoipa::path(
+    get,
+    path = "/v1/mods/{id}/versions/{version}/files",
+    tag = "mod_versions",
+    params(GetOnePath),
+    responses(
+        (status = 200, description = "Entries of the .geode, in archive order", body = inline(ApiResponse<Vec<ModVersionFile>>)),
+        (status = 404, description = "Mod or version not found, or the version predates stored file listings")
+    )
+)]
+#[get("v1/mods/{id}/versions/{version}/files")]
+pub async fn get_files(
+    path: web::Path<GetOnePath>,
+    data: web::Data<AppData>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+
+    let version = ModVersion::get_one(&path.id, &path.version, false, false, &mut pool)
+        .await?
+        .ok_or(ApiError::VersionNotFound(format!(
+            "Couldn't find version {} of {}",
+            path.version, path.id
+        )))?;
+
+    // Versions uploaded before listings were kept have no files
+    let files = mod_version_files::get_for_version(version.id, &mut pool).await?;
+    if files.is_empty() {
+        return Err(ApiError::NotFound("No file listing was kept for this version".into()));
+    }
+
+    Ok(web::Json(ApiResponse {
+        error: "".to_string(),
+        payload: files,
+    }))
+}
+
 #[derive(Deserialize, IntoParams)]
 struct DownloadQuery {
-    gd: Option<GDVersionEnum>,
+    gd: Option<GDVersion>,
     // platform1,platform2,...
     platforms: Option<String>,
     major: Option<u32>,
+    /// Geode version of the client, can also be sent in the X-Geode-Version header
+    geode: Option<String>,
+}
+
+impl DownloadQuery {
+    /// Only used for download statistics, so anything we can't make sense of is ignored
+    fn details(&self, req: &HttpRequest) -> mod_downloads::DownloadDetails {
+        // `android` and `mac` are recorded as they are, since the client
+        // didn't say which architecture it's on
+        let platform = self.platforms.as_deref().and_then(|p| {
+            let mut platforms = p.split(',').map(str::trim).filter(|x| !x.is_empty());
+            match (platforms.next(), platforms.next()) {
+                (Some(single), None) => VerPlatform::from_str(single).ok(),
+                _ => None,
+            }
+        });
+        let gd_version = self.gd.clone().filter(|g| !g.is_all());
+        let loader_version = self
+            .geode
+            .as_deref()
+            .or_else(|| {
+                req.headers()
+                    .get("X-Geode-Version")
+                    .and_then(|h| h.to_str().ok())
+            })
+            .and_then(|v| semver::Version::parse(v.trim().trim_start_matches('v')).ok())
+            .map(|v| v.to_string());
+        let has_user_agent = req
+            .headers()
+            .get("User-Agent")
+            .is_some_and(|h| !h.is_empty());
+
+        mod_downloads::DownloadDetails {
+            platform,
+            gd_version,
+            loader_version,
+            subnet_hash: None,
+            has_user_agent,
+        }
+    }
 }
 
 /// Download a specific version of a mod (redirects to download URL)
@@ -215,19 +332,20 @@ pub async fn download_version(
     data: web::Data<AppData>,
     query: web::Query<DownloadQuery>,
     info: ConnectionInfo,
+    req: HttpRequest,
 ) -> Result<impl Responder, ApiError> {
     let mut pool = data.db().acquire().await?;
     let mod_version = {
         if path.version == "latest" {
             let platform_str = query.platforms.clone().unwrap_or_default();
             let platforms = VerPlatform::parse_query_string(&platform_str)?;
-            ModVersion::get_latest_for_mod(&path.id, query.gd, platforms, query.major, &mut pool)
+            ModVersion::get_latest_for_mod(&path.id, query.gd.clone(), platforms, query.major, &mut pool)
                 .await?
         } else {
             ModVersion::get_one(&path.id, &path.version, false, false, &mut pool).await?
         }
     }
-    .ok_or(ApiError::NotFound(
+    .ok_or(ApiError::VersionNotFound(
         "Couldn't find valid mod version for given filters".into(),
     ))?;
     let url = mod_version.download_link;
@@ -245,14 +363,27 @@ pub async fn download_version(
         ));
     };
 
-    let net = ip.parse::<IpNetwork>().ok();
+    let ip = ip.parse::<IpAddr>().ok();
 
-    if let Some(net) = net {
+    if let Some(ip) = ip {
         let mut tx = pool.begin().await?;
 
+        let secret = data.download_hash_secret();
+        let ip_hashes = mod_downloads::hash_ip(ip, secret, data.download_dedup_days());
+
         let downloaded_mod_previously =
-            mod_downloads::has_downloaded_mod(net, &mod_version.mod_id, &mut tx).await?;
-        let inserted = mod_downloads::create(net, mod_version.id, &mut tx).await?;
+            mod_downloads::has_downloaded_mod(&ip_hashes, &mod_version.mod_id, &mut tx).await?;
+        let mut details = query.details(&req);
+        details.subnet_hash = Some(mod_downloads::hash_subnet(ip, secret));
+        let inserted =
+            mod_downloads::create(
+                &ip_hashes,
+                mod_version.id,
+                &details,
+                !downloaded_mod_previously,
+                &mut tx,
+            )
+            .await?;
 
         if inserted {
             mod_versions::increment_downloads(mod_version.id, &mut tx).await?;
@@ -304,9 +435,16 @@ pub async fn create_version(
 
     let the_mod = mods::get_one(&id, false, &mut pool)
         .await?
-        .ok_or(ApiError::NotFound(format!("Mod {} not found", &id)))?;
+        .ok_or(ApiError::ModNotFound(id.clone()))?;
 
-    if !developers::has_access_to_mod(dev.id, &the_mod.id, &mut pool).await? {
+    if !developers::has_mod_role(
+        dev.id,
+        &the_mod.id,
+        ModDeveloperRole::Maintainer,
+        &mut pool,
+    )
+    .await?
+    {
         return Err(ApiError::Authorization);
     }
 
@@ -353,7 +491,7 @@ pub async fn create_version(
     json.validate()?;
 
     if versions.iter().any(|v| v.version == json.version) {
-        return Err(ApiError::BadRequest(format!(
+        return Err(ApiError::VersionExists(format!(
             "Version {} already exists",
             json.version
         )));
@@ -376,7 +514,7 @@ pub async fn create_version(
         ))?;
 
         if new_version == latest_version {
-            return Err(ApiError::BadRequest(format!(
+            return Err(ApiError::VersionExists(format!(
                 "mod.json has the same version as the latest version: {}",
                 new_version
             )));
@@ -400,6 +538,8 @@ pub async fn create_version(
         }
     };
 
+    mod_media::check_mod_file_room(&the_mod.id, json.screenshots.len(), &mut tx).await?;
+
     version.gd = mod_gd_versions::create(version.id, &json, &mut tx).await?;
     version.dependencies = Some(
         dependencies::create(version.id, &json, &mut tx)
@@ -434,6 +574,15 @@ pub async fn create_version(
             }
         }
 
+        mod_media::replace_mod_file_screenshots(
+            data.public_storage(),
+            &the_mod.id,
+            &json.screenshots,
+            &mut tx,
+        )
+        .await?;
+        store_logo_renditions(data.public_storage(), &the_mod.id, &json.logo, &mut tx).await?;
+        mod_localizations::replace(&the_mod.id, &json.localized, &mut tx).await?;
         mods::update_with_json_moved(the_mod, json, &mut tx).await?;
     }
 
@@ -495,7 +644,7 @@ pub async fn update_version(
 
     let the_mod = mods::get_one(&path.id, false, &mut pool)
         .await?
-        .ok_or(ApiError::NotFound(format!("Mod {} not found", path.id)))?;
+        .ok_or(ApiError::ModNotFound(path.id.clone()))?;
 
     if !dev.admin {
         return Err(ApiError::Authorization);
@@ -503,7 +652,7 @@ pub async fn update_version(
 
     let version = mod_versions::get_by_version_str(&the_mod.id, &path.version, &mut pool)
         .await?
-        .ok_or(ApiError::NotFound(format!(
+        .ok_or(ApiError::VersionNotFound(format!(
             "Version {} not found",
             path.version
         )))?;
@@ -571,6 +720,15 @@ pub async fn update_version(
 
         mod_tags::update_for_mod(&the_mod.id, &tags, &mut tx).await?;
 
+        mod_media::replace_mod_file_screenshots(
+            data.public_storage(),
+            &the_mod.id,
+            &json.screenshots,
+            &mut tx,
+        )
+        .await?;
+        store_logo_renditions(data.public_storage(), &the_mod.id, &json.logo, &mut tx).await?;
+        mod_localizations::replace(&the_mod.id, &json.localized, &mut tx).await?;
         mods::update_with_json_moved(the_mod, json, &mut tx).await?;
     }
 
@@ -1,65 +1,55 @@
-#[derive(Deserialize, ToSchema)]
+#[derive(Deserialize, IntoParams)]
 pub struct ModGetQueryParams {
     pub abbreviate: Option<bool>,
+    /// Format of `about` and `changelog`, defaults to markdown
+    pub format: Option<ContentFormat>,
+    /// Language of `about` and `changelog`, takes precedence over Accept-Language
+    pub lang: Option<String>,
 }
 use crate::config::AppData;
 use crate::database::repository::developers;
 use crate::database::repository::incompatibilities;
+use crate::database::repository::mod_downloads;
 use crate::database::repository::mod_gd_versions;
 use crate::database::repository::mod_links;
+use crate::database::repository::mod_localizations;
+use crate::database::repository::mod_logo_renditions::{self, StoredRendition};
+use crate::database::repository::mod_media as mod_media_repo;
 use crate::database::repository::mod_tags;
 use crate::database::repository::mod_versions;
 use crate::database::repository::mods;
 use crate::database::repository::{dependencies, deprecations};
-use crate::endpoints::ApiError;
+use crate::endpoints::{ApiError, mod_media};
 use crate::events::mod_feature::ModFeaturedEvent;
+use crate::extractors::accept_language::AcceptLanguage;
 use crate::extractors::auth::Auth;
+use crate::images::{self, LOGO_SIZES};
+use crate::markdown;
 use crate::mod_zip;
+use crate::storage::{PublicStorage, StorageDisk};
 use crate::types::api::{ApiResponse, PaginatedData};
 use crate::types::mod_json::ModJson;
 use crate::types::models;
-use crate::types::models::deprecations::Deprecation;
-use crate::types::models::mod_entity::{Mod, ModUpdate};
-use crate::types::models::mod_gd_version::{GDVersionEnum, VerPlatform};
-use crate::types::models::mod_link::ModLinks;
+use crate::types::models::changelog::ChangelogEntry;
+use crate::types::models::developer::ModDeveloperRole;
+use crate::types::models::download_stats::{DownloadStatsPoint, ModDownloadStats, StatsGranularity};
+use crate::types::models::mod_entity::{ContentFormat, Mod, ModQueries, ModUpdate};
+use crate::types::models::mod_gd_version::VerPlatform;
+use crate::types::models::mod_link;
+use crate::types::models::mod_logo::LogoFormat;
 use crate::types::models::mod_version_status::ModVersionStatusEnum;
 use crate::webhook::discord::DiscordWebhook;
-use actix_web::{HttpResponse, Responder, get, post, put, web};
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, put, web};
+use chrono::{Datelike, Days, Months, NaiveDate, Utc};
 use serde::Deserialize;
-use serde::Serialize;
-use sqlx::Acquire;
-use utoipa::{ToSchema, IntoParams};
-
-#[derive(Deserialize, Default, Hash, Eq, PartialEq, ToSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum IndexSortType {
-    #[default]
-    Downloads,
-    RecentlyUpdated,
-    RecentlyPublished,
-    Oldest,
-    Name,
-    NameReverse,
-}
+use sqlx::{Acquire, PgConnection};
+use std::collections::BTreeMap;
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Deserialize, Hash, Eq, PartialEq, IntoParams)]
-pub struct IndexQueryParams {
-    pub page: Option<i64>,
-    pub per_page: Option<i64>,
-    pub query: Option<String>,
-    #[serde(default)]
-    pub gd: Option<GDVersionEnum>,
-    #[serde(default)]
-    pub platforms: Option<String>,
-    #[serde(default)]
-    pub sort: IndexSortType,
-    pub geode: Option<String>,
-    pub developer: Option<String>,
-    pub tags: Option<String>,
-    pub featured: Option<bool>,
-    pub jitless: Option<bool>,
-    pub status: Option<ModVersionStatusEnum>,
-}
+pub use geode_index_types::models::mod_entity::{
+    IndexQueryParams, IndexSortType, UpdateQueryParams, UpdateQueryResponse,
+};
 
 #[derive(Deserialize, ToSchema)]
 pub struct CreateQueryParams {
@@ -119,7 +109,8 @@ pub async fn index(
     path = "/v1/mods/{id}",
     tag = "mods",
     params(
-        ("id" = String, Path, description = "Mod ID")
+        ("id" = String, Path, description = "Mod ID"),
+        ModGetQueryParams
     ),
     responses(
         (status = 200, description = "Mod details", body = inline(ApiResponse<Mod>)),
@@ -131,6 +122,7 @@ pub async fn get(
     data: web::Data<AppData>,
     id: web::Path<String>,
     query: web::Query<ModGetQueryParams>,
+    accept_language: AcceptLanguage,
     auth: Auth,
 ) -> Result<impl Responder, ApiError> {
     let dev = auth.developer().ok();
@@ -143,7 +135,7 @@ pub async fn get(
 
     let mut the_mod: Mod = mods::get_one(&id, true, &mut pool)
         .await?
-        .ok_or(ApiError::NotFound(format!("Mod '{id}' not found")))?;
+        .ok_or(ApiError::ModNotFound(id.clone()))?;
 
     let version_statuses = match dev {
         None => Some(vec![
@@ -170,7 +162,11 @@ pub async fn get(
     the_mod.developers = developers::get_all_for_mod(&the_mod.id, &mut pool).await?;
     the_mod.versions =
         mod_versions::get_for_mod(&the_mod.id, version_statuses.as_deref(), &mut pool).await?;
-    the_mod.links = ModLinks::fetch(&the_mod.id, &mut pool).await?;
+    the_mod.links = mod_link::fetch(&the_mod.id, &mut pool).await?;
+    the_mod.media = mod_media::with_urls(
+        mod_media_repo::get_for_mod(&the_mod.id, &mut pool).await?,
+        data.public_storage(),
+    );
 
     for i in &mut the_mod.versions {
         i.modify_metadata(data.app_url(), has_extended_permissions);
@@ -178,12 +174,82 @@ pub async fn get(
 
     the_mod.set_abbreviated_download_counts(query.abbreviate.unwrap_or(false));
 
-    Ok(web::Json(ApiResponse {
+    let html = query.format == Some(ContentFormat::Html);
+    if html {
+        use_html(std::slice::from_mut(&mut the_mod), &mut pool).await?;
+    }
+
+    let localizations = mod_localizations::get_for_mod(&the_mod.id, &mut pool).await?;
+    let languages: Vec<String> = localizations.iter().map(|l| l.lang.clone()).collect();
+    let language = accept_language.best_match(query.lang.as_deref(), &languages);
+    let localized = localizations
+        .into_iter()
+        .find(|l| language.as_ref() == Some(&l.lang));
+
+    let mut response = HttpResponse::Ok();
+    response.append_header((header::VARY, "Accept-Language"));
+
+    // Either file falls back to the untranslated one when it has no translation
+    if let Some(l) = localized {
+        let about = pick_format(l.about, l.about_html, html);
+        let changelog = pick_format(l.changelog, l.changelog_html, html);
+
+        // Mixing translated and untranslated files leaves no single language to report
+        let mixed = (about.is_none() && the_mod.about.is_some())
+            || (changelog.is_none() && the_mod.changelog.is_some());
+        if !mixed && (about.is_some() || changelog.is_some()) {
+            response.append_header((header::CONTENT_LANGUAGE, l.lang));
+        }
+        if about.is_some() {
+            the_mod.about = about;
+        }
+        if changelog.is_some() {
+            the_mod.changelog = changelog;
+        }
+    }
+
+    Ok(response.json(ApiResponse {
         error: "".into(),
         payload: the_mod,
     }))
 }
 
+fn pick_format(markdown: Option<String>, html: Option<String>, want_html: bool) -> Option<String> {
+    if want_html {
+        html.or_else(|| markdown.as_deref().map(markdown::render_html))
+    } else {
+        markdown
+    }
+}
+
+/// Swaps `about` and `changelog` for their sanitized HTML versions. Mods that haven't
+/// been through the render-markdown job yet are rendered here.
+async fn use_html(list: &mut [Mod], conn: &mut PgConnection) -> Result<(), ApiError> {
+    let ids: Vec<String> = list.iter().map(|m| m.id.clone()).collect();
+    let rendered = mods::get_rendered_markdown(&ids, conn).await?;
+
+    for m in list {
+        let Some(r) = rendered.iter().find(|r| r.id == m.id) else {
+            continue;
+        };
+
+        if m.about.is_some() {
+            m.about = r
+                .about_html
+                .clone()
+                .or_else(|| r.about.as_deref().map(markdown::render_html));
+        }
+        if m.changelog.is_some() {
+            m.changelog = r
+                .changelog_html
+                .clone()
+                .or_else(|| r.changelog.as_deref().map(markdown::render_html));
+        }
+    }
+
+    Ok(())
+}
+
 /// Create a new mod
 #[utoipa::path(
     post,
@@ -219,17 +285,19 @@ pub async fn create(
     }
 
     if let Some(m) = &existing {
-        if !developers::has_access_to_mod(dev.id, &m.id, &mut pool).await? {
+        if !developers::has_mod_role(dev.id, &m.id, ModDeveloperRole::Maintainer, &mut pool)
+            .await?
+        {
             return Err(ApiError::Authorization);
         }
 
         let versions = mod_versions::get_for_mod(&m.id, None, &mut pool).await?;
 
         if !versions.is_empty() {
-            return Ok(HttpResponse::Conflict().json(ApiResponse {
-                error: format!("Mod {} already exists! Submit a new version.", m.id),
-                payload: "",
-            }));
+            return Err(ApiError::ModExists(format!(
+                "Mod {} already exists! Submit a new version.",
+                m.id
+            )));
         }
     }
 
@@ -246,8 +314,12 @@ pub async fn create(
 
     if !mod_already_exists {
         mods::assign_owner(&the_mod.id, dev.id, &mut tx).await?;
+        store_logo_renditions(data.public_storage(), &the_mod.id, &json.logo, &mut tx).await?;
+        mod_localizations::replace(&the_mod.id, &json.localized, &mut tx).await?;
     }
 
+    mod_media::check_mod_file_room(&the_mod.id, json.screenshots.len(), &mut tx).await?;
+
     if let Some(tags) = &json.tags {
         let tag_list = models::tag::parse_tag_list(tags, &the_mod.id, &mut tx).await?;
         mod_tags::update_for_mod(&the_mod.id, &tag_list, &mut tx).await?;
@@ -290,23 +362,6 @@ pub async fn create(
     }))
 }
 
-#[derive(Deserialize, IntoParams)]
-struct UpdateQueryParams {
-    ids: String,
-    gd: GDVersionEnum,
-    platform: VerPlatform,
-    geode: String,
-}
-#[derive(Serialize, ToSchema)]
-#[serde(untagged)]
-enum UpdateQueryResponse {
-    V4(Vec<ModUpdate>),
-    V5 {
-        updates: Vec<ModUpdate>,
-        deprecations: Vec<Deprecation>,
-    },
-}
-
 /// Get available mod updates for a list of installed mods
 #[utoipa::path(
     get,
@@ -343,7 +398,7 @@ pub async fn get_mod_updates(
     })?;
 
     let result: Vec<ModUpdate> =
-        Mod::get_updates(&ids, query.platform, &geode, query.gd, &mut pool).await?;
+        Mod::get_updates(&ids, query.platform, &geode, query.gd.clone(), &mut pool).await?;
 
     // On v5, we return deprecations as a separate array
     // v5 prereleases had a matjson bug that broke updates, so return the < v5 response for them
@@ -365,16 +420,30 @@ pub async fn get_mod_updates(
     }))
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct LogoQueryParams {
+    /// Width and height in pixels, one of 64, 128 or 336. Defaults to 336
+    size: Option<u32>,
+    /// Defaults to png
+    format: Option<LogoFormat>,
+}
+
+/// Logos change whenever a new version is accepted, so they can't be cached forever
+const LOGO_MAX_AGE: u32 = 60 * 60;
+
 /// Get the logo image for a mod
 #[utoipa::path(
     get,
     path = "/v1/mods/{id}/logo",
     tag = "mods",
     params(
-        ("id" = String, Path, description = "Mod ID")
+        ("id" = String, Path, description = "Mod ID"),
+        LogoQueryParams
     ),
     responses(
-        (status = 200, description = "Mod logo image", content_type = "image/png"),
+        (status = 200, description = "Mod logo image, image/webp when format is webp", content_type = "image/png"),
+        (status = 304, description = "Logo matches If-None-Match"),
+        (status = 400, description = "Unsupported size"),
         (status = 404, description = "Logo not found")
     )
 )]
@@ -382,15 +451,357 @@ pub async fn get_mod_updates(
 pub async fn get_logo(
     data: web::Data<AppData>,
     path: web::Path<String>,
+    query: web::Query<LogoQueryParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let size = query.size.unwrap_or(LOGO_SIZES[LOGO_SIZES.len() - 1]);
+    if !LOGO_SIZES.contains(&size) {
+        return Err(ApiError::BadRequest(format!(
+            "Logo size must be one of {}",
+            LOGO_SIZES.map(|s| s.to_string()).join(", ")
+        )));
+    }
+    let format = query.format.unwrap_or_default();
+
+    let id = path.into_inner();
+    let mut pool = data.db().acquire().await?;
+
+    let stored = match mod_logo_renditions::get_path(&id, size as i32, format, &mut pool).await? {
+        Some(p) => data
+            .public_storage()
+            .read(&p)
+            .await
+            .inspect_err(|e| log::error!("Failed to read logo {p}: {e}"))
+            .or(Err(ApiError::InternalError("Failed to read logo".into())))?,
+        None => vec![],
+    };
+
+    // Renditions are missing for logos the backfill job hasn't seen yet
+    let image = if !stored.is_empty() {
+        stored
+    } else {
+        let Some(logo) = mods::get_logo(&id, &mut pool).await? else {
+            return Ok(HttpResponse::NotFound().body(""));
+        };
+        images::render_logo(&logo, size, format)
+            .inspect_err(|e| log::error!("Failed to render logo for {id}: {e}"))
+            .or(Err(ApiError::InternalError("Failed to render logo".into())))?
+    };
+
+    let etag = format!("\"{}\"", sha256::digest(&image));
+    let cache_control = format!("public, max-age={LOGO_MAX_AGE}");
+
+    let not_modified = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag));
+
+    if not_modified {
+        return Ok(HttpResponse::NotModified()
+            .append_header((header::ETAG, etag))
+            .append_header((header::CACHE_CONTROL, cache_control))
+            .finish());
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .append_header((header::ETAG, etag))
+        .append_header((header::CACHE_CONTROL, cache_control))
+        .body(image))
+}
+
+/// Renders the mod's logo at every size and format and stores the files in public storage
+pub async fn store_logo_renditions(
+    storage: &PublicStorage,
+    mod_id: &str,
+    logo: &[u8],
+    conn: &mut PgConnection,
+) -> Result<(), ApiError> {
+    let renditions = if logo.is_empty() {
+        vec![]
+    } else {
+        images::render_logo_renditions(logo)
+            .inspect_err(|e| log::error!("Failed to render logo for {mod_id}: {e}"))
+            .or(Err(ApiError::InternalError("Failed to render logo".into())))?
+    };
+
+    let mut stored = Vec::with_capacity(renditions.len());
+    for rendition in renditions {
+        let path = storage
+            .store_hashed_with_extension("logos", &rendition.data, Some(rendition.format.extension()))
+            .await
+            .inspect_err(|e| log::error!("Failed to store logo for {mod_id}: {e}"))
+            .or(Err(ApiError::InternalError("Failed to store logo".into())))?;
+
+        stored.push(StoredRendition {
+            size: rendition.size as i32,
+            format: rendition.format,
+            path,
+        });
+    }
+
+    mod_logo_renditions::replace(mod_id, &stored, conn).await?;
+
+    Ok(())
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct ModStatsQueryParams {
+    /// First day to include, defaults to 30 days before `to`
+    from: Option<NaiveDate>,
+    /// Last day to include, defaults to today (UTC)
+    to: Option<NaiveDate>,
+    /// Only the mod's developers can use `day`, everyone else gets `week` or coarser
+    granularity: Option<StatsGranularity>,
+}
+
+/// Longest range that can be requested at once
+const MAX_STATS_RANGE_DAYS: i64 = 366;
+
+/// Get download statistics for a mod
+#[utoipa::path(
+    get,
+    path = "/v1/mods/{id}/stats",
+    tag = "mods",
+    params(
+        ("id" = String, Path, description = "Mod ID"),
+        ModStatsQueryParams
+    ),
+    responses(
+        (status = 200, description = "Downloads per period", body = inline(ApiResponse<ModDownloadStats>)),
+        (status = 400, description = "Invalid date range"),
+        (status = 404, description = "Mod not found")
+    )
+)]
+#[get("/v1/mods/{id}/stats")]
+pub async fn get_stats(
+    data: web::Data<AppData>,
+    id: web::Path<String>,
+    query: web::Query<ModStatsQueryParams>,
+    auth: Auth,
 ) -> Result<impl Responder, ApiError> {
-    use crate::database::repository::*;
     let mut pool = data.db().acquire().await?;
-    let image: Option<Vec<u8>> = mods::get_logo(&path.into_inner(), &mut pool).await?;
 
-    Ok(match image {
-        Some(i) => HttpResponse::Ok().content_type("image/png").body(i),
-        None => HttpResponse::NotFound().body(""),
-    })
+    if !mods::exists(&id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(id.into_inner()));
+    }
+
+    let detailed = match auth.developer() {
+        Ok(dev) => dev.admin || developers::has_access_to_mod(dev.id, &id, &mut pool).await?,
+        _ => false,
+    };
+
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| to.checked_sub_days(Days::new(30)).unwrap_or(to));
+    if from > to {
+        return Err(ApiError::BadRequest("from must not be after to".into()));
+    }
+    if (to - from).num_days() >= MAX_STATS_RANGE_DAYS {
+        return Err(ApiError::BadRequest(format!(
+            "Range can't be longer than {MAX_STATS_RANGE_DAYS} days"
+        )));
+    }
+
+    let granularity = match query.granularity.unwrap_or_default() {
+        StatsGranularity::Day if !detailed => StatsGranularity::Week,
+        g => g,
+    };
+
+    let rows = mod_downloads::get_stats_for_mod(&id, from, to, granularity, &mut pool).await?;
+    let breakdowns = if detailed {
+        Some(mod_downloads::get_breakdowns_for_mod(&id, from, to, &mut pool).await?)
+    } else {
+        None
+    };
+
+    let mut buckets: BTreeMap<NaiveDate, BTreeMap<String, i64>> = BTreeMap::new();
+    let mut date = period_start(from, granularity);
+    while date <= to {
+        buckets.insert(date, BTreeMap::new());
+        date = next_period(date, granularity);
+    }
+    for row in rows {
+        buckets
+            .entry(row.date)
+            .or_default()
+            .insert(row.version, row.downloads);
+    }
+
+    let points: Vec<DownloadStatsPoint> = buckets
+        .into_iter()
+        .map(|(date, versions)| DownloadStatsPoint {
+            date,
+            downloads: versions.values().sum(),
+            versions: detailed.then_some(versions),
+        })
+        .collect();
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: ModDownloadStats {
+            mod_id: id.into_inner(),
+            from,
+            to,
+            granularity,
+            total: points.iter().map(|p| p.downloads).sum(),
+            points,
+            breakdowns,
+        },
+    }))
+}
+
+fn period_start(date: NaiveDate, granularity: StatsGranularity) -> NaiveDate {
+    match granularity {
+        StatsGranularity::Day => date,
+        StatsGranularity::Week => date
+            .checked_sub_days(Days::new(date.weekday().num_days_from_monday().into()))
+            .unwrap_or(date),
+        StatsGranularity::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+fn next_period(date: NaiveDate, granularity: StatsGranularity) -> NaiveDate {
+    match granularity {
+        StatsGranularity::Day => date.checked_add_days(Days::new(1)),
+        StatsGranularity::Week => date.checked_add_days(Days::new(7)),
+        StatsGranularity::Month => date.checked_add_months(Months::new(1)),
+    }
+    .unwrap_or(NaiveDate::MAX)
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct ChangelogQueryParams {
+    /// Version the user has now, not included in the result
+    from: Option<String>,
+    /// Newest version to include, defaults to the latest accepted version
+    to: Option<String>,
+    /// Defaults to markdown
+    format: Option<ContentFormat>,
+    /// Language of the changelogs, takes precedence over Accept-Language
+    lang: Option<String>,
+}
+
+/// Get the changelog entries between two versions of a mod
+#[utoipa::path(
+    get,
+    path = "/v1/mods/{id}/changelog",
+    tag = "mods",
+    params(
+        ("id" = String, Path, description = "Mod ID"),
+        ChangelogQueryParams
+    ),
+    responses(
+        (status = 200, description = "Changelog of each accepted version in the range, newest first", body = inline(ApiResponse<Vec<ChangelogEntry>>)),
+        (status = 400, description = "Invalid version"),
+        (status = 404, description = "Mod not found")
+    )
+)]
+#[get("/v1/mods/{id}/changelog")]
+pub async fn get_changelog(
+    data: web::Data<AppData>,
+    id: web::Path<String>,
+    query: web::Query<ChangelogQueryParams>,
+    accept_language: AcceptLanguage,
+) -> Result<impl Responder, ApiError> {
+    let parse = |v: &str| {
+        semver::Version::parse(v.trim_start_matches('v'))
+            .or(Err(ApiError::BadRequest(format!("Invalid version {v}"))))
+    };
+    let from = query.from.as_deref().map(parse).transpose()?;
+    let to = query.to.as_deref().map(parse).transpose()?;
+
+    let mut pool = data.db().acquire().await?;
+
+    if !mods::exists(&id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(id.into_inner()));
+    }
+
+    let mut changelogs: Vec<_> = mod_versions::get_changelogs(&id, &mut pool)
+        .await?
+        .into_iter()
+        .map(|c| (semver::Version::parse(c.version.trim_start_matches('v')).ok(), c))
+        .collect();
+    // Newest version first, versions that aren't valid semver go last
+    changelogs.sort_by(|a, b| b.0.cmp(&a.0));
+
+    // Versions accepted before changelogs were stored per version can still be
+    // found in the newest changelog
+    let newest = changelogs.iter().find_map(|(_, c)| c.changelog.as_deref());
+
+    // Translations only exist for the latest .geode, so each version's section is
+    // looked up in the translated changelog.md and falls back to the untranslated one
+    let translations: Vec<(String, String)> = mod_localizations::get_for_mod(&id, &mut pool)
+        .await?
+        .into_iter()
+        .filter_map(|l| Some((l.lang, l.changelog?)))
+        .collect();
+    let languages: Vec<String> = translations.iter().map(|(lang, _)| lang.clone()).collect();
+    let language = accept_language.best_match(query.lang.as_deref(), &languages);
+    let translated = translations
+        .iter()
+        .find(|(lang, _)| language.as_ref() == Some(lang))
+        .map(|(_, changelog)| changelog.as_str());
+
+    let mut any_translated = false;
+    let mut any_untranslated = false;
+
+    let entries: Vec<ChangelogEntry> = changelogs
+        .iter()
+        .filter(|(version, _)| {
+            let Some(version) = version else {
+                return from.is_none() && to.is_none();
+            };
+            from.as_ref().is_none_or(|from| version > from)
+                && to.as_ref().is_none_or(|to| version <= to)
+        })
+        .map(|(_, c)| {
+            let translated = translated.and_then(|t| markdown::changelog_section(t, &c.version));
+            let changelog = match translated {
+                Some(section) => {
+                    any_translated = true;
+                    Some(section)
+                }
+                None => {
+                    // A changelog without a section for its own version can't be told
+                    // apart from the rest of the file
+                    let section = c
+                        .changelog
+                        .as_deref()
+                        .and_then(|own| markdown::changelog_section(own, &c.version))
+                        .or_else(|| {
+                            newest.and_then(|n| markdown::changelog_section(n, &c.version))
+                        });
+                    any_untranslated |= section.is_some();
+                    section
+                }
+            };
+
+            ChangelogEntry {
+                version: c.version.clone(),
+                changelog: match query.format.unwrap_or_default() {
+                    ContentFormat::Markdown => changelog,
+                    ContentFormat::Html => changelog.as_deref().map(markdown::render_html),
+                },
+            }
+        })
+        .collect();
+
+    let mut response = HttpResponse::Ok();
+    response.append_header((header::VARY, "Accept-Language"));
+    if let Some(lang) = language
+        && any_translated
+        && !any_untranslated
+    {
+        response.append_header((header::CONTENT_LANGUAGE, lang));
+    }
+
+    Ok(response.json(ApiResponse {
+        error: "".into(),
+        payload: entries,
+    }))
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -432,7 +843,7 @@ pub async fn update_mod(
     let id = path.into_inner();
 
     if !mods::exists(&id, &mut tx).await? {
-        return Err(ApiError::NotFound("Mod not found".into()));
+        return Err(ApiError::ModNotFound(id.clone()));
     }
 
     let featured = mods::is_featured(&id, &mut tx).await?;
@@ -0,0 +1,356 @@
+use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
+use serde::Deserialize;
+use sqlx::Acquire;
+use utoipa::{IntoParams, ToSchema};
+
+use super::ApiError;
+use crate::config::AppData;
+use crate::database::repository::{developers, mods, organizations};
+use crate::extractors::auth::Auth;
+use crate::types::api::ApiResponse;
+use crate::types::models::organization::{Organization, OrganizationProfile, OrganizationRole};
+
+#[derive(Deserialize, IntoParams)]
+struct OrganizationPath {
+    id: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct OrganizationMemberPath {
+    id: i32,
+    username: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct ModOrganizationPath {
+    id: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct CreateOrganizationPayload {
+    name: String,
+    display_name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct SetMemberPayload {
+    role: OrganizationRole,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct SetModOrganizationPayload {
+    /// Pass null to detach the mod from its organization
+    organization_id: Option<i32>,
+}
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    if !name
+        .chars()
+        .all(|x| x.is_ascii_lowercase() || x.is_ascii_digit() || x == '-')
+    {
+        return Err(ApiError::BadRequest(
+            "Organization name must contain only lowercase ASCII letters, digits and dashes".into(),
+        ));
+    }
+
+    if name.len() < 2 || name.len() > 64 {
+        return Err(ApiError::BadRequest(
+            "Organization name must be between 2 and 64 characters".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_display_name(display_name: &str) -> Result<(), ApiError> {
+    if display_name.chars().any(|x| x.is_control()) {
+        return Err(ApiError::BadRequest(
+            "Display name must not contain control characters".into(),
+        ));
+    }
+
+    let len = display_name.trim().chars().count();
+    if !(2..=64).contains(&len) {
+        return Err(ApiError::BadRequest(
+            "Display name must be between 2 and 64 characters".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Create an organization owned by the current developer
+#[utoipa::path(
+    post,
+    path = "/v1/organizations",
+    tag = "organizations",
+    request_body = CreateOrganizationPayload,
+    responses(
+        (status = 201, description = "Organization created", body = inline(ApiResponse<Organization>)),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Name already taken")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[post("v1/organizations")]
+pub async fn create(
+    data: web::Data<AppData>,
+    json: web::Json<CreateOrganizationPayload>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+
+    validate_name(&json.name)?;
+    validate_display_name(&json.display_name)?;
+
+    let mut pool = data.db().acquire().await?;
+
+    if organizations::get_one_by_name(&json.name, &mut pool)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(format!(
+            "Organization name {} is already taken",
+            json.name
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+    let organization =
+        organizations::create(&json.name, json.display_name.trim(), dev.id, &mut tx).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        error: "".into(),
+        payload: organization,
+    }))
+}
+
+/// Get an organization and its members
+#[utoipa::path(
+    get,
+    path = "/v1/organizations/{id}",
+    tag = "organizations",
+    params(OrganizationPath),
+    responses(
+        (status = 200, description = "Organization details", body = inline(ApiResponse<OrganizationProfile>)),
+        (status = 404, description = "Organization not found")
+    )
+)]
+#[get("v1/organizations/{id}")]
+pub async fn get_one(
+    data: web::Data<AppData>,
+    path: web::Path<OrganizationPath>,
+) -> Result<impl Responder, ApiError> {
+    let mut pool = data.db().acquire().await?;
+
+    let organization = organizations::get_one(path.id, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound("Organization not found".into()))?;
+    let members = organizations::get_members(path.id, &mut pool).await?;
+
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: OrganizationProfile {
+            organization,
+            members,
+        },
+    }))
+}
+
+/// Add a member to an organization or change their role
+#[utoipa::path(
+    put,
+    path = "/v1/organizations/{id}/members/{username}",
+    tag = "organizations",
+    params(OrganizationMemberPath),
+    request_body = SetMemberPayload,
+    responses(
+        (status = 204, description = "Member updated"),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Organization or developer not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[put("v1/organizations/{id}/members/{username}")]
+pub async fn set_member(
+    data: web::Data<AppData>,
+    path: web::Path<OrganizationMemberPath>,
+    json: web::Json<SetMemberPayload>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    if organizations::get_one(path.id, &mut pool).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".into()));
+    }
+
+    let own_role = organizations::get_role(path.id, dev.id, &mut pool).await?;
+
+    let target = developers::get_one_by_username(&path.username, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound(format!(
+            "No developer found with username {}",
+            path.username
+        )))?;
+
+    let target_role = organizations::get_role(path.id, target.id, &mut pool).await?;
+
+    // Admins manage regular members, only owners can hand out or take away admin rights
+    let required = if json.role > OrganizationRole::Member
+        || target_role.is_some_and(|r| r > OrganizationRole::Member)
+    {
+        OrganizationRole::Owner
+    } else {
+        OrganizationRole::Admin
+    };
+
+    if !dev.admin && own_role.is_none_or(|r| r < required) {
+        return Err(ApiError::Authorization);
+    }
+
+    let mut tx = pool.begin().await?;
+
+    organizations::set_member(path.id, target.id, json.role, &mut tx).await?;
+
+    if organizations::count_owners(path.id, &mut tx).await? == 0 {
+        return Err(ApiError::BadRequest(
+            "An organization must keep at least one owner".into(),
+        ));
+    }
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Remove a member from an organization
+#[utoipa::path(
+    delete,
+    path = "/v1/organizations/{id}/members/{username}",
+    tag = "organizations",
+    params(OrganizationMemberPath),
+    responses(
+        (status = 204, description = "Member removed"),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Organization or member not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[delete("v1/organizations/{id}/members/{username}")]
+pub async fn remove_member(
+    data: web::Data<AppData>,
+    path: web::Path<OrganizationMemberPath>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    let target = developers::get_one_by_username(&path.username, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound(format!(
+            "No developer found with username {}",
+            path.username
+        )))?;
+
+    let target_role = organizations::get_role(path.id, target.id, &mut pool)
+        .await?
+        .ok_or(ApiError::NotFound(format!(
+            "{} is not a member of this organization",
+            target.username
+        )))?;
+
+    // Members can always leave on their own
+    if target.id != dev.id && !dev.admin {
+        let required = if target_role > OrganizationRole::Member {
+            OrganizationRole::Owner
+        } else {
+            OrganizationRole::Admin
+        };
+
+        let own_role = organizations::get_role(path.id, dev.id, &mut pool).await?;
+        if own_role.is_none_or(|r| r < required) {
+            return Err(ApiError::Authorization);
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+
+    organizations::remove_member(path.id, target.id, &mut tx).await?;
+
+    if organizations::count_owners(path.id, &mut tx).await? == 0 {
+        return Err(ApiError::BadRequest(
+            "An organization must keep at least one owner".into(),
+        ));
+    }
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Attach a mod to an organization, or detach it
+#[utoipa::path(
+    put,
+    path = "/v1/mods/{id}/organization",
+    tag = "organizations",
+    params(ModOrganizationPath),
+    request_body = SetModOrganizationPayload,
+    responses(
+        (status = 204, description = "Mod organization updated"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Mod or organization not found")
+    ),
+    security(
+        ("bearer_token" = [])
+    )
+)]
+#[put("v1/mods/{id}/organization")]
+pub async fn set_mod_organization(
+    data: web::Data<AppData>,
+    path: web::Path<ModOrganizationPath>,
+    json: web::Json<SetModOrganizationPayload>,
+    auth: Auth,
+) -> Result<impl Responder, ApiError> {
+    let dev = auth.developer()?;
+    let mut pool = data.db().acquire().await?;
+
+    if !mods::exists(&path.id, &mut pool).await? {
+        return Err(ApiError::ModNotFound(path.id.clone()));
+    }
+
+    if !dev.admin && !developers::owns_mod(dev.id, &path.id, &mut pool).await? {
+        return Err(ApiError::Authorization);
+    }
+
+    if let Some(organization_id) = json.organization_id {
+        if organizations::get_one(organization_id, &mut pool)
+            .await?
+            .is_none()
+        {
+            return Err(ApiError::NotFound("Organization not found".into()));
+        }
+
+        let role = organizations::get_role(organization_id, dev.id, &mut pool).await?;
+        if !dev.admin && role.is_none_or(|r| r < OrganizationRole::Admin) {
+            return Err(ApiError::Authorization);
+        }
+    }
+
+    mods::set_organization(&path.id, json.organization_id, &mut pool).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
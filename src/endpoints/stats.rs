@@ -1,8 +1,26 @@
 use actix_web::{get, web, Responder};
+use chrono::{Days, NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::IntoParams;
 
 use super::ApiError;
 use crate::config::AppData;
-use crate::types::{api::ApiResponse, models::stats::Stats};
+use crate::database::repository::stats_snapshots;
+use crate::types::{
+    api::ApiResponse,
+    models::stats::{Stats, StatsSnapshot},
+};
+
+#[derive(Deserialize, IntoParams)]
+struct HistoryQuery {
+    /// First day to include, defaults to 90 days before `to`
+    from: Option<NaiveDate>,
+    /// Last day to include, defaults to today (UTC)
+    to: Option<NaiveDate>,
+}
+
+/// Longest range that can be requested at once
+const MAX_HISTORY_DAYS: i64 = 366 * 2;
 
 /// Get global index statistics
 #[utoipa::path(
@@ -18,6 +36,42 @@ pub async fn get_stats(data: web::Data<AppData>) -> Result<impl Responder, ApiEr
     let mut pool = data.db().acquire().await?;
     Ok(web::Json(ApiResponse {
         error: "".into(),
-        payload: Stats::get_cached(&mut pool).await?,
+        payload: Stats::get_cached(data.github(), &mut pool).await?,
+    }))
+}
+
+/// Get daily snapshots of the global index statistics
+#[utoipa::path(
+    get,
+    path = "/v1/stats/history",
+    tag = "stats",
+    params(HistoryQuery),
+    responses(
+        (status = 200, description = "One snapshot per day, days without one are left out", body = inline(ApiResponse<Vec<StatsSnapshot>>)),
+        (status = 400, description = "Invalid date range")
+    )
+)]
+#[get("/v1/stats/history")]
+pub async fn get_history(
+    data: web::Data<AppData>,
+    query: web::Query<HistoryQuery>,
+) -> Result<impl Responder, ApiError> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| to.checked_sub_days(Days::new(90)).unwrap_or(to));
+    if from > to {
+        return Err(ApiError::BadRequest("from must not be after to".into()));
+    }
+    if (to - from).num_days() >= MAX_HISTORY_DAYS {
+        return Err(ApiError::BadRequest(format!(
+            "Range can't be longer than {MAX_HISTORY_DAYS} days"
+        )));
+    }
+
+    let mut pool = data.db().acquire().await?;
+    Ok(web::Json(ApiResponse {
+        error: "".into(),
+        payload: stats_snapshots::get_range(from, to, &mut pool).await?,
     }))
 }
@@ -0,0 +1,85 @@
+use actix_web::http::header;
+use actix_web::{FromRequest, HttpRequest};
+use futures::future::{Ready, ready};
+
+use crate::endpoints::ApiError;
+
+/// Most languages a client can list, anything after is ignored
+const MAX_LANGUAGES: usize = 16;
+
+/// Languages from the Accept-Language header, most preferred first.
+/// Wildcards and languages with `q=0` are left out.
+pub struct AcceptLanguage(pub Vec<String>);
+
+impl AcceptLanguage {
+    /// Picks the best of `available` for the client, with `lang` taking precedence over
+    /// the header. Tags match case-insensitively, and on their primary language when no
+    /// exact match exists (`pt-BR` gets `pt`, `es` gets `es-419`).
+    ///
+    /// about.md and changelog.md are written in English, so a client preferring English
+    /// gets `None` unless the mod has an English variant of its own.
+    pub fn best_match(&self, lang: Option<&str>, available: &[String]) -> Option<String> {
+        let wanted = lang.into_iter().chain(self.0.iter().map(String::as_str));
+
+        for tag in wanted {
+            if let Some(exact) = available.iter().find(|a| a.eq_ignore_ascii_case(tag)) {
+                return Some(exact.clone());
+            }
+
+            let primary = primary_language(tag);
+            if let Some(similar) = available
+                .iter()
+                .find(|a| primary_language(a).eq_ignore_ascii_case(primary))
+            {
+                return Some(similar.clone());
+            }
+
+            if primary.eq_ignore_ascii_case("en") {
+                return None;
+            }
+        }
+
+        None
+    }
+}
+
+impl FromRequest for AcceptLanguage {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        let Some(value) = req
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|h| h.to_str().ok())
+        else {
+            return ready(Ok(AcceptLanguage(vec![])));
+        };
+
+        let mut languages: Vec<(String, f32)> = value
+            .split(',')
+            .take(MAX_LANGUAGES)
+            .filter_map(|part| {
+                let mut params = part.split(';');
+                let tag = params.next()?.trim();
+                let quality = params
+                    .filter_map(|p| p.trim().strip_prefix("q="))
+                    .find_map(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                (!tag.is_empty() && tag != "*" && quality > 0.0).then(|| (tag.into(), quality))
+            })
+            .collect();
+
+        // Stable, so equally preferred languages keep the client's order
+        languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ready(Ok(AcceptLanguage(
+            languages.into_iter().map(|(tag, _)| tag).collect(),
+        )))
+    }
+}
+
+fn primary_language(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
@@ -2,7 +2,7 @@ use std::pin::Pin;
 
 use crate::auth::AuthenticationError;
 use crate::config::AppData;
-use crate::database::repository::developers;
+use crate::database::repository::{auth_tokens, developers};
 use crate::endpoints::ApiError;
 use crate::types::models::developer::Developer;
 use actix_web::http::header::HeaderMap;
@@ -78,10 +78,14 @@ impl FromRequest for Auth {
                     developer: None,
                     token: Some(token),
                 }),
-                Some(dev) => Ok(Auth {
-                    developer: Some(dev),
-                    token: Some(token),
-                }),
+                Some(dev) => {
+                    // Failing to record the last use shouldn't fail the request
+                    let _ = auth_tokens::touch(token, &mut pool).await;
+                    Ok(Auth {
+                        developer: Some(dev),
+                        token: Some(token),
+                    })
+                }
             }
         })
     }
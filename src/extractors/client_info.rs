@@ -0,0 +1,35 @@
+use crate::endpoints::ApiError;
+use actix_web::http::header;
+use actix_web::{FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use sqlx::types::ipnetwork::IpNetwork;
+
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Best-effort information about the client making a request.
+/// Neither field is guaranteed to be present.
+pub struct ClientInfo {
+    pub ip: Option<IpNetwork>,
+    pub user_agent: Option<String>,
+}
+
+impl FromRequest for ClientInfo {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        let ip = req
+            .connection_info()
+            .realip_remote_addr()
+            .and_then(|ip| ip.parse::<IpNetwork>().ok());
+
+        let user_agent = req
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|h| h.to_str().ok())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+        ready(Ok(ClientInfo { ip, user_agent }))
+    }
+}
@@ -1 +1,3 @@
+pub mod accept_language;
 pub mod auth;
+pub mod client_info;
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+
+use crate::markdown;
+use crate::xml::escape_xml;
+
+/// Longest changelog excerpt put in a feed entry, in characters
+const MAX_EXCERPT_LENGTH: usize = 1000;
+
+pub struct AtomFeed {
+    pub id: String,
+    pub title: String,
+    pub self_url: String,
+    pub alternate_url: String,
+    pub logo: Option<String>,
+    pub entries: Vec<AtomEntry>,
+}
+
+pub struct AtomEntry {
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    pub updated: DateTime<Utc>,
+    pub author: Option<AtomAuthor>,
+    pub summary: Option<String>,
+    /// Plain text, changelogs are shown as the markdown they were written in
+    pub content: Option<String>,
+    pub thumbnail: Option<String>,
+}
+
+pub struct AtomAuthor {
+    pub name: String,
+    pub uri: String,
+}
+
+impl AtomFeed {
+    pub fn render(&self) -> String {
+        // Feeds need an updated time even when they're empty
+        let updated = self
+            .entries
+            .iter()
+            .map(|e| e.updated)
+            .max()
+            .unwrap_or_else(Utc::now);
+
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+        xml += r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:media="http://search.yahoo.com/mrss/">"#;
+        xml += &format!("<id>{}</id>", escape_xml(&self.id));
+        xml += &format!("<title>{}</title>", escape_xml(&self.title));
+        xml += &format!("<updated>{}</updated>", updated.to_rfc3339());
+        xml += &format!(
+            r#"<link rel="self" type="application/atom+xml" href="{}"/>"#,
+            escape_xml(&self.self_url)
+        );
+        xml += &format!(
+            r#"<link rel="alternate" type="text/html" href="{}"/>"#,
+            escape_xml(&self.alternate_url)
+        );
+        if let Some(logo) = &self.logo {
+            xml += &format!("<logo>{}</logo>", escape_xml(logo));
+        }
+        // Atom wants an author for every entry, this covers mods without an owner
+        xml += "<author><name>Geode SDK</name></author>";
+
+        for entry in &self.entries {
+            xml += &entry.render();
+        }
+
+        xml += "</feed>";
+        xml
+    }
+}
+
+impl AtomEntry {
+    fn render(&self) -> String {
+        let mut xml = String::from("<entry>");
+        xml += &format!("<id>{}</id>", escape_xml(&self.id));
+        xml += &format!("<title>{}</title>", escape_xml(&self.title));
+        xml += &format!("<updated>{}</updated>", self.updated.to_rfc3339());
+        xml += &format!(
+            r#"<link rel="alternate" type="text/html" href="{}"/>"#,
+            escape_xml(&self.link)
+        );
+        if let Some(author) = &self.author {
+            xml += &format!(
+                "<author><name>{}</name><uri>{}</uri></author>",
+                escape_xml(&author.name),
+                escape_xml(&author.uri)
+            );
+        }
+        if let Some(summary) = &self.summary {
+            xml += &format!("<summary>{}</summary>", escape_xml(summary));
+        }
+        if let Some(content) = &self.content {
+            xml += &format!(r#"<content type="text">{}</content>"#, escape_xml(content));
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            xml += &format!(r#"<media:thumbnail url="{}"/>"#, escape_xml(thumbnail));
+        }
+        xml += "</entry>";
+        xml
+    }
+}
+
+/// The version's section of a changelog.md, cut down to fit in a feed entry
+pub fn changelog_excerpt(changelog: &str, version: &str) -> Option<String> {
+    let text = markdown::changelog_section(changelog, version)?;
+
+    if text.chars().count() > MAX_EXCERPT_LENGTH {
+        let cut: String = text.chars().take(MAX_EXCERPT_LENGTH).collect();
+        Some(format!("{}…", cut.trim_end()))
+    } else {
+        Some(text)
+    }
+}
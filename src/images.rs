@@ -0,0 +1,165 @@
+use std::io::Cursor;
+
+use image::codecs::png::{PngDecoder, PngEncoder};
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, GenericImageView, ImageEncoder};
+
+use crate::types::models::mod_logo::LogoFormat;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ImageError {
+    #[error("Image must be a valid PNG: {0}")]
+    Decode(image::ImageError),
+    #[error("Image is too large, max size is {0}x{0}")]
+    TooLarge(u32),
+    #[error("Failed to encode image")]
+    Encode(image::ImageError),
+}
+
+const AVATAR_SIZE: u32 = 256;
+const SCREENSHOT_MAX_WIDTH: u32 = 1920;
+const SCREENSHOT_MAX_HEIGHT: u32 = 1080;
+const MAX_SOURCE_SIZE: u32 = 4096;
+
+/// Sizes logos are rendered at, the largest one matches what `extract_mod_logo` keeps
+pub const LOGO_SIZES: [u32; 3] = [64, 128, 336];
+
+pub struct LogoRendition {
+    pub size: u32,
+    pub format: LogoFormat,
+    pub data: Vec<u8>,
+}
+
+/// Decodes a PNG avatar, crops it to a centered square and scales it down to 256x256.
+/// The result is always re-encoded, so no metadata from the upload is kept.
+pub fn process_avatar(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let img = PngDecoder::new(Cursor::new(data))
+        .and_then(DynamicImage::from_decoder)
+        .map_err(ImageError::Decode)?;
+
+    let (width, height) = img.dimensions();
+    if width > MAX_SOURCE_SIZE || height > MAX_SOURCE_SIZE {
+        return Err(ImageError::TooLarge(MAX_SOURCE_SIZE));
+    }
+
+    let side = width.min(height);
+    let mut img = img.crop_imm((width - side) / 2, (height - side) / 2, side, side);
+
+    if side > AVATAR_SIZE {
+        img = img.resize(
+            AVATAR_SIZE,
+            AVATAR_SIZE,
+            image::imageops::FilterType::Lanczos3,
+        );
+    }
+
+    encode_png(img)
+}
+
+/// Decodes a PNG screenshot and scales it down to fit in 1920x1080, keeping
+/// its aspect ratio. Like avatars, the result is always re-encoded.
+pub fn process_screenshot(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let mut img = PngDecoder::new(Cursor::new(data))
+        .and_then(DynamicImage::from_decoder)
+        .map_err(ImageError::Decode)?;
+
+    let (width, height) = img.dimensions();
+    if width > MAX_SOURCE_SIZE || height > MAX_SOURCE_SIZE {
+        return Err(ImageError::TooLarge(MAX_SOURCE_SIZE));
+    }
+
+    if width > SCREENSHOT_MAX_WIDTH || height > SCREENSHOT_MAX_HEIGHT {
+        img = img.resize(
+            SCREENSHOT_MAX_WIDTH,
+            SCREENSHOT_MAX_HEIGHT,
+            image::imageops::FilterType::Lanczos3,
+        );
+    }
+
+    encode_png(img)
+}
+
+/// Renders a stored logo at every size in [`LOGO_SIZES`], in every [`LogoFormat`].
+/// Logos are never scaled up, so small logos keep their own size.
+pub fn render_logo_renditions(data: &[u8]) -> Result<Vec<LogoRendition>, ImageError> {
+    let img = decode_png(data)?;
+
+    let mut renditions = Vec::with_capacity(LOGO_SIZES.len() * LogoFormat::ALL.len());
+    for size in LOGO_SIZES {
+        let resized = resize_logo(&img, size);
+        for format in LogoFormat::ALL {
+            renditions.push(LogoRendition {
+                size,
+                format,
+                data: encode(resized.clone(), format)?,
+            });
+        }
+    }
+
+    Ok(renditions)
+}
+
+/// Renders a single logo rendition, for logos that haven't been backfilled yet
+pub fn render_logo(data: &[u8], size: u32, format: LogoFormat) -> Result<Vec<u8>, ImageError> {
+    let img = decode_png(data)?;
+    encode(resize_logo(&img, size), format)
+}
+
+fn decode_png(data: &[u8]) -> Result<DynamicImage, ImageError> {
+    PngDecoder::new(Cursor::new(data))
+        .and_then(DynamicImage::from_decoder)
+        .map_err(ImageError::Decode)
+}
+
+fn resize_logo(img: &DynamicImage, size: u32) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    if width > size || height > size {
+        img.resize(size, size, image::imageops::FilterType::Lanczos3)
+    } else {
+        img.clone()
+    }
+}
+
+fn encode(img: DynamicImage, format: LogoFormat) -> Result<Vec<u8>, ImageError> {
+    match format {
+        LogoFormat::Png => encode_png(img),
+        LogoFormat::Webp => encode_webp(img),
+    }
+}
+
+/// The WebP encoder in `image` is lossless only, which is fine at logo sizes
+fn encode_webp(img: DynamicImage) -> Result<Vec<u8>, ImageError> {
+    let img = img.to_rgba8();
+    let mut out: Vec<u8> = vec![];
+
+    WebPEncoder::new_lossless(&mut out)
+        .write_image(
+            img.as_raw(),
+            img.width(),
+            img.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .map_err(ImageError::Encode)?;
+
+    Ok(out)
+}
+
+fn encode_png(img: DynamicImage) -> Result<Vec<u8>, ImageError> {
+    let img = img.to_rgba8();
+    let mut out: Vec<u8> = vec![];
+
+    PngEncoder::new_with_quality(
+        &mut out,
+        image::codecs::png::CompressionType::Best,
+        image::codecs::png::FilterType::Adaptive,
+    )
+    .write_image(
+        img.as_raw(),
+        img.width(),
+        img.height(),
+        image::ExtendedColorType::Rgba8,
+    )
+    .map_err(ImageError::Encode)?;
+
+    Ok(out)
+}
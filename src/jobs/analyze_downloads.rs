@@ -0,0 +1,25 @@
+use crate::database::repository::download_flags;
+use crate::endpoints::ApiError;
+use sqlx::{Acquire, PgConnection};
+
+/// Downloads of one version from a single subnet on one day before they're flagged
+const SUBNET_THRESHOLD: i64 = 20;
+/// Downloads in the first hour after publishing before they're flagged, if the
+/// mod's other versions don't usually get as many
+const BURST_THRESHOLD: i64 = 100;
+
+pub async fn analyze_downloads(conn: &mut PgConnection) -> Result<(), ApiError> {
+    let mut tx = conn.begin().await?;
+
+    let result = download_flags::analyze(SUBNET_THRESHOLD, BURST_THRESHOLD, &mut tx).await?;
+    log::info!(
+        "Flagged downloads: {} from crowded subnets, {} in publish bursts, {} without user agent",
+        result.subnet,
+        result.publish_burst,
+        result.no_user_agent
+    );
+
+    tx.commit().await?;
+
+    Ok(())
+}
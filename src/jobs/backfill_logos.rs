@@ -0,0 +1,35 @@
+use crate::database::repository::{mod_logo_renditions, mods};
+use crate::endpoints::ApiError;
+use crate::endpoints::mods::store_logo_renditions;
+use crate::storage::PublicStorage;
+use sqlx::{Acquire, PgConnection};
+
+pub async fn backfill_logos(
+    storage: &PublicStorage,
+    all: bool,
+    conn: &mut PgConnection,
+) -> Result<(), ApiError> {
+    let ids = mod_logo_renditions::get_mods_with_logo(!all, conn).await?;
+    log::info!("Rendering logos for {} mods", ids.len());
+
+    let mut rendered = 0;
+    for id in ids {
+        let Some(logo) = mods::get_logo(&id, conn).await? else {
+            continue;
+        };
+
+        // One transaction per mod, so a broken logo doesn't undo the rest
+        let mut tx = conn.begin().await?;
+        match store_logo_renditions(storage, &id, &logo, &mut tx).await {
+            Ok(()) => {
+                tx.commit().await?;
+                rendered += 1;
+            }
+            Err(e) => log::error!("Failed to backfill logo for {id}: {e}"),
+        }
+    }
+
+    log::info!("Rendered logos for {rendered} mods");
+
+    Ok(())
+}
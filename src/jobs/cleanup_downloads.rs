@@ -1,9 +1,16 @@
 use crate::database::repository::mod_downloads;
 use crate::endpoints::ApiError;
-use sqlx::PgConnection;
+use sqlx::{Acquire, PgConnection};
 
-pub async fn cleanup_downloads(conn: &mut PgConnection) -> Result<(), ApiError> {
-    mod_downloads::cleanup(conn).await?;
+pub async fn cleanup_downloads(window_days: u32, conn: &mut PgConnection) -> Result<(), ApiError> {
+    let mut tx = conn.begin().await?;
+
+    let rolled_up = mod_downloads::rollup(window_days, &mut tx).await?;
+    let breakdowns = mod_downloads::rollup_breakdowns(window_days, &mut tx).await?;
+    log::info!("Rolled up {rolled_up} daily download counts, {breakdowns} breakdown counts");
+    mod_downloads::cleanup(window_days, &mut tx).await?;
+
+    tx.commit().await?;
 
     Ok(())
 }
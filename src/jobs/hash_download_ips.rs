@@ -0,0 +1,27 @@
+use crate::database::repository::mod_downloads;
+use crate::endpoints::ApiError;
+use sqlx::{Acquire, PgConnection};
+
+/// Hashes the IPs of downloads from before they were hashed, with the key of
+/// the day they were made on, so they keep deduplicating downloads. Needs
+/// DOWNLOAD_HASH_SECRET, which is why the migration can't do it.
+pub async fn hash_download_ips(secret: &[u8], conn: &mut PgConnection) -> Result<(), ApiError> {
+    let mut tx = conn.begin().await?;
+
+    let Some(unhashed) = mod_downloads::get_unhashed(&mut tx).await? else {
+        log::info!("Download IPs are already hashed");
+        return Ok(());
+    };
+
+    let hashes: Vec<String> = unhashed
+        .iter()
+        .map(|d| mod_downloads::hash_ip_on(d.ip, secret, d.day))
+        .collect();
+    mod_downloads::set_hashes(&unhashed, &hashes, &mut tx).await?;
+    mod_downloads::drop_ips(&mut tx).await?;
+
+    tx.commit().await?;
+    log::info!("Hashed the IPs of {} downloads", hashes.len());
+
+    Ok(())
+}
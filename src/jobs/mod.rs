@@ -1,4 +1,11 @@
+pub mod analyze_downloads;
+pub mod backfill_logos;
 pub mod cleanup_downloads;
+pub mod hash_download_ips;
 pub mod logout_user;
 pub mod migrate;
+pub mod refresh_trending;
+pub mod render_markdown;
+pub mod snapshot_stats;
+pub mod sync_loader_releases;
 pub mod token_cleanup;
@@ -0,0 +1,14 @@
+use crate::database::repository::mod_trending_scores;
+use crate::endpoints::ApiError;
+use sqlx::{Acquire, PgConnection};
+
+pub async fn refresh_trending(conn: &mut PgConnection) -> Result<(), ApiError> {
+    let mut tx = conn.begin().await?;
+
+    let count = mod_trending_scores::refresh(&mut tx).await?;
+    log::info!("Refreshed trending scores for {count} mods");
+
+    tx.commit().await?;
+
+    Ok(())
+}
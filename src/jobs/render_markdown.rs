@@ -0,0 +1,46 @@
+use crate::database::repository::{mod_localizations, mods};
+use crate::endpoints::ApiError;
+use crate::markdown;
+use sqlx::{Acquire, PgConnection};
+
+pub async fn render_markdown(all: bool, conn: &mut PgConnection) -> Result<(), ApiError> {
+    let mut tx = conn.begin().await?;
+
+    let to_render = mods::get_markdown_to_render(all, &mut tx).await?;
+    let count = to_render.len();
+
+    for m in to_render {
+        let about_html = m.about.as_deref().map(markdown::render_html);
+        let changelog_html = m.changelog.as_deref().map(markdown::render_html);
+        mods::update_rendered_markdown(
+            &m.id,
+            about_html.as_deref(),
+            changelog_html.as_deref(),
+            &mut tx,
+        )
+        .await?;
+    }
+
+    let translations = mod_localizations::get_to_render(all, &mut tx).await?;
+    let translation_count = translations.len();
+
+    for t in translations {
+        let about_html = t.about.as_deref().map(markdown::render_html);
+        let changelog_html = t.changelog.as_deref().map(markdown::render_html);
+        mod_localizations::update_rendered(
+            &t.mod_id,
+            &t.lang,
+            about_html.as_deref(),
+            changelog_html.as_deref(),
+            &mut tx,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    log::info!(
+        "Rendered about.md and changelog.md for {count} mods and {translation_count} translations"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use chrono::Utc;
+use sqlx::PgConnection;
+
+use crate::config::GitHubClientData;
+use crate::database::repository::{mod_versions, stats_snapshots};
+use crate::endpoints::ApiError;
+use crate::types::models::stats::{Stats, StatsSnapshot};
+
+pub async fn snapshot_stats(
+    github: &GitHubClientData,
+    conn: &mut PgConnection,
+) -> Result<(), ApiError> {
+    let stats = Stats::get_cached(github, conn).await?;
+    let snapshot = StatsSnapshot {
+        date: Utc::now().date_naive(),
+        total_mod_count: stats.total_mod_count,
+        total_mod_downloads: stats.total_mod_downloads,
+        total_registered_developers: stats.total_registered_developers,
+        pending_mod_versions: mod_versions::count_pending(conn).await?,
+        total_geode_downloads: stats.total_geode_downloads,
+    };
+
+    stats_snapshots::upsert(&snapshot, conn).await?;
+    log::info!("Saved stats snapshot for {}", snapshot.date);
+
+    Ok(())
+}
@@ -0,0 +1,530 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, header::HeaderValue};
+use serde::Deserialize;
+use sqlx::PgConnection;
+
+use crate::config::GitHubClientData;
+use crate::database::repository::gd_versions;
+use crate::endpoints::ApiError;
+use crate::types::models::loader_version::{
+    LoaderAsset, LoaderChannel, LoaderVersion, LoaderVersionCreate, LoaderVersionQueries,
+};
+use crate::types::models::mod_gd_version::{GDVersion, VerPlatform};
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    /// Branch or commit the tag was created from, if GitHub created the tag
+    target_commitish: String,
+    prerelease: bool,
+    draft: bool,
+    body: Option<String>,
+    published_at: Option<DateTime<Utc>>,
+    assets: Vec<GithubReleaseAsset>,
+}
+
+#[derive(Deserialize)]
+struct GithubReleaseAsset {
+    name: String,
+    browser_download_url: String,
+    size: i64,
+    /// `sha256:<hex>`, only set for assets uploaded after GitHub started computing them
+    digest: Option<String>,
+}
+
+impl GithubRelease {
+    fn channel(&self) -> LoaderChannel {
+        if self.prerelease {
+            LoaderChannel::Beta
+        } else {
+            LoaderChannel::Stable
+        }
+    }
+
+    /// Maps release assets to platforms by their exact file name, e.g.
+    /// `geode-v4.3.1-android64.zip`, so installers and other extras are skipped
+    fn loader_assets(&self) -> Vec<LoaderAsset> {
+        let tag = self.tag_name.to_lowercase();
+        let mut ret = vec![];
+        for asset in &self.assets {
+            let Some(sha256) = asset
+                .digest
+                .as_deref()
+                .and_then(|d| d.strip_prefix("sha256:"))
+            else {
+                continue;
+            };
+            let name = asset.name.to_lowercase();
+            let platforms: &[VerPlatform] = match name
+                .strip_prefix(&format!("geode-{tag}-"))
+                .and_then(|n| n.strip_suffix(".zip"))
+            {
+                Some("win") => &[VerPlatform::Win],
+                Some("mac") => &[VerPlatform::MacArm, VerPlatform::MacIntel],
+                Some("android32") => &[VerPlatform::Android32],
+                Some("android64") => &[VerPlatform::Android64],
+                Some("ios") => &[VerPlatform::Ios],
+                _ => &[],
+            };
+
+            for platform in platforms {
+                if ret.iter().any(|a: &LoaderAsset| a.platform == *platform) {
+                    continue;
+                }
+                ret.push(LoaderAsset {
+                    platform: *platform,
+                    url: asset.browser_download_url.clone(),
+                    size: asset.size,
+                    sha256: sha256.to_lowercase(),
+                });
+            }
+        }
+        ret
+    }
+}
+
+#[derive(Deserialize)]
+struct GithubTag {
+    name: String,
+    commit: GithubTagCommit,
+}
+
+#[derive(Deserialize)]
+struct GithubTagCommit {
+    sha: String,
+}
+
+#[derive(Default, Debug)]
+struct GDTargets {
+    win: Option<GDVersion>,
+    mac: Option<GDVersion>,
+    android: Option<GDVersion>,
+    ios: Option<GDVersion>,
+}
+
+impl GDTargets {
+    fn is_empty(&self) -> bool {
+        self.win.is_none() && self.mac.is_none() && self.android.is_none() && self.ios.is_none()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&'static str, &Option<GDVersion>)> {
+        [
+            ("win", &self.win),
+            ("mac", &self.mac),
+            ("android", &self.android),
+            ("ios", &self.ios),
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Default)]
+pub struct SyncReport {
+    /// Tags of the loader versions that were added
+    pub created: Vec<String>,
+    /// Differences between GitHub and the index that weren't fixed automatically
+    pub drift: Vec<String>,
+}
+
+/// Adds loader versions for releases of the loader repository that the index
+/// doesn't know about yet, and reports releases that don't match what's stored.
+pub async fn sync_loader_releases(
+    github: &GitHubClientData,
+    dry_run: bool,
+    conn: &mut PgConnection,
+) -> Result<SyncReport, ApiError> {
+    let client = Client::new();
+    let releases: Vec<GithubRelease> = get_all_pages(
+        &client,
+        &format!("{}/releases", github.loader_repository_url()),
+    )
+    .await?;
+    // commits of every tag, fetched once instead of per release
+    let mut tag_commits: Option<HashMap<String, String>> = None;
+
+    let existing: HashMap<String, _> = LoaderVersion::get_all(conn)
+        .await?
+        .into_iter()
+        .map(|v| (v.tag.clone(), v))
+        .collect();
+
+    let mut report = SyncReport::default();
+    let mut seen = HashSet::new();
+
+    for release in releases
+        .iter()
+        .filter(|r| !r.draft && r.tag_name != "nightly")
+    {
+        let tag = release.tag_name.trim_start_matches('v').to_string();
+        let targets = parse_gd_targets(release.body.as_deref().unwrap_or(""), &release.assets);
+        seen.insert(tag.clone());
+
+        if let Some(version) = existing.get(&tag) {
+            if version.channel != release.channel() {
+                report.drift.push(format!(
+                    "{tag}: channel is {:?} on GitHub but {:?} in the index",
+                    release.channel(),
+                    version.channel
+                ));
+            }
+            if version.yanked {
+                report.drift.push(format!(
+                    "{tag}: yanked in the index but still released on GitHub"
+                ));
+            }
+            let stored = [
+                ("win", &version.win),
+                ("mac", &version.mac),
+                ("android", &version.android),
+                ("ios", &version.ios),
+            ];
+            for ((platform, target), (_, stored)) in targets.iter().zip(stored) {
+                if let Some(target) = target
+                    && Some(target) != stored.as_ref()
+                {
+                    report.drift.push(format!(
+                        "{tag}: targets GD {target} on {platform} on GitHub but {} in the index",
+                        stored.as_ref().map_or("nothing".into(), |x| x.to_string())
+                    ));
+                }
+            }
+            continue;
+        }
+
+        if targets.is_empty() {
+            report.drift.push(format!(
+                "{tag}: release notes don't declare any GD versions, skipping"
+            ));
+            continue;
+        }
+
+        let gd: Vec<GDVersion> = targets.iter().filter_map(|(_, x)| x.clone()).collect();
+        let unknown = gd_versions::get_unknown(&gd, conn).await?;
+        if !unknown.is_empty() {
+            report.drift.push(format!(
+                "{tag}: targets unknown GD version(s) {}, skipping",
+                unknown.join(", ")
+            ));
+            continue;
+        }
+
+        if !dry_run {
+            let tag_commits = match tag_commits {
+                Some(ref commits) => commits,
+                None => {
+                    let tags: Vec<GithubTag> =
+                        get_all_pages(&client, &format!("{}/tags", github.loader_repository_url()))
+                            .await?;
+                    tag_commits.insert(tags.into_iter().map(|t| (t.name, t.commit.sha)).collect())
+                }
+            };
+            let Some(commit_hash) = tag_commits.get(&release.tag_name).cloned().or_else(|| {
+                is_commit_sha(&release.target_commitish).then(|| release.target_commitish.clone())
+            }) else {
+                report.drift.push(format!(
+                    "{tag}: couldn't find the commit the release was tagged at, skipping"
+                ));
+                continue;
+            };
+
+            LoaderVersion::create_version(
+                LoaderVersionCreate {
+                    tag: tag.clone(),
+                    channel: release.channel(),
+                    commit_hash,
+                    changelog: release.body.clone().filter(|b| !b.trim().is_empty()),
+                    mac: targets.mac.clone(),
+                    win: targets.win.clone(),
+                    android: targets.android.clone(),
+                    ios: targets.ios.clone(),
+                    created_at: release.published_at,
+                    assets: release.loader_assets(),
+                },
+                conn,
+            )
+            .await?;
+        }
+        report.created.push(tag);
+    }
+
+    let mut missing: Vec<_> = existing
+        .values()
+        .filter(|v| !seen.contains(&v.tag))
+        .collect();
+    missing.sort_by_key(|v| std::cmp::Reverse(v.created_at));
+    for version in missing {
+        report.drift.push(format!(
+            "{}: in the index but not released on GitHub",
+            version.tag
+        ));
+    }
+
+    for tag in &report.created {
+        log::info!(
+            "{} loader version {tag}",
+            if dry_run { "Would add" } else { "Added" }
+        );
+    }
+    for drift in &report.drift {
+        log::warn!("{drift}");
+    }
+    log::info!(
+        "Loader release sync from {}: {} added, {} drifted",
+        github.loader_repository(),
+        report.created.len(),
+        report.drift.len()
+    );
+
+    Ok(report)
+}
+
+/// Fetches every page of a GitHub list endpoint by following `Link: rel="next"`
+async fn get_all_pages<T: serde::de::DeserializeOwned>(
+    client: &Client,
+    url: &str,
+) -> Result<Vec<T>, ApiError> {
+    let mut ret = vec![];
+    let mut next = Some(format!("{url}?per_page=100"));
+
+    while let Some(url) = next {
+        let resp = client
+            .get(&url)
+            .header("Accept", HeaderValue::from_str("application/json").unwrap())
+            .header("User-Agent", "geode_index")
+            .send()
+            .await
+            .inspect_err(|e| log::error!("Failed to request {url}: {e}"))?;
+
+        if !resp.status().is_success() {
+            return Err(ApiError::InternalError(format!(
+                "GitHub returned {} for {url}",
+                resp.status()
+            )));
+        }
+
+        next = resp
+            .headers()
+            .get("Link")
+            .and_then(|h| h.to_str().ok())
+            .and_then(next_page_url);
+        ret.extend(resp.json::<Vec<T>>().await?);
+    }
+
+    Ok(ret)
+}
+
+/// Picks the `rel="next"` URL out of a `Link` header
+fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+fn is_commit_sha(s: &str) -> bool {
+    s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Release notes declare the GD versions a release targets on a line like
+/// `GD: win 2.2074, mac 2.2074, android 2.2074, ios 2.2074`. A bare
+/// `GD: 2.2074` applies to every platform the release has a build for.
+fn parse_gd_targets(body: &str, assets: &[GithubReleaseAsset]) -> GDTargets {
+    let mut targets = GDTargets::default();
+
+    let Some(line) = body.lines().find_map(|line| {
+        let line = line.trim().trim_start_matches(['-', '*']).trim();
+        line.get(..3)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("gd:"))
+            .map(|_| &line[3..])
+    }) else {
+        return targets;
+    };
+
+    for entry in line.split(',') {
+        let parts: Vec<&str> = entry.split_whitespace().collect();
+        match parts.as_slice() {
+            [platform, version] => {
+                let Ok(version) = GDVersion::from_str(version) else {
+                    continue;
+                };
+                match platform.to_lowercase().as_str() {
+                    "win" | "windows" => targets.win = Some(version),
+                    "mac" | "macos" => targets.mac = Some(version),
+                    "android" => targets.android = Some(version),
+                    "ios" => targets.ios = Some(version),
+                    _ => {}
+                }
+            }
+            [version] => {
+                let Ok(version) = GDVersion::from_str(version) else {
+                    continue;
+                };
+                let has_build = |platform: &str| {
+                    assets
+                        .iter()
+                        .any(|a| a.name.contains(&format!("-{platform}")))
+                };
+                for (platform, slot) in [
+                    ("win", &mut targets.win),
+                    ("mac", &mut targets.mac),
+                    ("android", &mut targets.android),
+                    ("ios", &mut targets.ios),
+                ] {
+                    if has_build(platform) {
+                        *slot = Some(version.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(name: &str, digest: Option<&str>) -> GithubReleaseAsset {
+        GithubReleaseAsset {
+            name: name.into(),
+            browser_download_url: format!("https://example.com/{name}"),
+            size: 1024,
+            digest: digest.map(|d| d.into()),
+        }
+    }
+
+    fn gd(s: &str) -> Option<GDVersion> {
+        Some(GDVersion::from_str(s).unwrap())
+    }
+
+    #[test]
+    fn bare_gd_line_applies_to_platforms_with_builds() {
+        let assets = [
+            asset("geode-v4.3.1-win.zip", None),
+            asset("geode-v4.3.1-android64.zip", None),
+        ];
+        let targets = parse_gd_targets("## v4.3.1\n\n* GD: 2.2074\n* Fixed things", &assets);
+
+        assert_eq!(targets.win, gd("2.2074"));
+        assert_eq!(targets.android, gd("2.2074"));
+        assert_eq!(targets.mac, None);
+        assert_eq!(targets.ios, None);
+    }
+
+    #[test]
+    fn per_platform_gd_line() {
+        let targets = parse_gd_targets(
+            "gd: win 2.2074, mac 2.2074, Android 2.2081, ios 2.2081, linux 2.2074",
+            &[],
+        );
+
+        assert_eq!(targets.win, gd("2.2074"));
+        assert_eq!(targets.mac, gd("2.2074"));
+        assert_eq!(targets.android, gd("2.2081"));
+        assert_eq!(targets.ios, gd("2.2081"));
+    }
+
+    #[test]
+    fn missing_gd_line() {
+        assert!(parse_gd_targets("Fixed things", &[]).is_empty());
+    }
+
+    #[test]
+    fn loader_assets_by_file_name() {
+        let release = GithubRelease {
+            tag_name: "v4.3.1".into(),
+            target_commitish: "main".into(),
+            prerelease: false,
+            draft: false,
+            body: None,
+            published_at: None,
+            assets: vec![
+                asset("geode-v4.3.1-win.zip", Some("sha256:ABCD")),
+                asset("geode-v4.3.1-mac.zip", Some("sha256:1234")),
+                asset("geode-v4.3.1-android32.zip", Some("sha256:5678")),
+                asset("geode-v4.3.1-ios.zip", None),
+                asset("geode-installer-v4.3.1-win.exe", Some("sha256:ffff")),
+            ],
+        };
+        let assets = release.loader_assets();
+
+        let platforms: Vec<_> = assets.iter().map(|a| a.platform).collect();
+        assert_eq!(
+            platforms,
+            [
+                VerPlatform::Win,
+                VerPlatform::MacArm,
+                VerPlatform::MacIntel,
+                VerPlatform::Android32,
+            ]
+        );
+        assert_eq!(assets[0].sha256, "abcd");
+        assert_eq!(assets[0].url, "https://example.com/geode-v4.3.1-win.zip");
+    }
+
+    #[test]
+    fn loader_assets_skip_installers() {
+        let release = GithubRelease {
+            tag_name: "v4.3.1".into(),
+            target_commitish: "main".into(),
+            prerelease: false,
+            draft: false,
+            body: None,
+            published_at: None,
+            assets: vec![
+                asset("geode-installer-v4.3.1-win.exe", Some("sha256:ffff")),
+                asset("geode-installer-v4.3.1-mac.pkg", Some("sha256:eeee")),
+                asset("geode-v4.3.1-win.zip", Some("sha256:abcd")),
+                asset("geode-v4.3.1-mac.zip", Some("sha256:1234")),
+            ],
+        };
+        let assets = release.loader_assets();
+
+        let urls: Vec<_> = assets
+            .iter()
+            .map(|a| (a.platform, a.url.as_str()))
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                (VerPlatform::Win, "https://example.com/geode-v4.3.1-win.zip"),
+                (
+                    VerPlatform::MacArm,
+                    "https://example.com/geode-v4.3.1-mac.zip"
+                ),
+                (
+                    VerPlatform::MacIntel,
+                    "https://example.com/geode-v4.3.1-mac.zip"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn link_header_next_page() {
+        assert_eq!(
+            next_page_url(
+                r#"<https://api.github.com/repositories/1/releases?per_page=100&page=2>; rel="next", <https://api.github.com/repositories/1/releases?per_page=100&page=5>; rel="last""#
+            )
+            .as_deref(),
+            Some("https://api.github.com/repositories/1/releases?per_page=100&page=2")
+        );
+        assert_eq!(
+            next_page_url(r#"<https://api.github.com/repositories/1/releases?page=4>; rel="prev""#),
+            None
+        );
+    }
+}
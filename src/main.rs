@@ -9,7 +9,6 @@ use actix_web::{
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-mod abbreviate;
 mod auth;
 mod cli;
 mod config;
@@ -17,11 +16,15 @@ mod database;
 mod endpoints;
 mod events;
 mod extractors;
+mod feeds;
+mod images;
 mod jobs;
+mod markdown;
 mod mod_zip;
 mod openapi;
 mod types;
 mod webhook;
+mod xml;
 mod storage;
 
 #[tokio::main]
@@ -66,9 +69,24 @@ async fn main() -> anyhow::Result<()> {
             .service(endpoints::mods::create)
             .service(endpoints::mods::update_mod)
             .service(endpoints::mods::get_logo)
+            .service(endpoints::mods::get_stats)
+            .service(endpoints::mods::get_changelog)
+            .service(endpoints::mod_media::index)
+            .service(endpoints::mod_media::upload_screenshot)
+            .service(endpoints::mod_media::add_video)
+            .service(endpoints::mod_media::update)
+            .service(endpoints::mod_media::delete)
+            .service(endpoints::downloads::flagged)
+            .service(endpoints::downloads::discount)
             .service(endpoints::mod_status_badge::status_badge)
+            .service(endpoints::feeds::new_mods)
+            .service(endpoints::feeds::updates)
+            .service(endpoints::feeds::mod_feed)
+            .service(endpoints::feeds::developer_feed)
             .service(endpoints::mod_versions::get_version_index)
             .service(endpoints::mod_versions::get_one)
+            .service(endpoints::mod_versions::get_manifest)
+            .service(endpoints::mod_versions::get_files)
             .service(endpoints::mod_versions::download_version)
             .service(endpoints::mod_versions::create_version)
             .service(endpoints::mod_versions::update_version)
@@ -87,18 +105,43 @@ async fn main() -> anyhow::Result<()> {
             .service(endpoints::developers::get_developer)
             .service(endpoints::developers::add_developer_to_mod)
             .service(endpoints::developers::remove_dev_from_mod)
+            .service(endpoints::developers::update_dev_role)
             .service(endpoints::developers::delete_token)
             .service(endpoints::developers::delete_tokens)
+            .service(endpoints::developers::get_sessions)
+            .service(endpoints::developers::delete_session)
+            .service(endpoints::mod_transfers::get_own_transfers)
+            .service(endpoints::mod_transfers::create_transfer)
+            .service(endpoints::mod_transfers::accept_transfer)
+            .service(endpoints::mod_transfers::delete_transfer)
             .service(endpoints::developers::update_profile)
+            .service(endpoints::developers::update_avatar)
+            .service(endpoints::developers::delete_avatar)
             .service(endpoints::developers::get_own_mods)
             .service(endpoints::developers::get_me)
             .service(endpoints::developers::update_developer)
+            .service(endpoints::organizations::create)
+            .service(endpoints::organizations::get_one)
+            .service(endpoints::organizations::set_member)
+            .service(endpoints::organizations::remove_member)
+            .service(endpoints::organizations::set_mod_organization)
             .service(endpoints::tags::index)
             .service(endpoints::tags::detailed_index)
             .service(endpoints::stats::get_stats)
+            .service(endpoints::stats::get_history)
+            .service(endpoints::gd_versions::index)
+            .service(endpoints::gd_versions::create)
+            .service(endpoints::gd_versions::update)
+            .service(endpoints::gd_versions::detect)
+            .service(endpoints::gd_versions::alias_index)
+            .service(endpoints::gd_versions::create_alias)
+            .service(endpoints::gd_versions::update_alias)
+            .service(endpoints::gd_versions::delete_alias)
             .service(endpoints::loader::get_one)
             .service(endpoints::loader::create_version)
             .service(endpoints::loader::get_many)
+            .service(endpoints::loader::yank)
+            .service(endpoints::loader::unyank)
             .service(endpoints::health::health)
     })
     .bind(("0.0.0.0", port))?;
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use pulldown_cmark::{Options, Parser};
+use regex::{Captures, Regex};
+
+static COLOR_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<c(?:([a-z])|-([0-9a-f]{6}))>").unwrap());
+static COLOR_TAG_END: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)</c>").unwrap());
+
+/// Renders markdown from a .geode file (about.md, changelog.md) to HTML that is safe to
+/// embed anywhere: no scripts or event handlers, links only to http(s) and mailto, and
+/// the only inline style kept is the color Geode's `<cX>` tags turn into.
+pub fn render_html(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, Parser::new_ext(markdown, options));
+
+    let html = COLOR_TAG.replace_all(&html, |caps: &Captures| {
+        let color = match (caps.get(1), caps.get(2)) {
+            (Some(letter), _) => color_for_tag(letter.as_str()),
+            (_, Some(hex)) => Some(hex.as_str().to_lowercase()),
+            _ => None,
+        };
+
+        match color {
+            Some(color) => format!(r##"<span style="color: #{color}">"##),
+            // Unknown tags still need a span for their </c>
+            None => "<span>".into(),
+        }
+    });
+    let html = COLOR_TAG_END.replace_all(&html, "</span>");
+
+    ammonia::Builder::default()
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .add_tag_attributes("span", &["style"])
+        .filter_style_properties(HashSet::from(["color"]))
+        .clean(&html)
+        .to_string()
+}
+
+/// Same colors the in-game markdown renderer uses
+fn color_for_tag(tag: &str) -> Option<String> {
+    let color = match tag.to_ascii_lowercase().as_str() {
+        "a" => "9632ff",
+        "b" => "4a52e1",
+        "c" => "ffff96",
+        "d" => "ff96ff",
+        "f" => "96ffff",
+        "g" => "40e348",
+        "j" => "32c8ff",
+        "l" => "60abef",
+        "o" => "ffa54b",
+        "p" => "ff00ff",
+        "r" => "ff5a5a",
+        "s" => "ffdc41",
+        "y" => "ffff00",
+        _ => return None,
+    };
+
+    Some(color.into())
+}
+
+/// Finds the section of a changelog.md written for `version`: a heading that mentions
+/// the version, with or without a leading `v`, up to the next heading of the same level.
+pub fn changelog_section(changelog: &str, version: &str) -> Option<String> {
+    let version = version.trim_start_matches('v');
+    let mut section: Option<(usize, Vec<&str>)> = None;
+
+    for line in changelog.lines() {
+        let trimmed = line.trim_start();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+
+        if let Some((section_level, lines)) = &mut section {
+            if level > 0 && level <= *section_level {
+                break;
+            }
+            lines.push(line);
+        } else if level > 0 && mentions_version(&trimmed[level..], version) {
+            section = Some((level, vec![]));
+        }
+    }
+
+    let text = section?.1.join("\n");
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.into())
+}
+
+fn mentions_version(heading: &str, version: &str) -> bool {
+    heading
+        .split(|c: char| c.is_whitespace() || matches!(c, '[' | ']' | '(' | ')' | ':' | ','))
+        .any(|word| word.trim_start_matches('v') == version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_scripts() {
+        let html = render_html("Hi <script>alert(1)</script> there");
+        assert!(!html.contains("script"), "{html}");
+        assert!(!html.contains("alert"), "{html}");
+    }
+
+    #[test]
+    fn strips_unsafe_links() {
+        let html = render_html(
+            "[a](javascript:alert(1)) [b](data:text/html,hi) [c](https://geode-sdk.org)",
+        );
+        assert!(!html.contains("javascript:"), "{html}");
+        assert!(!html.contains("data:"), "{html}");
+        assert!(html.contains(r#"href="https://geode-sdk.org""#), "{html}");
+
+        let html = render_html(r#"<a href="javascript:alert(1)">a</a>"#);
+        assert!(!html.contains("javascript:"), "{html}");
+    }
+
+    #[test]
+    fn strips_event_handlers() {
+        let html = render_html(
+            r#"<img src="https://example.com/a.png" onerror="alert(1)"> <b onclick="alert(1)">b</b>"#,
+        );
+        assert!(!html.contains("onerror"), "{html}");
+        assert!(!html.contains("onclick"), "{html}");
+        assert!(!html.contains("alert"), "{html}");
+    }
+
+    #[test]
+    fn style_only_keeps_color() {
+        let html = render_html(
+            r#"<span style="color: red; position: fixed; background: url(x)">a</span>"#,
+        );
+        assert!(html.contains(r#"style="color:red""#), "{html}");
+        assert!(!html.contains("position"), "{html}");
+        assert!(!html.contains("background"), "{html}");
+
+        let html = render_html(r#"<div style="color: red">a</div>"#);
+        assert!(!html.contains("style"), "{html}");
+    }
+
+    #[test]
+    fn color_tags() {
+        assert_eq!(
+            render_html("<cr>red</c> and <CG>green</c>"),
+            "<p><span style=\"color:#ff5a5a\">red</span> and <span style=\"color:#40e348\">green</span></p>\n"
+        );
+        assert_eq!(
+            render_html("<c-00FF00>hex</c>"),
+            "<p><span style=\"color:#00ff00\">hex</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn unknown_and_unmatched_color_tags() {
+        assert_eq!(render_html("<cz>plain</c>"), "<p><span>plain</span></p>\n");
+        assert_eq!(render_html("no opening</c> tag"), "<p>no opening tag</p>\n");
+        assert_eq!(
+            render_html("<cr>never closed"),
+            "<p><span style=\"color:#ff5a5a\">never closed</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn color_tags_in_code_are_left_alone() {
+        let html = render_html("`<cr>red</c>`\n\n```\n<cg>green</c>\n```");
+        assert!(!html.contains("<span"), "{html}");
+        assert!(
+            html.contains("<code>&lt;cr&gt;red&lt;/c&gt;</code>"),
+            "{html}"
+        );
+        assert!(html.contains("&lt;cg&gt;green&lt;/c&gt;"), "{html}");
+    }
+}
@@ -22,6 +22,8 @@ pub enum ModZipError {
     SerdeJsonError(#[from] serde_json::Error),
     #[error("Invalid mod logo: {0}")]
     InvalidLogo(String),
+    #[error("Invalid screenshot: {0}")]
+    InvalidScreenshot(String),
     #[error(".geode file hash mismatch: {0} doesn't match {1}")]
     ModFileHashMismatch(String, String),
     #[error("Failed to fetch .geode file: {0}")]
@@ -32,6 +34,11 @@ pub enum ModZipError {
     ModFileTooLargeUncompressed(u64, u64),
     #[error("Invalid mod.json: {0}")]
     InvalidModJson(String),
+    #[error(
+        "Invalid mod.json: validation error: {}",
+        crate::types::mod_json::extract_validation_error(.0)
+    )]
+    ModJsonValidation(validator::ValidationErrors),
     #[error("Invalid binaries: {0}")]
     InvalidBinaries(String),
 }
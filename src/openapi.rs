@@ -10,9 +10,24 @@ use crate::{endpoints, types};
         endpoints::mods::create,
         endpoints::mods::update_mod,
         endpoints::mods::get_logo,
+        endpoints::mods::get_stats,
+        endpoints::mods::get_changelog,
+        endpoints::mod_media::index,
+        endpoints::mod_media::upload_screenshot,
+        endpoints::mod_media::add_video,
+        endpoints::mod_media::update,
+        endpoints::mod_media::delete,
+        endpoints::downloads::flagged,
+        endpoints::downloads::discount,
+        endpoints::feeds::new_mods,
+        endpoints::feeds::updates,
+        endpoints::feeds::mod_feed,
+        endpoints::feeds::developer_feed,
         endpoints::mods::get_mod_updates,
         endpoints::mod_versions::get_version_index,
         endpoints::mod_versions::get_one,
+        endpoints::mod_versions::get_manifest,
+        endpoints::mod_versions::get_files,
         endpoints::mod_versions::download_version,
         endpoints::mod_versions::create_version,
         endpoints::mod_versions::update_version,
@@ -25,18 +40,43 @@ use crate::{endpoints, types};
         endpoints::developers::get_developer,
         endpoints::developers::add_developer_to_mod,
         endpoints::developers::remove_dev_from_mod,
+        endpoints::developers::update_dev_role,
         endpoints::developers::delete_token,
         endpoints::developers::delete_tokens,
+        endpoints::developers::get_sessions,
+        endpoints::developers::delete_session,
+        endpoints::mod_transfers::get_own_transfers,
+        endpoints::mod_transfers::create_transfer,
+        endpoints::mod_transfers::accept_transfer,
+        endpoints::mod_transfers::delete_transfer,
         endpoints::developers::update_profile,
+        endpoints::developers::update_avatar,
+        endpoints::developers::delete_avatar,
         endpoints::developers::get_own_mods,
         endpoints::developers::get_me,
         endpoints::developers::update_developer,
+        endpoints::organizations::create,
+        endpoints::organizations::get_one,
+        endpoints::organizations::set_member,
+        endpoints::organizations::remove_member,
+        endpoints::organizations::set_mod_organization,
         endpoints::tags::index,
         endpoints::tags::detailed_index,
         endpoints::stats::get_stats,
+        endpoints::stats::get_history,
+        endpoints::gd_versions::index,
+        endpoints::gd_versions::create,
+        endpoints::gd_versions::update,
+        endpoints::gd_versions::detect,
+        endpoints::gd_versions::alias_index,
+        endpoints::gd_versions::create_alias,
+        endpoints::gd_versions::update_alias,
+        endpoints::gd_versions::delete_alias,
         endpoints::loader::get_one,
         endpoints::loader::create_version,
         endpoints::loader::get_many,
+        endpoints::loader::yank,
+        endpoints::loader::unyank,
         endpoints::health::health,
         endpoints::auth::refresh_token,
         endpoints::auth::github::start_github_login,
@@ -48,19 +88,50 @@ use crate::{endpoints, types};
     components(
         schemas(
             types::api::ApiResponse<String>,
+            types::api::ApiErrorResponse,
+            types::api::ErrorCode,
+            types::api::FieldError,
             types::api::PaginatedData<types::models::mod_entity::Mod>,
             types::api::PaginatedData<types::models::developer::Developer>,
             types::api::PaginatedData<types::models::loader_version::LoaderVersion>,
             types::models::mod_entity::Mod,
             types::models::mod_entity::ModUpdate,
+            types::models::mod_entity::ContentFormat,
+            types::models::changelog::ChangelogEntry,
             types::models::mod_version::ModVersion,
+            types::models::mod_version_file::ModVersionFile,
             types::models::developer::ModDeveloper,
+            types::models::developer::ModDeveloperRole,
             types::models::developer::Developer,
+            types::models::session::Session,
+            types::models::mod_transfer::ModTransfer,
+            types::models::mod_transfer::ModTransferStatus,
+            types::models::organization::Organization,
+            types::models::organization::OrganizationRole,
+            types::models::organization::OrganizationMember,
+            types::models::organization::OrganizationProfile,
+            types::models::organization::DeveloperTeam,
+            types::models::developer::DeveloperProfile,
+            types::models::developer::DeveloperLinks,
+            types::models::developer::DeveloperStats,
             types::models::deprecations::Deprecation,
+            types::models::download_flags::DownloadFlagReason,
+            types::models::download_flags::FlaggedDownloads,
+            types::models::download_flags::DiscountedDownloads,
+            types::models::mod_media::ModMedia,
+            types::models::mod_media::ModMediaKind,
+            types::models::mod_media::ModMediaSource,
+            types::models::download_stats::ModDownloadStats,
+            types::models::download_stats::DownloadStatsPoint,
+            types::models::download_stats::StatsGranularity,
+            types::models::download_stats::DownloadBreakdowns,
+            types::models::mod_logo::LogoFormat,
             types::models::tag::Tag,
             types::models::stats::Stats,
+            types::models::stats::StatsSnapshot,
             types::models::mod_version_status::ModVersionStatusEnum,
-            types::models::mod_gd_version::GDVersionEnum,
+            types::models::mod_gd_version::GDVersion,
+            types::models::mod_gd_version::GDRelease,
             types::models::mod_gd_version::VerPlatform,
             types::models::mod_gd_version::DetailedGDVersion,
             types::models::dependency::ResponseDependency,
@@ -71,6 +142,8 @@ use crate::{endpoints, types};
             types::models::incompatibility::IncompatibilityImportance,
             types::models::mod_link::ModLinks,
             types::models::loader_version::LoaderVersion,
+            types::models::loader_version::LoaderAsset,
+            types::models::loader_version::LoaderChannel,
             types::models::gd_version_alias::GDVersionAlias,
             endpoints::mods::IndexSortType,
             endpoints::developers::SimpleDevMod,
@@ -82,8 +155,12 @@ use crate::{endpoints, types};
         (name = "mod_versions", description = "Mod version management endpoints"),
         (name = "deprecations", description = "Mod deprecation management endpoints"),
         (name = "developers", description = "Developer management endpoints"),
+        (name = "organizations", description = "Organization management endpoints"),
         (name = "tags", description = "Tag management endpoints"),
         (name = "stats", description = "Statistics endpoints"),
+        (name = "downloads", description = "Download analysis endpoints"),
+        (name = "feeds", description = "Atom feeds of accepted mods and versions"),
+        (name = "gd", description = "Geometry Dash version endpoints"),
         (name = "loader", description = "Geode loader version endpoints"),
         (name = "auth", description = "Authentication endpoints"),
         (name = "health", description = "Health check endpoint"),
@@ -1,4 +1,3 @@
 pub mod api;
 pub mod mod_json;
 pub mod models;
-pub mod serde;
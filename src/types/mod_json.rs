@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Cursor, Read};
 
 use actix_web::web::Bytes;
@@ -9,17 +9,25 @@ use serde::Deserialize;
 use validator::{Validate, ValidationError};
 use zip::read::ZipFile;
 
+use crate::images;
 use crate::mod_zip::{self, ModZipError};
+use crate::types::api::FieldError;
 
 use super::models::{
     dependency::{DependencyCreate, DependencyImportance, ModVersionCompare},
     incompatibility::{IncompatibilityCreate, IncompatibilityImportance},
     mod_gd_version::DetailedGDVersion,
+    mod_version_file::ModVersionFile,
 };
 
 const MAX_MOD_JSON_SIZE: u64 = 128 * 1024; // 128 KB
 const MAX_MARKDOWN_FILE_SIZE: u64 = 1048576; // 1 MB
 const MAX_MOD_ZIP_SIZE: u64 = 1024 * 1024 * 512; // 512 mb
+const MAX_SCREENSHOT_FILE_SIZE: u64 = 5 * 1000 * 1000; // 5 MB
+/// Most screenshots and videos a mod can have, from its .geode and uploaded ones together
+pub const MAX_MOD_MEDIA: usize = 10;
+/// Most languages about.md and changelog.md can be translated to
+const MAX_LOCALIZATIONS: usize = 50;
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct ModJson {
@@ -65,15 +73,33 @@ pub struct ModJson {
     pub gd: DetailedGDVersion,
     #[serde(skip_deserializing, skip_serializing)]
     pub logo: Vec<u8>,
+    /// PNGs from the `screenshots` folder, sorted by file name and re-encoded
+    #[serde(skip_deserializing, skip_serializing)]
+    pub screenshots: Vec<Vec<u8>>,
     #[validate(length(min = 1, max = MAX_MARKDOWN_FILE_SIZE))]
     pub about: Option<String>,
     #[validate(length(min = 1, max = MAX_MARKDOWN_FILE_SIZE))]
     pub changelog: Option<String>,
+    /// Translations from `about.<lang>.md` and `changelog.<lang>.md`, by language tag
+    #[serde(skip_deserializing, skip_serializing)]
+    pub localized: BTreeMap<String, LocalizedMarkdown>,
+    /// mod.json exactly as it was submitted
+    #[serde(skip_deserializing, skip_serializing)]
+    pub raw: String,
+    /// Every file in the archive
+    #[serde(skip_deserializing, skip_serializing)]
+    pub files: Vec<ModVersionFile>,
     pub dependencies: Option<ModJsonDependencies>,
     pub incompatibilities: Option<ModJsonIncompatibilities>,
     pub links: Option<ModJsonLinks>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct LocalizedMarkdown {
+    pub about: Option<String>,
+    pub changelog: Option<String>,
+}
+
 #[derive(Deserialize, Debug, Clone, Validate)]
 pub struct ModJsonLinks {
     #[validate(length(max = 512))]
@@ -188,11 +214,20 @@ impl ModJson {
         let hash = sha256::digest(slice);
         let mut archive = mod_zip::bytes_to_ziparchive(file)?;
 
-        // check total size of the zip file
+        // check total size of the zip file, and keep a listing of it for reviewers
         let mut total_size: u64 = 0;
+        let mut files: Vec<ModVersionFile> = Vec::with_capacity(archive.len());
         for i in 0..archive.len() {
             let file = archive.by_index(i)?;
             total_size += file.size();
+
+            if file.is_file() {
+                files.push(ModVersionFile {
+                    name: file.name().into(),
+                    size: i64::try_from(file.size()).unwrap_or(i64::MAX),
+                    crc32: file.crc32(),
+                });
+            }
         }
 
         if total_size > MAX_MOD_ZIP_SIZE {
@@ -203,7 +238,7 @@ impl ModJson {
             ));
         }
 
-        let json_file = archive
+        let mut json_file = archive
             .by_name("mod.json")
             .or(Err(ModZipError::InvalidModJson(
                 "No mod.json found in .geode file".into(),
@@ -216,13 +251,23 @@ impl ModJson {
             )));
         }
 
-        let mut json = serde_json::from_reader::<ZipFile<Cursor<Bytes>>, ModJson>(json_file)
+        let mut raw = String::with_capacity(json_file.size() as usize);
+        json_file
+            .read_to_string(&mut raw)
+            .inspect_err(|e| log::error!("Failed to read mod.json: {e}"))?;
+        drop(json_file);
+
+        let mut json = serde_json::from_str::<ModJson>(&raw)
             .inspect_err(|e| log::error!("Failed to parse mod.json: {e}"))?;
+        json.raw = raw;
+        json.files = files;
 
         json.version = json.version.trim_start_matches('v').to_string();
         json.hash = hash;
         json.download_url = parse_download_url(download_url);
 
+        let mut screenshots: Vec<(String, Vec<u8>)> = vec![];
+
         for i in 0..archive.len() {
             if let Ok(mut file) = archive.by_index(i) {
                 if file.name().ends_with(".dll") {
@@ -269,15 +314,56 @@ impl ModJson {
                                 ))
                             })?,
                     );
+                } else if let Some((kind, lang)) = localized_markdown_name(file.name()) {
+                    if file.size() > MAX_MARKDOWN_FILE_SIZE {
+                        return Err(ModZipError::InvalidModJson(format!(
+                            "{kind}.{lang}.md is too large (max {} MB)",
+                            MAX_MARKDOWN_FILE_SIZE / 1_000_000
+                        )));
+                    }
+
+                    let text = parse_zip_entry_to_str(&mut file).map_err(|e| {
+                        ModZipError::InvalidModJson(format!("Failed to read {kind}.{lang}.md: {e}"))
+                    })?;
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+
+                    let entry = json.localized.entry(lang).or_default();
+                    match kind {
+                        "about" => entry.about = Some(text),
+                        _ => entry.changelog = Some(text),
+                    }
                 } else if file.name() == "logo.png" {
                     if store_image {
                         json.logo = mod_zip::extract_mod_logo(&mut file)?;
                     } else {
                         mod_zip::validate_mod_logo(&mut file)?;
                     }
+                } else if file.name().starts_with("screenshots/")
+                    && file.name().ends_with(".png")
+                {
+                    let name = file.name().to_string();
+                    screenshots.push((name.clone(), extract_screenshot(&name, &mut file)?));
                 }
             }
         }
+
+        if json.localized.len() > MAX_LOCALIZATIONS {
+            return Err(ModZipError::InvalidModJson(format!(
+                "Too many translations of about.md and changelog.md (max {MAX_LOCALIZATIONS} languages)"
+            )));
+        }
+
+        if screenshots.len() > MAX_MOD_MEDIA {
+            return Err(ModZipError::InvalidScreenshot(format!(
+                "Too many screenshots (max {MAX_MOD_MEDIA})"
+            )));
+        }
+
+        screenshots.sort_by(|a, b| a.0.cmp(&b.0));
+        json.screenshots = screenshots.into_iter().map(|(_, data)| data).collect();
+
         Ok(json)
     }
 
@@ -484,10 +570,7 @@ impl ModJson {
     pub fn validate(&self) -> Result<(), ModZipError> {
         if let Err(e) = <Self as Validate>::validate(self) {
             log::warn!("mod.json validation error: {e}");
-            let useful_error = extract_validation_error(&e);
-            return Err(ModZipError::InvalidModJson(format!(
-                "validation error: {useful_error}"
-            )));
+            return Err(ModZipError::ModJsonValidation(e));
         }
 
         let id_regex = Regex::new(r#"^[a-z0-9_\-]+\.[a-z0-9_\-]+$"#).unwrap();
@@ -590,6 +673,46 @@ impl ModJson {
     }
 }
 
+/// Decodes and re-encodes a screenshot, which also validates it
+fn extract_screenshot(
+    name: &str,
+    file: &mut ZipFile<Cursor<Bytes>>,
+) -> Result<Vec<u8>, ModZipError> {
+    if file.size() > MAX_SCREENSHOT_FILE_SIZE {
+        return Err(ModZipError::InvalidScreenshot(format!(
+            "{name} is too large (max {} MB)",
+            MAX_SCREENSHOT_FILE_SIZE / 1_000_000
+        )));
+    }
+
+    let mut data: Vec<u8> = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut data)
+        .inspect_err(|e| log::error!("{name} read fail: {e}"))?;
+
+    images::process_screenshot(&data)
+        .map_err(|e| ModZipError::InvalidScreenshot(format!("{name}: {e}")))
+}
+
+/// Splits `about.es.md` into `("about", "es")`. Only files at the root of the archive
+/// with a plausible language tag count.
+fn localized_markdown_name(name: &str) -> Option<(&'static str, String)> {
+    let rest = name.strip_suffix(".md")?;
+    let (kind, lang) = if let Some(lang) = rest.strip_prefix("about.") {
+        ("about", lang)
+    } else {
+        ("changelog", rest.strip_prefix("changelog.")?)
+    };
+
+    let mut parts = lang.split('-');
+    let primary = parts.next()?;
+    let valid = (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && parts.all(|p| (2..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()))
+        && lang.split('-').count() <= 3;
+
+    valid.then(|| (kind, lang.to_string()))
+}
+
 fn parse_zip_entry_to_str(file: &mut ZipFile<Cursor<Bytes>>) -> Result<String, String> {
     let mut string: String = String::from("");
     match file.read_to_string(&mut string) {
@@ -736,7 +859,43 @@ fn map_field_error(e: &validator::ValidationError) -> String {
     }
 }
 
-fn extract_validation_error(e: &validator::ValidationErrors) -> String {
+/// Flattens nested validation errors into a list of per-field errors
+pub fn extract_field_errors(e: &validator::ValidationErrors) -> Vec<FieldError> {
+    fn walk(prefix: &str, e: &validator::ValidationErrors, out: &mut Vec<FieldError>) {
+        use validator::ValidationErrorsKind;
+
+        for (field, err) in e.errors() {
+            let path = if prefix.is_empty() {
+                field.to_string()
+            } else {
+                format!("{prefix}.{field}")
+            };
+
+            match err {
+                ValidationErrorsKind::Struct(s) => walk(&path, s, out),
+                ValidationErrorsKind::Field(errors) => {
+                    out.extend(errors.iter().map(|e| FieldError {
+                        field: path.clone(),
+                        code: e.code.to_string(),
+                        message: map_field_error(e),
+                    }));
+                }
+                ValidationErrorsKind::List(map) => {
+                    for (index, errors) in map {
+                        walk(&format!("{path}[{index}]"), errors, out);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    walk("", e, &mut out);
+    out.sort_by(|a, b| a.field.cmp(&b.field));
+    out
+}
+
+pub fn extract_validation_error(e: &validator::ValidationErrors) -> String {
     use validator::ValidationErrorsKind;
 
     let mut str_errors = Vec::new();
@@ -5,7 +5,9 @@ use sqlx::PgConnection;
 use utoipa::ToSchema;
 use crate::database::DatabaseError;
 
-use super::mod_gd_version::{GDVersionEnum, VerPlatform};
+use super::mod_gd_version::{GDVersion, VerPlatform};
+
+pub use geode_index_types::models::dependency::{DependencyImportance, ResponseDependency};
 
 #[derive(sqlx::FromRow, Clone)]
 pub struct Dependency {}
@@ -17,14 +19,6 @@ pub struct DependencyCreate {
     pub importance: DependencyImportance,
 }
 
-#[derive(Serialize, Debug, Clone, ToSchema)]
-pub struct ResponseDependency {
-    pub mod_id: String,
-    pub version: String,
-    pub importance: DependencyImportance,
-    pub required: bool,
-}
-
 #[derive(sqlx::FromRow, Clone, Debug)]
 pub struct FetchedDependency {
     pub mod_version_id: i32,
@@ -97,21 +91,11 @@ impl Display for ModVersionCompare {
     }
 }
 
-#[derive(sqlx::Type, Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, ToSchema)]
-#[sqlx(type_name = "dependency_importance", rename_all = "lowercase")]
-#[serde(rename_all = "lowercase")]
-pub enum DependencyImportance {
-    Suggested,
-    Recommended,
-    #[default]
-    Required,
-}
-
 impl Dependency {
     pub async fn get_for_mod_versions(
         ids: &Vec<i32>,
         platform: Option<VerPlatform>,
-        gd: Option<GDVersionEnum>,
+        gd: Option<GDVersion>,
         geode: Option<&semver::Version>,
         pool: &mut PgConnection,
     ) -> Result<HashMap<i32, Vec<FetchedDependency>>, DatabaseError> {
@@ -147,10 +131,11 @@ impl Dependency {
                 INNER JOIN mods dpcy ON dp.dependency_id = dpcy.id
                 INNER JOIN mod_versions dpcy_version ON dpcy_version.mod_id = dpcy.id
                 INNER JOIN mod_gd_versions dpcy_mgv ON dpcy_version.id = dpcy_mgv.mod_id
+                INNER JOIN gd_versions dpcy_gdv ON dpcy_gdv.name = dpcy_mgv.gd
                 INNER JOIN mod_version_statuses dpcy_status ON dpcy_version.status_id = dpcy_status.id
                 WHERE dpcy_status.status = 'accepted'
                 AND mv.id = ANY($1)
-                AND ($2 IS NULL OR dpcy_mgv.gd = $2 OR dpcy_mgv.gd = '*')
+                AND ($2 IS NULL OR dpcy_gdv.sort_order IN (SELECT sort_order FROM gd_versions WHERE name IN ($2, '*')))
                 AND ($3 IS NULL OR dpcy_mgv.platform = $3)
                 AND ($4 IS NULL OR $4 = dpcy_version.geode_major)
                 AND ($5 IS NULL OR $5 >= dpcy_version.geode_minor)
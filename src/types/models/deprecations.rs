@@ -1,10 +1 @@
-use serde::Serialize;
-use utoipa::ToSchema;
-
-#[derive(sqlx::FromRow, Serialize, Clone, ToSchema)]
-pub struct Deprecation {
-    pub id: i32,
-    pub mod_id: String,
-    pub by: Vec<String>,
-    pub reason: String,
-}
+pub use geode_index_types::models::deprecations::Deprecation;
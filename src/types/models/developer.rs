@@ -1,13 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::{Validate, ValidateUrl, ValidationError};
 
-#[derive(Deserialize, Serialize, Clone, Debug, ToSchema)]
-pub struct ModDeveloper {
-    pub id: i32,
-    pub username: String,
-    pub display_name: String,
-    pub is_owner: bool,
-}
+use super::organization::DeveloperTeam;
+
+pub use geode_index_types::models::developer::{ModDeveloper, ModDeveloperRole};
 
 #[derive(sqlx::FromRow, Serialize, Clone, Debug, ToSchema)]
 pub struct Developer {
@@ -18,3 +16,45 @@ pub struct Developer {
     pub admin: bool,
     pub github_id: i64,
 }
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, Validate, ToSchema)]
+pub struct DeveloperLinks {
+    #[validate(length(max = 512), url(message = "must be a valid URL"))]
+    pub website: Option<String>,
+    #[validate(length(max = 5), custom(function = "validate_social_links"))]
+    #[serde(default)]
+    pub social: Vec<String>,
+}
+
+fn validate_social_links(links: &[String]) -> Result<(), ValidationError> {
+    for link in links {
+        if link.len() > 512 {
+            return Err(ValidationError::new("length")
+                .with_message("links must be at most 512 characters".into()));
+        }
+        if !link.validate_url() {
+            return Err(ValidationError::new("url").with_message("links must be valid URLs".into()));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Clone, Debug, Default, ToSchema)]
+pub struct DeveloperStats {
+    /// Number of mods with at least one accepted version
+    pub mod_count: i64,
+    pub total_downloads: i64,
+    pub first_published_at: Option<DateTime<Utc>>,
+}
+
+/// Public profile of a developer
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct DeveloperProfile {
+    #[serde(flatten)]
+    pub developer: Developer,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub links: DeveloperLinks,
+    pub stats: DeveloperStats,
+    pub organizations: Vec<DeveloperTeam>,
+}
@@ -0,0 +1,3 @@
+pub use geode_index_types::models::download_stats::{
+    DownloadBreakdowns, DownloadStatsPoint, ModDownloadStats, StatsGranularity,
+};
@@ -1,22 +1,79 @@
 use crate::{
     database::DatabaseError,
-    types::models::mod_gd_version::{GDVersionEnum, VerPlatform},
+    types::models::mod_gd_version::{GDVersion, VerPlatform},
 };
 
 use serde::Serialize;
 use utoipa::ToSchema;
 
-use sqlx::{types::Uuid, PgConnection, Postgres, QueryBuilder};
+use sqlx::{
+    types::{
+        chrono::{DateTime, Utc},
+        Uuid,
+    },
+    PgConnection, Postgres, QueryBuilder,
+};
 
 #[derive(Serialize, ToSchema)]
 pub struct GDVersionAlias {
-    pub version_name: GDVersionEnum,
+    pub version_name: GDVersion,
     pub mac_arm_uuid: Option<String>,
     pub mac_intel_uuid: Option<String>,
     pub android_manifest_id: Option<i32>,
     pub windows_timestamp: Option<i32>,
     pub ios_bundle_version: Option<String>,
-    pub added_at: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Parsed platform identifiers of a GD version
+#[derive(Default)]
+pub struct GDVersionAliasCreate {
+    pub mac_arm_uuid: Option<Uuid>,
+    pub mac_intel_uuid: Option<Uuid>,
+    pub android_manifest_id: Option<i32>,
+    pub windows_timestamp: Option<i32>,
+    pub ios_bundle_version: Option<String>,
+}
+
+pub fn parse_uuid(identifier: &str) -> Result<Uuid, DatabaseError> {
+    Uuid::parse_str(identifier)
+        .map_err(|_| DatabaseError::InvalidInput("Identifier is not a valid UUID".to_string()))
+}
+
+pub fn parse_manifest_id(identifier: &str) -> Result<i32, DatabaseError> {
+    identifier
+        .parse::<i32>()
+        .ok()
+        .filter(|x| *x > 0)
+        .ok_or_else(|| {
+            DatabaseError::InvalidInput("Identifier is not a valid manifest id".to_string())
+        })
+}
+
+pub fn parse_timestamp(identifier: &str) -> Result<i32, DatabaseError> {
+    identifier
+        .parse::<i32>()
+        .ok()
+        .filter(|x| *x > 0)
+        .ok_or_else(|| {
+            DatabaseError::InvalidInput("Identifier is not a valid timestamp".to_string())
+        })
+}
+
+/// iOS bundle versions look like `2.2.0.7.0`
+pub fn parse_bundle_version(identifier: &str) -> Result<String, DatabaseError> {
+    let valid = !identifier.is_empty()
+        && identifier
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+
+    if valid {
+        Ok(identifier.to_string())
+    } else {
+        Err(DatabaseError::InvalidInput(
+            "Identifier is not a valid bundle version".to_string(),
+        ))
+    }
 }
 
 impl GDVersionAlias {
@@ -24,23 +81,19 @@ impl GDVersionAlias {
         platform: VerPlatform,
         identifier: &str,
         pool: &mut PgConnection,
-    ) -> Result<Option<GDVersionEnum>, DatabaseError> {
+    ) -> Result<Option<GDVersion>, DatabaseError> {
         let mut query_builder: QueryBuilder<Postgres> =
             QueryBuilder::new(r#"SELECT version_name FROM gd_version_aliases"#);
 
         match platform {
             VerPlatform::Android | VerPlatform::Android32 | VerPlatform::Android64 => {
-                let manifest_id = identifier.parse::<i32>().map_err(|_| {
-                    DatabaseError::InvalidInput("Identifier is not a valid manifest id".to_string())
-                })?;
+                let manifest_id = parse_manifest_id(identifier)?;
 
                 query_builder.push(" WHERE android_manifest_id=");
                 query_builder.push_bind(manifest_id);
             }
             VerPlatform::Mac => {
-                let uuid = Uuid::parse_str(identifier).map_err(|_| {
-                    DatabaseError::InvalidInput("Identifier is not a valid UUID".to_string())
-                })?;
+                let uuid = parse_uuid(identifier)?;
 
                 query_builder.push(" WHERE mac_arm_uuid=");
                 query_builder.push_bind(uuid);
@@ -48,31 +101,25 @@ impl GDVersionAlias {
                 query_builder.push_bind(uuid);
             }
             VerPlatform::MacArm => {
-                let uuid = Uuid::parse_str(identifier).map_err(|_| {
-                    DatabaseError::InvalidInput("Identifier is not a valid UUID".to_string())
-                })?;
+                let uuid = parse_uuid(identifier)?;
 
                 query_builder.push(" WHERE mac_arm_uuid=");
                 query_builder.push_bind(uuid);
             }
             VerPlatform::MacIntel => {
-                let uuid = Uuid::parse_str(identifier).map_err(|_| {
-                    DatabaseError::InvalidInput("Identifier is not a valid UUID".to_string())
-                })?;
+                let uuid = parse_uuid(identifier)?;
 
                 query_builder.push(" WHERE mac_intel_uuid=");
                 query_builder.push_bind(uuid);
             }
             VerPlatform::Win => {
-                let timestamp = identifier.parse::<i32>().map_err(|_| {
-                    DatabaseError::InvalidInput("Identifier is not a valid timestamp".to_string())
-                })?;
+                let timestamp = parse_timestamp(identifier)?;
 
                 query_builder.push(" WHERE windows_timestamp=");
                 query_builder.push_bind(timestamp);
             }
             VerPlatform::Ios => {
-                let bundle_version = identifier.to_string();
+                let bundle_version = parse_bundle_version(identifier)?;
                 query_builder.push(" WHERE ios_bundle_version=");
                 query_builder.push_bind(bundle_version);
             } // _ => return Err(ApiError::BadRequest("Invalid platform".to_string())),
@@ -82,7 +129,7 @@ impl GDVersionAlias {
         query_builder.push(" ORDER BY added_at DESC LIMIT 1");
 
         query_builder
-            .build_query_scalar::<GDVersionEnum>()
+            .build_query_scalar::<GDVersion>()
             .fetch_optional(&mut *pool)
             .await
             .map_err(|e| e.into())
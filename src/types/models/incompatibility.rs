@@ -1,14 +1,13 @@
 use std::collections::HashMap;
 
-use super::{
-    dependency::ResponseDependency,
-    mod_gd_version::{GDVersionEnum, VerPlatform},
-};
+use super::mod_gd_version::{GDVersion, VerPlatform};
 use crate::database::DatabaseError;
 use crate::types::models::dependency::ModVersionCompare;
-use serde::{Deserialize, Serialize};
 use sqlx::{PgConnection, Postgres};
-use utoipa::ToSchema;
+
+pub use geode_index_types::models::incompatibility::{
+    IncompatibilityImportance, Replacement, ResponseIncompatibility,
+};
 
 #[derive(sqlx::FromRow, Clone, Debug)]
 pub struct FetchedIncompatibility {
@@ -29,35 +28,6 @@ pub struct IncompatibilityCreate {
 #[derive(sqlx::FromRow)]
 pub struct Incompatibility {}
 
-#[derive(Debug, Serialize, Clone, ToSchema)]
-pub struct Replacement {
-    pub id: String,
-    pub version: String,
-    #[serde(skip_serializing)]
-    pub replacement_id: i32,
-    pub download_link: String,
-    pub dependencies: Vec<ResponseDependency>,
-    pub incompatibilities: Vec<ResponseIncompatibility>,
-}
-
-#[derive(sqlx::Type, Debug, Serialize, Clone, Copy, Deserialize, PartialEq, Default, ToSchema)]
-#[sqlx(type_name = "incompatibility_importance", rename_all = "lowercase")]
-#[serde(rename_all = "lowercase")]
-pub enum IncompatibilityImportance {
-    #[default]
-    Breaking,
-    Conflicting,
-    Superseded,
-}
-
-#[derive(Serialize, Debug, Clone, ToSchema)]
-pub struct ResponseIncompatibility {
-    pub mod_id: String,
-    pub version: String,
-    pub importance: IncompatibilityImportance,
-    pub breaking: bool,
-}
-
 impl FetchedIncompatibility {
     pub fn into_response(self) -> ResponseIncompatibility {
         ResponseIncompatibility {
@@ -113,7 +83,7 @@ impl Incompatibility {
     pub async fn get_for_mod_versions(
         ids: &Vec<i32>,
         platform: Option<VerPlatform>,
-        gd: Option<GDVersionEnum>,
+        gd: Option<GDVersion>,
         geode: Option<&semver::Version>,
         pool: &mut PgConnection,
     ) -> Result<HashMap<i32, Vec<FetchedIncompatibility>>, DatabaseError> {
@@ -131,8 +101,9 @@ impl Incompatibility {
             icp.incompatibility_id, icp.mod_id, icp.version FROM incompatibilities icp
             INNER JOIN mod_versions mv ON mv.id = icp.mod_id
             INNER JOIN mod_gd_versions mgv ON mv.id = mgv.mod_id
+            INNER JOIN gd_versions gdv ON gdv.name = mgv.gd
             WHERE mv.id = ANY($1)
-            AND ($2 IS NULL OR mgv.gd = $2)
+            AND ($2 IS NULL OR gdv.sort_order = (SELECT sort_order FROM gd_versions WHERE name = $2))
             AND ($3 IS NULL OR mgv.platform = $3)
             AND ($4 IS NULL OR $4 = mv.geode_major)
             AND ($5 IS NULL OR $5 >= mv.geode_minor)
@@ -1,57 +1,51 @@
+use std::collections::HashMap;
+
 use crate::{
     database::DatabaseError,
-    types::{
-        models::mod_gd_version::{DetailedGDVersion, GDVersionEnum, VerPlatform},
-        serde::chrono_dt_secs,
-    },
+    types::models::mod_gd_version::{DetailedGDVersion, GDVersion, VerPlatform},
 };
 
-use serde::Serialize;
-use utoipa::ToSchema;
-
 use sqlx::{
     PgConnection, Postgres, QueryBuilder,
     types::chrono::{DateTime, Utc},
 };
 
+pub use geode_index_types::models::loader_version::{LoaderAsset, LoaderChannel, LoaderVersion};
+
 #[derive(Debug)]
 pub struct LoaderVersionCreate {
     pub tag: String,
-    pub prerelease: bool,
+    pub channel: LoaderChannel,
     pub commit_hash: String,
-    pub mac: Option<GDVersionEnum>,
-    pub win: Option<GDVersionEnum>,
-    pub android: Option<GDVersionEnum>,
-    pub ios: Option<GDVersionEnum>,
-}
-
-#[derive(Serialize, Debug, ToSchema)]
-pub struct LoaderVersion {
-    pub version: String,
-    pub tag: String,
-    pub gd: DetailedGDVersion,
-    pub prerelease: bool,
-    pub commit_hash: String,
-    #[serde(with = "chrono_dt_secs")]
-    pub created_at: DateTime<Utc>,
+    pub changelog: Option<String>,
+    pub mac: Option<GDVersion>,
+    pub win: Option<GDVersion>,
+    pub android: Option<GDVersion>,
+    pub ios: Option<GDVersion>,
+    /// Defaults to now
+    pub created_at: Option<DateTime<Utc>>,
+    pub assets: Vec<LoaderAsset>,
 }
 
 #[derive(sqlx::FromRow, Debug)]
 pub struct LoaderVersionGetOne {
     pub tag: String,
-    pub prerelease: bool,
+    pub channel: LoaderChannel,
     pub commit_hash: String,
+    pub changelog: Option<String>,
+    pub yanked: bool,
+    pub yank_reason: Option<String>,
     pub created_at: DateTime<Utc>,
-    pub mac: Option<GDVersionEnum>,
-    pub win: Option<GDVersionEnum>,
-    pub android: Option<GDVersionEnum>,
-    pub ios: Option<GDVersionEnum>,
+    pub mac: Option<GDVersion>,
+    pub win: Option<GDVersion>,
+    pub android: Option<GDVersion>,
+    pub ios: Option<GDVersion>,
 }
 
 pub struct GetVersionsQuery {
-    pub gd: Option<GDVersionEnum>,
+    pub gd: Option<GDVersion>,
     pub platform: Option<VerPlatform>,
-    pub prerelease: bool,
+    pub channel: LoaderChannel,
 }
 
 impl LoaderVersionGetOne {
@@ -59,16 +53,21 @@ impl LoaderVersionGetOne {
         LoaderVersion {
             tag: format!("v{}", self.tag),
             version: self.tag,
-            prerelease: self.prerelease,
+            prerelease: self.channel != LoaderChannel::Stable,
+            channel: self.channel,
             created_at: self.created_at,
             commit_hash: self.commit_hash,
+            changelog: self.changelog,
+            assets: vec![],
+            yanked: self.yanked,
+            yank_reason: self.yank_reason,
             gd: DetailedGDVersion {
                 win: self.win,
-                mac: self.mac,
-                mac_arm: self.mac,
+                mac: self.mac.clone(),
+                mac_arm: self.mac.clone(),
                 mac_intel: self.mac,
-                android: self.android,
-                android32: self.android,
+                android: self.android.clone(),
+                android32: self.android.clone(),
                 android64: self.android,
                 ios: self.ios,
             },
@@ -76,21 +75,69 @@ impl LoaderVersionGetOne {
     }
 }
 
-impl LoaderVersion {
-    pub async fn get_latest(
-        gd: Option<GDVersionEnum>,
+/// Queries for [`LoaderVersion`], which lives in the shared types crate
+pub trait LoaderVersionQueries {
+    async fn get_latest(
+        gd: Option<GDVersion>,
         platform: Option<VerPlatform>,
-        accept_prereleases: bool,
+        channel: LoaderChannel,
+        pool: &mut PgConnection,
+    ) -> Result<Option<LoaderVersion>, DatabaseError>;
+
+    async fn get_one(
+        tag: &str,
+        pool: &mut PgConnection,
+    ) -> Result<Option<LoaderVersion>, DatabaseError>;
+
+    async fn get_all(pool: &mut PgConnection) -> Result<Vec<LoaderVersionGetOne>, DatabaseError>;
+
+    async fn create_version(
+        version: LoaderVersionCreate,
+        pool: &mut PgConnection,
+    ) -> Result<(), DatabaseError>;
+
+    async fn set_assets(
+        tag: &str,
+        assets: &[LoaderAsset],
+        pool: &mut PgConnection,
+    ) -> Result<(), DatabaseError>;
+
+    async fn set_yanked(
+        tag: &str,
+        yanked: bool,
+        reason: Option<&str>,
+        pool: &mut PgConnection,
+    ) -> Result<(), DatabaseError>;
+
+    async fn with_assets(
+        versions: Vec<LoaderVersion>,
+        pool: &mut PgConnection,
+    ) -> Result<Vec<LoaderVersion>, DatabaseError>;
+
+    async fn get_many(
+        query: GetVersionsQuery,
+        per_page: i64,
+        page: i64,
+        pool: &mut PgConnection,
+    ) -> Result<Vec<LoaderVersion>, DatabaseError>;
+}
+
+impl LoaderVersionQueries for LoaderVersion {
+    async fn get_latest(
+        gd: Option<GDVersion>,
+        platform: Option<VerPlatform>,
+        channel: LoaderChannel,
         pool: &mut PgConnection,
     ) -> Result<Option<LoaderVersion>, DatabaseError> {
         let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
             r#"SELECT
-                mac, win, android, ios, tag, commit_hash, created_at, prerelease
+                mac, win, android, ios, tag, commit_hash, created_at,
+                channel, changelog, yanked, yank_reason
                 FROM geode_versions
             "#,
         );
 
-        match (platform, gd) {
+        match (platform, gd.clone()) {
             (Some(p), Some(g)) => {
                 match p {
                     VerPlatform::Android | VerPlatform::Android32 | VerPlatform::Android64 => {
@@ -112,105 +159,230 @@ impl LoaderVersion {
             }
             (None, Some(g)) => {
                 query_builder.push(" WHERE (android=");
-                query_builder.push_bind(g);
+                query_builder.push_bind(g.clone());
                 query_builder.push(" or mac=");
-                query_builder.push_bind(g);
+                query_builder.push_bind(g.clone());
                 query_builder.push(" or win=");
-                query_builder.push_bind(g);
+                query_builder.push_bind(g.clone());
                 query_builder.push(" or ios=");
                 query_builder.push_bind(g);
                 query_builder.push(')');
             }
             (None, None) => {
-                // if gd version isn't specifed, select whatever versions have the latest gd version
-                query_builder.push(
-                    r#" WHERE
-                    (android=enum_last(NULL::gd_version) OR
-                    win=enum_last(NULL::gd_version) OR
-                    mac=enum_last(NULL::gd_version) OR
-                    ios=enum_last(NULL::gd_version))
-                    "#,
-                );
+                // if gd version isn't specifed, this is handled by ordering too
+                query_builder.push(" WHERE 1=1");
             }
         }
 
-        if !accept_prereleases {
-            query_builder.push(" AND prerelease=FALSE ");
-        }
+        query_builder.push(" AND yanked=FALSE AND channel <= ");
+        query_builder.push_bind(channel);
 
         query_builder.push(" ORDER BY ");
 
         if gd.is_none() {
             if let Some(p) = platform {
                 // if there's a platform but no gd, order by the latest gd for that platform
+                query_builder.push(" (SELECT g.sort_order FROM gd_versions g WHERE g.name = ");
                 match p {
                     VerPlatform::Android | VerPlatform::Android32 | VerPlatform::Android64 => {
-                        query_builder.push(" android")
+                        query_builder.push("android")
                     }
                     VerPlatform::Mac | VerPlatform::MacIntel | VerPlatform::MacArm => {
-                        query_builder.push(" mac")
+                        query_builder.push("mac")
                     }
-                    VerPlatform::Win => query_builder.push(" win"),
-                    VerPlatform::Ios => query_builder.push(" ios"),
+                    VerPlatform::Win => query_builder.push("win"),
+                    VerPlatform::Ios => query_builder.push("ios"),
                     // _ => return Err(ApiError::BadRequest("Invalid platform".to_string())),
                 };
-                query_builder.push(" DESC, ");
+                query_builder.push(") DESC NULLS LAST, ");
+            } else {
+                // otherwise pick whatever versions support the latest gd version on any platform
+                query_builder.push(
+                    r#" (SELECT MAX(g.sort_order) FROM gd_versions g
+                    WHERE g.name IN (android, win, mac, ios)) DESC NULLS LAST, "#,
+                );
             }
         }
 
         query_builder.push(" created_at DESC LIMIT 1;");
 
-        query_builder
+        let Some(version) = query_builder
             .build_query_as::<LoaderVersionGetOne>()
             .fetch_optional(&mut *pool)
-            .await
-            .map_err(|e| e.into())
-            .map(|x| x.map(|y| y.into_loader_version()))
+            .await?
+            .map(|x| x.into_loader_version())
+        else {
+            return Ok(None);
+        };
+
+        Ok(LoaderVersion::with_assets(vec![version], pool).await?.pop())
     }
 
-    pub async fn get_one(
+    async fn get_one(
         tag: &str,
         pool: &mut PgConnection,
     ) -> Result<Option<LoaderVersion>, DatabaseError> {
-        sqlx::query_as!(
+        let version = sqlx::query_as!(
             LoaderVersionGetOne,
             r#"SELECT
-				        mac as "mac: _", win as "win: _", android as "android: _", ios as "ios: _",
-				        tag, created_at, commit_hash, prerelease
-			      FROM geode_versions
-				    WHERE tag = $1"#,
+    				        mac as "mac: _", win as "win: _", android as "android: _", ios as "ios: _",
+    				        tag, created_at, commit_hash, channel as "channel: _",
+    				        changelog, yanked, yank_reason
+    			      FROM geode_versions
+    				    WHERE tag = $1"#,
             tag
         )
         .fetch_optional(&mut *pool)
+        .await?
+        .map(|x| x.into_loader_version());
+
+        match version {
+            Some(v) => Ok(LoaderVersion::with_assets(vec![v], pool).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    /// Every loader version, newest first
+    async fn get_all(pool: &mut PgConnection) -> Result<Vec<LoaderVersionGetOne>, DatabaseError> {
+        sqlx::query_as!(
+            LoaderVersionGetOne,
+            r#"SELECT
+                mac as "mac: _", win as "win: _", android as "android: _", ios as "ios: _",
+                tag, created_at, commit_hash, channel as "channel: _",
+                changelog, yanked, yank_reason
+            FROM geode_versions
+            ORDER BY created_at DESC"#
+        )
+        .fetch_all(&mut *pool)
         .await
+        .inspect_err(|e| log::error!("LoaderVersion::get_all failed: {e}"))
         .map_err(|e| e.into())
-        .map(|x| x.map(|y| y.into_loader_version()))
     }
 
-    pub async fn create_version(
+    async fn create_version(
         version: LoaderVersionCreate,
         pool: &mut PgConnection,
     ) -> Result<(), DatabaseError> {
         sqlx::query!(
             r#"INSERT INTO geode_versions
-                (tag, prerelease, mac, win, android, ios, commit_hash)
+                (tag, channel, mac, win, android, ios, commit_hash, created_at, changelog)
             VALUES
-                ($1, $2, $3, $4, $5, $6, $7)"#,
+                ($1, $2, $3, $4, $5, $6, $7, COALESCE($8, CURRENT_TIMESTAMP), $9)"#,
             version.tag,
-            version.prerelease,
+            version.channel as LoaderChannel,
             version.mac as _,
             version.win as _,
             version.android as _,
             version.ios as _,
-            version.commit_hash
+            version.commit_hash,
+            version.created_at,
+            version.changelog
+        )
+        .execute(&mut *pool)
+        .await?;
+
+        LoaderVersion::set_assets(&version.tag, &version.assets, pool).await
+    }
+
+    /// Replaces the assets of a loader version
+    async fn set_assets(
+        tag: &str,
+        assets: &[LoaderAsset],
+        pool: &mut PgConnection,
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!("DELETE FROM geode_version_assets WHERE tag = $1", tag)
+            .execute(&mut *pool)
+            .await
+            .inspect_err(|e| log::error!("LoaderVersion::set_assets failed: {e}"))?;
+
+        if assets.is_empty() {
+            return Ok(());
+        }
+
+        let platforms: Vec<VerPlatform> = assets.iter().map(|a| a.platform).collect();
+        let urls: Vec<String> = assets.iter().map(|a| a.url.clone()).collect();
+        let sizes: Vec<i64> = assets.iter().map(|a| a.size).collect();
+        let hashes: Vec<String> = assets.iter().map(|a| a.sha256.clone()).collect();
+
+        sqlx::query!(
+            "INSERT INTO geode_version_assets (tag, platform, url, size, sha256)
+            SELECT $1, * FROM UNNEST(
+                $2::gd_ver_platform[],
+                $3::text[],
+                $4::int8[],
+                $5::text[]
+            )",
+            tag,
+            &platforms as &[VerPlatform],
+            &urls,
+            &sizes,
+            &hashes
+        )
+        .execute(&mut *pool)
+        .await
+        .inspect_err(|e| log::error!("LoaderVersion::set_assets failed: {e}"))
+        .map(|_| ())
+        .map_err(|e| e.into())
+    }
+
+    /// Yanked versions are skipped when looking up the latest version
+    async fn set_yanked(
+        tag: &str,
+        yanked: bool,
+        reason: Option<&str>,
+        pool: &mut PgConnection,
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            "UPDATE geode_versions SET yanked = $2, yank_reason = $3 WHERE tag = $1",
+            tag,
+            yanked,
+            reason
         )
         .execute(&mut *pool)
         .await
+        .inspect_err(|e| log::error!("LoaderVersion::set_yanked failed: {e}"))
         .map(|_| ())
         .map_err(|e| e.into())
     }
 
-    pub async fn get_many(
+    async fn with_assets(
+        mut versions: Vec<LoaderVersion>,
+        pool: &mut PgConnection,
+    ) -> Result<Vec<LoaderVersion>, DatabaseError> {
+        if versions.is_empty() {
+            return Ok(versions);
+        }
+
+        let tags: Vec<String> = versions.iter().map(|v| v.version.clone()).collect();
+        let rows = sqlx::query!(
+            r#"SELECT tag, platform as "platform: VerPlatform", url, size, sha256
+            FROM geode_version_assets
+            WHERE tag = ANY($1)
+            ORDER BY platform"#,
+            &tags
+        )
+        .fetch_all(&mut *pool)
+        .await
+        .inspect_err(|e| log::error!("LoaderVersion::with_assets failed: {e}"))?;
+
+        let mut assets: HashMap<String, Vec<LoaderAsset>> = HashMap::new();
+        for row in rows {
+            assets.entry(row.tag).or_default().push(LoaderAsset {
+                platform: row.platform,
+                url: row.url,
+                size: row.size,
+                sha256: row.sha256,
+            });
+        }
+
+        for version in &mut versions {
+            version.assets = assets.remove(&version.version).unwrap_or_default();
+        }
+
+        Ok(versions)
+    }
+
+    async fn get_many(
         query: GetVersionsQuery,
         per_page: i64,
         page: i64,
@@ -222,7 +394,8 @@ impl LoaderVersion {
         let mut query_builder = QueryBuilder::new(
             r#"
             SELECT 
-                mac, win, android, ios, tag, created_at, commit_hash, prerelease
+                mac, win, android, ios, tag, created_at, commit_hash,
+                channel, changelog, yanked, yank_reason
             FROM geode_versions
             "#,
         );
@@ -258,11 +431,11 @@ impl LoaderVersion {
             }
             (None, Some(g)) => {
                 query_builder.push(" WHERE (((((((((android=");
-                query_builder.push_bind(g);
+                query_builder.push_bind(g.clone());
                 query_builder.push(" or mac=");
-                query_builder.push_bind(g);
+                query_builder.push_bind(g.clone());
                 query_builder.push(" or win=");
-                query_builder.push_bind(g);
+                query_builder.push_bind(g.clone());
                 query_builder.push(" or ios=");
                 query_builder.push_bind(g);
                 query_builder.push(')');
@@ -272,9 +445,8 @@ impl LoaderVersion {
             }
         }
 
-        if !query.prerelease {
-            query_builder.push(" AND prerelease=FALSE ");
-        }
+        query_builder.push(" AND channel <= ");
+        query_builder.push_bind(query.channel);
 
         query_builder.push(" ORDER BY created_at DESC ");
 
@@ -283,11 +455,14 @@ impl LoaderVersion {
         query_builder.push(" OFFSET ");
         query_builder.push_bind(offset);
 
-        query_builder
+        let versions = query_builder
             .build_query_as::<LoaderVersionGetOne>()
             .fetch_all(&mut *pool)
-            .await
-            .map(|x| x.into_iter().map(|y| y.into_loader_version()).collect())
-            .map_err(|e| e.into())
+            .await?
+            .into_iter()
+            .map(|x| x.into_loader_version())
+            .collect();
+
+        LoaderVersion::with_assets(versions, pool).await
     }
 }
@@ -1,15 +1,23 @@
+pub mod changelog;
 pub mod dependency;
-pub mod download_count;
 pub mod developer;
+pub mod download_flags;
+pub mod download_stats;
 pub mod github_login_attempt;
 pub mod incompatibility;
 pub mod mod_entity;
 pub mod mod_gd_version;
 pub mod mod_link;
+pub mod mod_logo;
+pub mod mod_media;
 pub mod mod_version;
+pub mod mod_version_file;
 pub mod mod_version_status;
 pub mod stats;
 pub mod tag;
 pub mod loader_version;
 pub mod gd_version_alias;
 pub mod deprecations;
+pub mod session;
+pub mod mod_transfer;
+pub mod organization;
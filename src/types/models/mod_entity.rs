@@ -1,14 +1,14 @@
 use super::{
-    dependency::ResponseDependency,
-    developer::ModDeveloper,
-    download_count::DownloadCount,
-    incompatibility::{Replacement, ResponseIncompatibility},
-    mod_gd_version::{DetailedGDVersion, GDVersionEnum, ModGDVersion, VerPlatform},
-    mod_link::ModLinks,
+    developer::{ModDeveloper, ModDeveloperRole},
+    mod_gd_version::{DetailedGDVersion, GDVersion, ModGDVersion, VerPlatform},
+    mod_link::{self, ModLinks},
     tag::Tag,
 };
 use crate::{
-    database::{DatabaseError, repository::developers},
+    database::{
+        DatabaseError,
+        repository::{developers, organizations},
+    },
     endpoints::ApiError,
 };
 use crate::{
@@ -18,52 +18,23 @@ use crate::{
     },
     types::{
         api::PaginatedData,
-        models::{mod_version::ModVersion, mod_version_status::ModVersionStatusEnum},
-        serde::chrono_dt_secs,
+        models::{
+            mod_version::{ModVersion, ModVersionQueries},
+            mod_version_status::ModVersionStatusEnum,
+        },
     },
 };
 use semver::Version;
-use serde::Serialize;
 use sqlx::{
     PgConnection,
     types::chrono::{DateTime, Utc},
 };
 use std::collections::HashMap;
-use utoipa::ToSchema;
-
-#[derive(Serialize, Debug, Clone, sqlx::FromRow, ToSchema)]
-pub struct Mod {
-    pub id: String,
-    pub repository: Option<String>,
-    pub featured: bool,
-    #[schema(value_type = i32)]
-    pub download_count: DownloadCount,
-    pub developers: Vec<ModDeveloper>,
-    pub versions: Vec<ModVersion>,
-    pub tags: Vec<String>,
-    pub about: Option<String>,
-    pub changelog: Option<String>,
-    #[serde(with = "chrono_dt_secs")]
-    pub created_at: DateTime<Utc>,
-    #[serde(with = "chrono_dt_secs")]
-    pub updated_at: DateTime<Utc>,
-    pub links: Option<ModLinks>,
-}
 
-#[derive(Serialize, Debug, ToSchema)]
-pub struct ModUpdate {
-    pub id: String,
-    pub version: String,
-    #[serde(skip_serializing)]
-    pub mod_version_id: i32,
-    pub download_link: String,
-    pub replacement: Option<Replacement>,
-    pub dependencies: Vec<ResponseDependency>,
-    pub incompatibilities: Vec<ResponseIncompatibility>,
-}
+pub use geode_index_types::models::mod_entity::{ContentFormat, Mod, ModUpdate};
 
 #[derive(Debug, sqlx::FromRow)]
-struct ModRecord {
+pub(crate) struct ModRecord {
     id: String,
     #[sqlx(default)]
     repository: Option<String>,
@@ -108,15 +79,52 @@ pub struct ModStats {
     pub total_downloads: i64,
 }
 
-impl Mod {
-    pub fn set_abbreviated_download_counts(&mut self, abbreviate: bool) {
-        self.download_count.set_abbreviated(abbreviate);
-        for version in &mut self.versions {
-            version.set_abbreviated_download_count(abbreviate);
-        }
-    }
+/// Queries for [`Mod`], which lives in the shared types crate
+pub trait ModQueries {
+    async fn get_stats(pool: &mut PgConnection) -> Result<ModStats, DatabaseError>;
+
+    async fn get_index(
+        pool: &mut PgConnection,
+        query: &IndexQueryParams,
+    ) -> Result<PaginatedData<Mod>, ApiError>;
+
+    async fn get_pending(
+        records: Vec<ModRecord>,
+        total_count: i64,
+        pool: &mut PgConnection,
+    ) -> Result<PaginatedData<Mod>, ApiError>;
 
-    pub async fn get_stats(pool: &mut PgConnection) -> Result<ModStats, DatabaseError> {
+    async fn get_all_for_dev(
+        id: i32,
+        status: ModVersionStatusEnum,
+        only_owner: bool,
+        min_role: Option<ModDeveloperRole>,
+        pool: &mut PgConnection,
+    ) -> Result<Vec<SimpleDevMod>, DatabaseError>;
+
+    async fn get_one(
+        id: &str,
+        only_accepted: bool,
+        pool: &mut PgConnection,
+    ) -> Result<Option<Mod>, DatabaseError>;
+
+    async fn update_mod(
+        id: &str,
+        featured: bool,
+        pool: &mut PgConnection,
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_updates(
+        ids: &[String],
+        platforms: VerPlatform,
+        geode: &semver::Version,
+        gd: GDVersion,
+        pool: &mut PgConnection,
+    ) -> Result<Vec<ModUpdate>, DatabaseError>;
+}
+
+impl ModQueries for Mod {
+    async fn get_stats(pool: &mut PgConnection) -> Result<ModStats, DatabaseError> {
         let result = sqlx::query!(
             "
             SELECT COUNT(id) as id_count, SUM(download_count) as download_sum
@@ -149,7 +157,7 @@ impl Mod {
         }
     }
 
-    pub async fn get_index(
+    async fn get_index(
         pool: &mut PgConnection,
         query: &IndexQueryParams,
     ) -> Result<PaginatedData<Mod>, ApiError> {
@@ -184,6 +192,19 @@ impl Mod {
             None => None,
         };
 
+        let organization = match &query.organization {
+            Some(o) => match organizations::get_one_by_name(o, pool).await? {
+                Some(o) => Some(o),
+                None => {
+                    return Ok(PaginatedData {
+                        data: vec![],
+                        count: 0,
+                    });
+                }
+            },
+            None => None,
+        };
+
         let order = match query.sort {
             IndexSortType::Downloads => "q.download_count DESC",
             IndexSortType::RecentlyUpdated => "q.updated_at DESC",
@@ -191,6 +212,15 @@ impl Mod {
             IndexSortType::Oldest => "q.created_at ASC",
             IndexSortType::Name => "q.name ASC",
             IndexSortType::NameReverse => "q.name DESC",
+            // scores are filled in by the refresh-trending job
+            IndexSortType::Trending => {
+                "(SELECT mts.trending_score FROM mod_trending_scores mts WHERE mts.mod_id = q.id)
+                    DESC NULLS LAST, q.download_count DESC"
+            }
+            IndexSortType::PopularThisWeek => {
+                "(SELECT mts.weekly_downloads FROM mod_trending_scores mts WHERE mts.mod_id = q.id)
+                    DESC NULLS LAST, q.download_count DESC"
+            }
         };
 
         let geode = query
@@ -217,7 +247,7 @@ impl Mod {
             }
         });
 
-        let gd = query.gd.map(|x| vec![x, GDVersionEnum::All]);
+        let gd = query.gd.clone().map(|x| vec![x, GDVersion::all()]);
 
         let core_query = |builder: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>| {
             // clone these due to silly lifetime rules, the closure lives till the
@@ -238,6 +268,10 @@ impl Mod {
                 builder.push(" INNER JOIN mod_gd_versions mgv ON mgv.mod_id = mv.id ");
             }
 
+            if gd.is_some() {
+                builder.push(" INNER JOIN gd_versions gdv ON gdv.name = mgv.gd ");
+            }
+
             if tags.is_some() {
                 builder.push(" LEFT JOIN mods_mod_tags mmt ON mmt.mod_id = m.id ");
             }
@@ -264,6 +298,10 @@ impl Mod {
                 builder.push(" AND md.developer_id = ").push_bind(d.id);
             }
 
+            if let Some(o) = &organization {
+                builder.push(" AND m.organization_id = ").push_bind(o.id);
+            }
+
             builder.push(" AND mvs.status = ").push_bind(status);
 
             if let Some(rp) = requires_patching {
@@ -288,7 +326,10 @@ impl Mod {
             }
 
             if let Some(g) = gd {
-                builder.push(" AND mgv.gd = ANY(").push_bind(g).push(") ");
+                builder
+                    .push(" AND gdv.sort_order IN (SELECT sort_order FROM gd_versions WHERE name = ANY(")
+                    .push_bind(g)
+                    .push(")) ");
             }
 
             if let Some(p) = platforms {
@@ -390,14 +431,15 @@ impl Mod {
         let mut versions = ModVersion::get_latest_for_mods(
             pool,
             &ids,
-            query.gd,
+            query.gd.clone(),
             platforms.as_deref(),
             geode.as_ref(),
             requires_patching,
         )
         .await?;
         let mut developers = developers::get_all_for_mods(&ids, pool).await?;
-        let links = ModLinks::fetch_for_mods(&ids, pool).await?;
+        let mut organizations = organizations::get_for_mods(&ids, pool).await?;
+        let links = mod_link::fetch_for_mods(&ids, pool).await?;
         let mod_version_ids: Vec<i32> = versions
             .iter()
             .map(|(_, mod_version)| mod_version.id)
@@ -415,6 +457,7 @@ impl Mod {
                 let devs = developers.remove(&x.id).unwrap_or_default();
                 let tags = tags.remove(&x.id).unwrap_or_default();
                 let links = links.iter().find(|link| link.mod_id == x.id).cloned();
+                let organization = organizations.remove(&x.id);
 
                 Some(Mod {
                     id: x.id,
@@ -429,6 +472,8 @@ impl Mod {
                     about: None,
                     changelog: None,
                     links,
+                    organization,
+                    media: vec![],
                 })
             })
             .collect();
@@ -443,7 +488,8 @@ impl Mod {
         let ids: Vec<_> = records.iter().map(|x| x.id.clone()).collect();
         let versions = ModVersion::get_pending_for_mods(&ids, pool).await?;
         let developers = developers::get_all_for_mods(&ids, pool).await?;
-        let links = ModLinks::fetch_for_mods(&ids, pool).await?;
+        let organizations = organizations::get_for_mods(&ids, pool).await?;
+        let links = mod_link::fetch_for_mods(&ids, pool).await?;
         let mut mod_version_ids: Vec<i32> = vec![];
         for (_, mod_version) in versions.iter() {
             mod_version_ids.append(&mut mod_version.iter().map(|x| x.id).collect());
@@ -476,6 +522,8 @@ impl Mod {
                     about: x.about,
                     changelog: x.changelog,
                     links,
+                    organization: organizations.get(&x.id).cloned(),
+                    media: vec![],
                 }
             })
             .collect::<Vec<Mod>>();
@@ -486,10 +534,11 @@ impl Mod {
         })
     }
 
-    pub async fn get_all_for_dev(
+    async fn get_all_for_dev(
         id: i32,
         status: ModVersionStatusEnum,
         only_owner: bool,
+        min_role: Option<ModDeveloperRole>,
         pool: &mut PgConnection,
     ) -> Result<Vec<SimpleDevMod>, DatabaseError> {
         struct Record {
@@ -516,16 +565,32 @@ impl Mod {
                 ) as "validated!: _"
             FROM mods m
             INNER JOIN mod_versions mv ON m.id = mv.mod_id
-            INNER JOIN mods_developers md ON md.mod_id = m.id
+            INNER JOIN (
+                -- same access rules as developers::has_mod_role and developers::owns_mod
+                SELECT a.mod_id, bool_or(a.is_owner) as is_owner, bool_or(a.has_role) as has_role
+                FROM (
+                    SELECT md.mod_id, md.is_owner,
+                        ($4::mod_developer_role IS NULL OR md.is_owner OR md.role >= $4) as has_role
+                    FROM mods_developers md
+                    WHERE md.developer_id = $1
+                    UNION ALL
+                    SELECT om_m.id, om.role >= 'admin', true
+                    FROM organization_members om
+                    INNER JOIN mods om_m ON om_m.organization_id = om.organization_id
+                    WHERE om.developer_id = $1
+                ) a
+                GROUP BY a.mod_id
+            ) access ON access.mod_id = m.id
             INNER JOIN mod_version_statuses mvs ON mvs.mod_version_id = mv.id
-            WHERE md.developer_id = $1
-            AND mvs.status = $2
-            AND ($3 = false OR md.is_owner = true)
+            WHERE mvs.status = $2
+            AND ($3 = false OR access.is_owner)
+            AND access.has_role
             ORDER BY m.created_at DESC, mv.id DESC
             "#,
             id,
             status as ModVersionStatusEnum,
-            only_owner
+            only_owner,
+            min_role as Option<ModDeveloperRole>
         )
         .fetch_all(&mut *pool)
         .await
@@ -572,7 +637,7 @@ impl Mod {
         Ok(mods)
     }
 
-    pub async fn get_one(
+    async fn get_one(
         id: &str,
         only_accepted: bool,
         pool: &mut PgConnection,
@@ -644,7 +709,8 @@ impl Mod {
             ModGDVersion::get_for_mod_versions(&ids, pool).await?;
         let tags: Vec<String> = Tag::get_tags_for_mod(id, pool).await?;
         let devs: Vec<ModDeveloper> = developers::get_all_for_mod(id, pool).await?;
-        let links: Option<ModLinks> = ModLinks::fetch(id, pool).await?;
+        let links: Option<ModLinks> = mod_link::fetch(id, pool).await?;
+        let organization = organizations::get_for_mod(id, pool).await?;
 
         for i in &mut versions {
             let gd_versions = gd.get(&i.id).cloned().unwrap_or_default();
@@ -664,13 +730,15 @@ impl Mod {
             about: records[0].about.clone(),
             changelog: records[0].changelog.clone(),
             links,
+            organization,
+            media: vec![],
         };
         Ok(Some(mod_entity))
     }
 
     /// At the moment this is only used to set the mod to featured.
     /// DOES NOT check if the mod exists
-    pub async fn update_mod(
+    async fn update_mod(
         id: &str,
         featured: bool,
         pool: &mut PgConnection,
@@ -683,11 +751,11 @@ impl Mod {
             .map(|_| ())
     }
 
-    pub async fn get_updates(
+    async fn get_updates(
         ids: &[String],
         platforms: VerPlatform,
         geode: &semver::Version,
-        gd: GDVersionEnum,
+        gd: GDVersion,
         pool: &mut PgConnection,
     ) -> Result<Vec<ModUpdate>, DatabaseError> {
         #[derive(sqlx::FromRow)]
@@ -716,9 +784,10 @@ impl Mod {
                 INNER JOIN mod_versions mv ON mv.mod_id = m.id
                 INNER JOIN mod_version_statuses mvs ON mvs.mod_version_id = mv.id
                 INNER JOIN mod_gd_versions mgv ON mv.id = mgv.mod_id
+                INNER JOIN gd_versions gdv ON gdv.name = mgv.gd
                 WHERE mvs.status = 'accepted'
                 AND mgv.platform = $1
-                AND (mgv.gd = $2 OR mgv.gd = '*')
+                AND gdv.sort_order IN (SELECT sort_order FROM gd_versions WHERE name IN ($2, '*'))
                 AND m.id = ANY($3)
                 AND $4 = mv.geode_major
                 AND $5 >= mv.geode_minor
@@ -740,7 +809,7 @@ impl Mod {
             ) q
             WHERE q.rn = 1",
             platforms as VerPlatform,
-            gd as GDVersionEnum,
+            gd as GDVersion,
             ids,
             i32::try_from(geode.major).unwrap_or_default(),
             i32::try_from(geode.minor).unwrap_or_default(),
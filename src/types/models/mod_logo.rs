@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema, sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[sqlx(type_name = "logo_format", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum LogoFormat {
+    #[default]
+    Png,
+    Webp,
+}
+
+impl LogoFormat {
+    pub const ALL: [LogoFormat; 2] = [LogoFormat::Png, LogoFormat::Webp];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LogoFormat::Png => "png",
+            LogoFormat::Webp => "webp",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            LogoFormat::Png => "image/png",
+            LogoFormat::Webp => "image/webp",
+        }
+    }
+}
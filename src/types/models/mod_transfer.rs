@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(sqlx::Type, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase", type_name = "mod_transfer_status")]
+pub enum ModTransferStatus {
+    Pending,
+    Accepted,
+    Rejected,
+    Cancelled,
+    /// Done by an admin without the recipient having to accept
+    Forced,
+}
+
+#[derive(sqlx::FromRow, Serialize, Debug, Clone, ToSchema)]
+pub struct ModTransfer {
+    pub id: i32,
+    pub mod_id: String,
+    /// Owner at the time the transfer was created, if any
+    pub from_developer_id: Option<i32>,
+    pub to_developer_id: i32,
+    pub initiated_by: Option<i32>,
+    pub status: ModTransferStatus,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
@@ -1,64 +1,21 @@
 use super::{
-    dependency::{Dependency, ModVersionCompare, ResponseDependency},
-    download_count::DownloadCount,
-    developer::ModDeveloper,
-    incompatibility::{Incompatibility, ResponseIncompatibility},
-    mod_gd_version::{DetailedGDVersion, GDVersionEnum, ModGDVersion, VerPlatform},
+    dependency::{Dependency, ModVersionCompare},
+    incompatibility::Incompatibility,
+    mod_gd_version::{DetailedGDVersion, GDVersion, ModGDVersion, VerPlatform},
     mod_version_status::ModVersionStatusEnum,
     tag::Tag,
 };
 use crate::database::DatabaseError;
 use crate::database::repository::developers;
-use crate::types::{
-    api::{PaginatedData, create_download_link},
-    serde::chrono_dt_secs,
-};
+use crate::types::api::PaginatedData;
 use semver::Version;
-use serde::Serialize;
-use utoipa::ToSchema;
 use sqlx::{
     PgConnection, Postgres, QueryBuilder,
     types::chrono::{DateTime, Utc},
 };
 use std::collections::HashMap;
 
-#[derive(Serialize, Debug, sqlx::FromRow, Clone, ToSchema)]
-pub struct ModVersion {
-    #[serde(skip_serializing)]
-    pub id: i32,
-    pub name: String,
-    pub description: Option<String>,
-    pub version: String,
-    pub download_link: String,
-    pub hash: String,
-    pub geode: String,
-    #[schema(value_type = i32)]
-    pub download_count: DownloadCount,
-    pub early_load: bool,
-    pub requires_patching: bool,
-    pub api: bool,
-    pub mod_id: String,
-    pub gd: DetailedGDVersion,
-    pub status: ModVersionStatusEnum,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub dependencies: Option<Vec<ResponseDependency>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub incompatibilities: Option<Vec<ResponseIncompatibility>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub developers: Option<Vec<ModDeveloper>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tags: Option<Vec<String>>,
-    #[serde(with = "chrono_dt_secs::option")]
-    pub created_at: Option<DateTime<Utc>>,
-    #[serde(with = "chrono_dt_secs::option")]
-    pub updated_at: Option<DateTime<Utc>>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    /// Admin/developer only - Reason given to status
-    pub info: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    /// Admin/developer only - Direct download to mod
-    pub direct_download_link: Option<String>,
-}
+pub use geode_index_types::models::mod_version::ModVersion;
 
 #[derive(sqlx::FromRow)]
 struct ModVersionGetOne {
@@ -85,7 +42,7 @@ pub struct IndexQuery {
     pub mod_id: String,
     pub page: i64,
     pub per_page: i64,
-    pub gd: Option<GDVersionEnum>,
+    pub gd: Option<GDVersion>,
     pub compare: Option<(semver::Version, ModVersionCompare)>,
     pub platforms: Vec<VerPlatform>,
     pub status: ModVersionStatusEnum,
@@ -129,27 +86,51 @@ impl ModVersionGetOne {
     }
 }
 
-impl ModVersion {
-    pub fn set_abbreviated_download_count(&mut self, abbreviate: bool) {
-        self.download_count.set_abbreviated(abbreviate);
-    }
+/// Queries for [`ModVersion`], which lives in the shared types crate
+pub trait ModVersionQueries {
+    async fn get_index(
+        query: IndexQuery,
+        pool: &mut PgConnection,
+    ) -> Result<PaginatedData<ModVersion>, DatabaseError>;
 
-    fn modify_download_link(&mut self, app_url: &str) {
-        self.download_link = create_download_link(app_url, &self.mod_id, &self.version)
-    }
+    async fn get_latest_for_mods(
+        pool: &mut PgConnection,
+        ids: &[String],
+        gd: Option<GDVersion>,
+        platforms: Option<&[VerPlatform]>,
+        geode: Option<&semver::Version>,
+        requires_patching: Option<bool>,
+    ) -> Result<HashMap<String, ModVersion>, DatabaseError>;
 
-    pub fn modify_metadata(&mut self, app_url: &str, keep_information: bool) {
-        if keep_information {
-            self.direct_download_link = Some(self.download_link.clone());
-        } else {
-            self.direct_download_link = None;
-            self.info = None;
-        }
+    async fn get_pending_for_mods(
+        ids: &[String],
+        pool: &mut PgConnection,
+    ) -> Result<HashMap<String, Vec<ModVersion>>, DatabaseError>;
 
-        self.modify_download_link(app_url)
-    }
+    async fn get_latest_for_mod(
+        id: &str,
+        gd: Option<GDVersion>,
+        platforms: Vec<VerPlatform>,
+        major: Option<u32>,
+        pool: &mut PgConnection,
+    ) -> Result<Option<ModVersion>, DatabaseError>;
+
+    async fn get_one(
+        id: &str,
+        version: &str,
+        fetch_extras: bool,
+        fetch_only_accepted: bool,
+        pool: &mut PgConnection,
+    ) -> Result<Option<ModVersion>, DatabaseError>;
+
+    async fn get_accepted_count(
+        mod_id: &str,
+        pool: &mut PgConnection,
+    ) -> Result<i64, DatabaseError>;
+}
 
-    pub async fn get_index(
+impl ModVersionQueries for ModVersion {
+    async fn get_index(
         query: IndexQuery,
         pool: &mut PgConnection,
     ) -> Result<PaginatedData<ModVersion>, DatabaseError> {
@@ -165,6 +146,7 @@ impl ModVersion {
             FROM mod_versions mv
             INNER JOIN mod_version_statuses mvs ON mvs.mod_version_id = mv.id
             INNER JOIN mod_gd_versions mgv ON mgv.mod_id = mv.id
+            INNER JOIN gd_versions gdv ON gdv.name = mgv.gd
             "#,
         );
         let mut counter_q: QueryBuilder<Postgres> = QueryBuilder::new(
@@ -173,6 +155,7 @@ impl ModVersion {
             FROM mod_versions mv
             INNER JOIN mod_version_statuses mvs ON mvs.mod_version_id = mv.id
             INNER JOIN mod_gd_versions mgv ON mgv.mod_id = mv.id
+            INNER JOIN gd_versions gdv ON gdv.name = mgv.gd
             "#,
         );
         let sql = "WHERE mv.mod_id = ";
@@ -188,18 +171,18 @@ impl ModVersion {
         q.push(" ");
         counter_q.push(" ");
         if let Some(gd) = query.gd {
-            let sql = "AND (mgv.gd = ";
+            let sql = "AND gdv.sort_order IN (SELECT sort_order FROM gd_versions WHERE name IN (";
             q.push(sql);
             counter_q.push(sql);
-            q.push_bind(gd);
+            q.push_bind(gd.clone());
             counter_q.push_bind(gd);
-            let sql = " OR mgv.gd = ";
+            let sql = ", ";
             q.push(sql);
             counter_q.push(sql);
-            q.push_bind(GDVersionEnum::All);
-            counter_q.push_bind(GDVersionEnum::All);
-            q.push(" ");
-            counter_q.push(" ");
+            q.push_bind(GDVersion::all());
+            counter_q.push_bind(GDVersion::all());
+            q.push(")) ");
+            counter_q.push(")) ");
         }
         if !query.platforms.is_empty() {
             let sql = "AND mgv.platform IN (";
@@ -299,10 +282,10 @@ impl ModVersion {
         Ok(PaginatedData { data: ret, count })
     }
 
-    pub async fn get_latest_for_mods(
+    async fn get_latest_for_mods(
         pool: &mut PgConnection,
         ids: &[String],
-        gd: Option<GDVersionEnum>,
+        gd: Option<GDVersion>,
         platforms: Option<&[VerPlatform]>,
         geode: Option<&semver::Version>,
         requires_patching: Option<bool>,
@@ -311,7 +294,7 @@ impl ModVersion {
             return Ok(Default::default());
         }
 
-        let gd_vec = gd.map(|x| vec![GDVersionEnum::All, x]);
+        let gd_vec = gd.map(|x| vec![GDVersion::all(), x]);
 
         sqlx::query_as(
             "SELECT
@@ -331,8 +314,9 @@ impl ModVersion {
                 INNER JOIN mod_versions mv ON m.id = mv.mod_id
                 INNER JOIN mod_version_statuses mvs ON mvs.mod_version_id = mv.id
                 INNER JOIN mod_gd_versions mgv ON mgv.mod_id = mv.id
+                INNER JOIN gd_versions gdv ON gdv.name = mgv.gd
                 WHERE mvs.status = 'accepted'
-                AND ($1 IS NULL OR mgv.gd = ANY($1))
+                AND ($1 IS NULL OR gdv.sort_order IN (SELECT sort_order FROM gd_versions WHERE name = ANY($1)))
                 AND ($2 IS NULL OR mgv.platform = ANY($2))
                 AND m.id = ANY($3)
                 AND ($8 IS NULL OR mv.requires_patching = $8)
@@ -380,7 +364,7 @@ impl ModVersion {
         })
     }
 
-    pub async fn get_pending_for_mods(
+    async fn get_pending_for_mods(
         ids: &[String],
         pool: &mut PgConnection,
     ) -> Result<HashMap<String, Vec<ModVersion>>, DatabaseError> {
@@ -416,9 +400,9 @@ impl ModVersion {
         Ok(ret)
     }
 
-    pub async fn get_latest_for_mod(
+    async fn get_latest_for_mod(
         id: &str,
-        gd: Option<GDVersionEnum>,
+        gd: Option<GDVersion>,
         platforms: Vec<VerPlatform>,
         major: Option<u32>,
         pool: &mut PgConnection,
@@ -438,6 +422,7 @@ impl ModVersion {
                 FROM mods m
                 INNER JOIN mod_versions mv ON m.id = mv.mod_id
                 INNER JOIN mod_gd_versions mgv ON mgv.mod_id = mv.id
+                INNER JOIN gd_versions gdv ON gdv.name = mgv.gd
                 INNER JOIN mod_version_statuses mvs ON mvs.mod_version_id = mv.id
                 WHERE mvs.status = 'accepted'"#,
         );
@@ -446,12 +431,13 @@ impl ModVersion {
             query_builder.push(" AND mv.version LIKE ");
             query_builder.push_bind(major_ver);
         }
-        if let Some(g) = gd {
-            query_builder.push(" AND (mgv.gd = ");
-            query_builder.push_bind(g);
-            query_builder.push(" OR mgv.gd = ");
-            query_builder.push_bind(GDVersionEnum::All);
-            query_builder.push(")");
+        if let Some(g) = &gd {
+            query_builder
+                .push(" AND gdv.sort_order IN (SELECT sort_order FROM gd_versions WHERE name IN (");
+            query_builder.push_bind(g.clone());
+            query_builder.push(", ");
+            query_builder.push_bind(GDVersion::all());
+            query_builder.push("))");
         }
         for (i, platform) in platforms.iter().enumerate() {
             if i == 0 {
@@ -505,7 +491,7 @@ impl ModVersion {
         Ok(Some(version))
     }
 
-    pub async fn get_one(
+    async fn get_one(
         id: &str,
         version: &str,
         fetch_extras: bool,
@@ -562,7 +548,7 @@ impl ModVersion {
         Ok(Some(version))
     }
 
-    pub async fn get_accepted_count(
+    async fn get_accepted_count(
         mod_id: &str,
         pool: &mut PgConnection,
     ) -> Result<i64, DatabaseError> {
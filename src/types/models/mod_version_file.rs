@@ -0,0 +1 @@
+pub use geode_index_types::models::mod_version_file::ModVersionFile;
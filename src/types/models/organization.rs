@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub use geode_index_types::models::organization::Organization;
+
+/// Role of a developer inside an organization. Each role includes the ones before it.
+#[derive(
+    sqlx::Type, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase", type_name = "organization_role")]
+pub enum OrganizationRole {
+    /// Can publish and manage the organization's mods
+    Member,
+    /// Acts as owner of the organization's mods and manages members
+    Admin,
+    /// Can also manage admins and other owners
+    Owner,
+}
+
+#[derive(sqlx::FromRow, Serialize, Clone, Debug, ToSchema)]
+pub struct OrganizationMember {
+    pub id: i32,
+    pub username: String,
+    pub display_name: String,
+    pub role: OrganizationRole,
+}
+
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct OrganizationProfile {
+    #[serde(flatten)]
+    pub organization: Organization,
+    pub members: Vec<OrganizationMember>,
+}
+
+/// An organization as seen from one of its members
+#[derive(sqlx::FromRow, Serialize, Clone, Debug, ToSchema)]
+pub struct DeveloperTeam {
+    pub id: i32,
+    pub name: String,
+    pub display_name: String,
+    pub role: OrganizationRole,
+}
@@ -1,11 +1,11 @@
-use crate::{database::repository::developers, endpoints::ApiError};
-use chrono::Utc;
+use crate::{config::GitHubClientData, database::repository::developers, endpoints::ApiError};
+use chrono::{NaiveDate, Utc};
 use reqwest::{Client, header::HeaderValue};
 use serde::{Deserialize, Serialize};
 use sqlx::PgConnection;
 use utoipa::ToSchema;
 
-use super::mod_entity::Mod;
+use super::mod_entity::{Mod, ModQueries};
 
 #[derive(Deserialize)]
 struct GithubReleaseAsset {
@@ -28,22 +28,40 @@ pub struct Stats {
     pub total_registered_developers: i64,
 }
 
+/// Stats as they were at the end of a day, see the snapshot-stats job
+#[derive(Deserialize, Serialize, Clone, Debug, ToSchema)]
+pub struct StatsSnapshot {
+    pub date: NaiveDate,
+    pub total_mod_count: i64,
+    pub total_mod_downloads: i64,
+    pub total_registered_developers: i64,
+    /// Mod versions waiting for review
+    pub pending_mod_versions: i64,
+    pub total_geode_downloads: i64,
+}
+
 impl Stats {
-    pub async fn get_cached(pool: &mut PgConnection) -> Result<Stats, ApiError> {
+    pub async fn get_cached(
+        github: &GitHubClientData,
+        pool: &mut PgConnection,
+    ) -> Result<Stats, ApiError> {
         let mod_stats = Mod::get_stats(&mut *pool).await?;
         Ok(Stats {
             total_mod_count: mod_stats.total_count,
             total_mod_downloads: mod_stats.total_downloads,
             total_registered_developers: developers::index_count(None, &mut *pool).await?,
-            total_geode_downloads: Self::get_latest_github_release_download_count(&mut *pool)
-                .await?,
+            total_geode_downloads: Self::get_latest_github_release_download_count(
+                github, &mut *pool,
+            )
+            .await?,
         })
     }
 
     async fn get_latest_github_release_download_count(
+        github: &GitHubClientData,
         pool: &mut PgConnection,
     ) -> Result<i64, ApiError> {
-        // If release stats were fetched less than a day ago, just use cached stats
+        // If release stats were fetched recently enough, just use cached stats
         if let Ok((cache_time, total_download_count)) = sqlx::query!(
             "SELECT s.checked_at, s.total_download_count
             FROM github_loader_release_stats s
@@ -53,13 +71,15 @@ impl Stats {
         .await
         .map(|d| (d.checked_at, d.total_download_count))
         {
-            if Utc::now().signed_duration_since(cache_time).num_days() < 1 {
+            if Utc::now().signed_duration_since(cache_time).num_hours()
+                < github.stats_cache_hours()
+            {
                 return Ok(total_download_count);
             }
         }
 
         // Fetch latest stats
-        let new = Self::fetch_github_release_stats().await?;
+        let new = Self::fetch_github_release_stats(github).await?;
         sqlx::query!(
             "INSERT INTO github_loader_release_stats (total_download_count, latest_loader_version)
             VALUES ($1, $2)",
@@ -72,10 +92,12 @@ impl Stats {
         Ok(new.0)
     }
 
-    async fn fetch_github_release_stats() -> Result<(i64, String), ApiError> {
+    async fn fetch_github_release_stats(
+        github: &GitHubClientData,
+    ) -> Result<(i64, String), ApiError> {
         let client = Client::new();
         let resp = client
-            .get("https://api.github.com/repos/geode-sdk/geode/releases")
+            .get(format!("{}/releases", github.loader_repository_url()))
             .header("Accept", HeaderValue::from_str("application/json").unwrap())
             .header("User-Agent", "geode_index")
             .query(&[("per_page", "100")])
@@ -1 +0,0 @@
-pub mod chrono_dt_secs;
\ No newline at end of file
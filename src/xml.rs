@@ -0,0 +1,9 @@
+/// Escapes text for use in XML content and attribute values.
+/// Also drops control characters, which aren't allowed anywhere in XML 1.0
+pub fn escape_xml(text: &str) -> String {
+    text.replace(|c: char| c.is_control() && !matches!(c, '\t' | '\n' | '\r'), "")
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}